@@ -0,0 +1,342 @@
+#![cfg(feature = "std")]
+
+use valuable::*;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn assert_same(a: Value<'_>, b: Value<'_>) {
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+
+    let (a, b) = (OwnedValue::from(a), OwnedValue::from(b));
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+}
+
+#[test]
+fn test_primitives() {
+    assert_same(Value::I32(1), 1.as_value());
+    assert_same(Value::String("a"), "a".to_string().as_value());
+    assert_same(Value::Bytes(b"a"), Value::Bytes(b"a"));
+    assert_same(Value::Unit, Value::Unit);
+
+    assert_ne!(Value::I32(1), Value::I32(2));
+    assert_ne!(Value::I32(1), Value::I64(1));
+    assert_ne!(Value::String("1"), Value::I32(1));
+
+    assert!(Value::I32(1) < Value::I32(2));
+    assert!(Value::String("a") < Value::String("b"));
+    // Different kinds are ordered as documented on `Value`.
+    assert!(Value::Bool(true) < Value::Char('a'));
+    assert!(Value::I32(100) < Value::U8(0));
+    assert!(Value::Isize(0) < Value::Display(&"a"));
+    assert!(Value::Display(&"a") < Value::U8(0));
+    assert!(Value::Usize(0) < Value::Listable(&[0_u8; 0]));
+    assert!(Value::Tuplable(&(1,)) < Value::Bytes(b""));
+    assert!(Value::Bytes(b"") < Value::Unit);
+}
+
+#[test]
+fn test_floats() {
+    assert_same(Value::F64(f64::NAN), Value::F64(-f64::NAN));
+    assert_same(Value::F32(0.0), Value::F32(-0.0));
+    assert_same(Value::F64(0.0), Value::F64(-0.0));
+
+    assert!(Value::F64(f64::INFINITY) < Value::F64(f64::NAN));
+    assert!(Value::F32(-1.0) < Value::F32(0.0));
+    assert_ne!(Value::F32(1.0), Value::F64(1.0));
+
+    let mut values = vec![
+        Value::F64(f64::NAN),
+        Value::F64(1.0),
+        Value::F64(f64::NEG_INFINITY),
+    ];
+    values.sort();
+    assert_eq!(format!("{:?}", values), "[-inf, 1.0, NaN]",);
+}
+
+#[test]
+fn test_lists() {
+    assert_same(vec![1, 2].as_value(), [1, 2].as_value());
+    assert_same(Vec::<u8>::new().as_value(), [0_u8; 0].as_value());
+
+    assert_ne!(vec![1, 2].as_value(), vec![1, 2, 3].as_value());
+    assert!(vec![1, 2].as_value() < vec![1, 2, 3].as_value());
+    assert!(vec![1, 3].as_value() > vec![1, 2, 3].as_value());
+}
+
+#[test]
+fn test_maps() {
+    let btree = BTreeMap::from([("a", 1), ("b", 2), ("c", 3)]);
+    let hash_map: HashMap<_, _> = btree.clone().into_iter().collect();
+    assert_same(btree.as_value(), hash_map.as_value());
+
+    let other = BTreeMap::from([("a", 1), ("b", 3)]);
+    assert_ne!(btree.as_value(), other.as_value());
+    assert!(btree.as_value() < other.as_value());
+}
+
+#[test]
+fn test_tuples() {
+    assert_same((1, "a").as_value(), (1, "a").as_value());
+    assert_ne!((1, "a").as_value(), (1, "b").as_value());
+    assert_ne!((1,).as_value(), vec![1].as_value());
+}
+
+#[test]
+fn test_dynamic_struct() {
+    struct Dynamic(&'static str, &'static [(&'static str, i32)]);
+
+    impl Valuable for Dynamic {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            // One call per field.
+            for (name, value) in self.1 {
+                visit.visit_named_fields(&NamedValues::new(
+                    &[NamedField::new(name)],
+                    &[Value::I32(*value)],
+                ));
+            }
+        }
+    }
+
+    impl Structable for Dynamic {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic(self.0, Fields::Named(&[]))
+        }
+    }
+
+    assert_same(
+        Dynamic("S", &[("a", 1), ("b", 2)]).as_value(),
+        Dynamic("S", &[("a", 1), ("b", 2)]).as_value(),
+    );
+    assert_ne!(
+        Dynamic("S", &[("a", 1)]).as_value(),
+        Dynamic("T", &[("a", 1)]).as_value(),
+    );
+    assert_ne!(
+        Dynamic("S", &[("a", 1)]).as_value(),
+        Dynamic("S", &[("b", 1)]).as_value(),
+    );
+    assert_ne!(
+        Dynamic("S", &[("a", 1)]).as_value(),
+        Dynamic("S", &[("a", 2)]).as_value(),
+    );
+}
+
+#[test]
+fn test_chunks() {
+    // A struct that passes its fields in chunks of the given size.
+    struct Chunked(&'static [(&'static str, i32)], usize);
+
+    impl Valuable for Chunked {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            for chunk in self.0.chunks(self.1) {
+                let fields: Vec<_> = chunk
+                    .iter()
+                    .map(|(name, _)| NamedField::new(name))
+                    .collect();
+                let values: Vec<_> = chunk.iter().map(|(_, value)| Value::I32(*value)).collect();
+                visit.visit_named_fields(&NamedValues::new(&fields, &values));
+            }
+        }
+    }
+
+    impl Structable for Chunked {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic("S", Fields::Named(&[]))
+        }
+    }
+
+    let fields: &[&[(&str, i32)]] = &[
+        &[],
+        &[("a", 1)],
+        &[("a", 1), ("b", 2)],
+        &[("a", 1), ("b", 3)],
+        &[("a", 1), ("b", 2), ("c", 3)],
+        &[("a", 2)],
+        &[("b", 1)],
+    ];
+
+    for a in fields {
+        for b in fields {
+            let expect =
+                OwnedValue::capture(&Chunked(a, 1)).cmp(&OwnedValue::capture(&Chunked(b, 1)));
+            for a_chunk in 1..4 {
+                for b_chunk in 1..4 {
+                    let (a, b) = (Chunked(a, a_chunk), Chunked(b, b_chunk));
+                    assert_eq!(a.as_value().cmp(&b.as_value()), expect);
+                    if expect.is_eq() {
+                        assert_eq!(hash(&a.as_value()), hash(&b.as_value()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_stops_at_first_difference() {
+    use std::cell::Cell;
+
+    // Counts the number of times it is visited.
+    struct Counted<'a>(u32, &'a Cell<usize>);
+
+    impl Valuable for Counted<'_> {
+        fn as_value(&self) -> Value<'_> {
+            Value::Tuplable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            self.1.set(self.1.get() + 1);
+            visit.visit_unnamed_fields(&[Value::U32(self.0)]);
+        }
+    }
+
+    impl Tuplable for Counted<'_> {
+        fn definition(&self) -> TupleDef {
+            TupleDef::new_static(1)
+        }
+    }
+
+    let visits = Cell::new(0);
+    let a: Vec<_> = (0..100).map(|i| Counted(i, &visits)).collect();
+    let b: Vec<_> = (0..100).map(|i| Counted(i + 1, &visits)).collect();
+
+    // Nested values are compared without being captured first.
+    assert!(a.as_value() < b.as_value());
+    assert_eq!(visits.get(), 2);
+}
+
+#[test]
+fn test_sequential_lists() {
+    use std::cell::Cell;
+
+    // A list that can only be visited from the start, and counts the number
+    // of times it is.
+    struct Sequential<'a>(u32, &'a Cell<usize>);
+
+    impl Valuable for Sequential<'_> {
+        fn as_value(&self) -> Value<'_> {
+            Value::Listable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            self.1.set(self.1.get() + 1);
+            for i in 0..self.0 {
+                if let std::ops::ControlFlow::Break(()) = visit.control_flow() {
+                    break;
+                }
+                visit.visit_value(Value::U32(i));
+            }
+        }
+    }
+
+    impl Listable for Sequential<'_> {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0 as usize, Some(self.0 as usize))
+        }
+    }
+
+    let visits = Cell::new(0);
+    let (a, b) = (Sequential(1000, &visits), Sequential(1000, &visits));
+    assert_eq!(a.as_value(), b.as_value());
+    assert!(visits.get() < 10, "{}", visits.get());
+
+    visits.set(0);
+    let c = Sequential(1001, &visits);
+    assert!(a.as_value() < c.as_value());
+    assert!(c.as_value() > b.as_value());
+    assert!(visits.get() < 20, "{}", visits.get());
+
+    // Large sets are compared in linear time.
+    let a: BTreeSet<u32> = (0..100_000).collect();
+    let mut b = a.clone();
+    assert_eq!(a.as_value(), b.as_value());
+    b.remove(&99_999);
+    b.insert(100_000);
+    assert!(a.as_value() < b.as_value());
+}
+
+#[test]
+fn test_sets() {
+    let (a, b) = (vec![3, 1], vec![1, 2]);
+    let values = [a.as_value(), b.as_value(), [3, 1].as_value(), Value::I32(0)];
+
+    let set: HashSet<_> = values.iter().copied().collect();
+    assert_eq!(set.len(), 3);
+
+    let set: BTreeSet<_> = values.iter().map(|v| OwnedValue::from(*v)).collect();
+    assert_eq!(format!("{:?}", set), "{0, [1, 2], [3, 1]}");
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Valuable)]
+    #[valuable(rename = "Point")]
+    struct Renamed {
+        x: i32,
+        #[valuable(rename = "z")]
+        y: i32,
+    }
+
+    #[derive(Valuable)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square(u32),
+    }
+
+    #[test]
+    fn test_structs() {
+        assert_same(
+            Point { x: 1, y: 2 }.as_value(),
+            Point { x: 1, y: 2 }.as_value(),
+        );
+        assert_ne!(
+            Point { x: 1, y: 2 }.as_value(),
+            Point { x: 2, y: 1 }.as_value(),
+        );
+        // Field names are compared.
+        assert_ne!(
+            Point { x: 1, y: 2 }.as_value(),
+            Renamed { x: 1, y: 2 }.as_value(),
+        );
+    }
+
+    #[test]
+    fn test_enums() {
+        assert_same(
+            Shape::Circle { radius: 1 }.as_value(),
+            Shape::Circle { radius: 1 }.as_value(),
+        );
+        assert_ne!(
+            Shape::Circle { radius: 1 }.as_value(),
+            Shape::Square(1).as_value(),
+        );
+        assert!(Shape::Square(1).as_value() < Shape::Square(2).as_value());
+        assert_same(Some(1).as_value(), 1.as_value());
+    }
+}
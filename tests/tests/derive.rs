@@ -194,6 +194,340 @@ fn test_transparent() {
     assert!(matches!(Valuable::as_value(&T('a')), Value::Char('a')));
 }
 
+#[test]
+fn test_bytes() {
+    #[derive(Valuable)]
+    struct S {
+        #[valuable(bytes)]
+        f1: Vec<u8>,
+        f2: Vec<u8>,
+    }
+
+    #[derive(Valuable)]
+    struct T(#[valuable(bytes)] [u8; 2]);
+
+    #[derive(Valuable)]
+    #[valuable(transparent)]
+    struct U(#[valuable(bytes)] &'static [u8]);
+
+    #[derive(Valuable)]
+    enum E {
+        S {
+            #[valuable(bytes)]
+            f: String,
+        },
+        T(#[valuable(bytes)] Vec<u8>),
+    }
+
+    let s = S {
+        f1: vec![0, b'a'],
+        f2: vec![0, b'a'],
+    };
+    assert_eq!(
+        format!("{:?}", s.as_value()),
+        r#"S { f1: b"\x00a", f2: [0, 97] }"#
+    );
+    assert_eq!(format!("{:?}", T([1, 2]).as_value()), r#"T(b"\x01\x02")"#);
+    assert!(matches!(U(b"u").as_value(), Value::Bytes(b"u")));
+    assert_eq!(tests::visit_counts(&U(b"u")).visit_value, 1);
+    assert_eq!(
+        format!("{:?}", E::S { f: "s".to_string() }.as_value()),
+        r#"E::S { f: b"s" }"#
+    );
+    assert_eq!(format!("{:?}", E::T(vec![]).as_value()), r#"E::T(b"")"#);
+}
+
+#[test]
+fn test_option() {
+    #[derive(Valuable)]
+    struct S {
+        #[valuable(option)]
+        f1: Option<()>,
+        f2: Option<()>,
+    }
+
+    #[derive(Valuable)]
+    struct T(#[valuable(option)] Option<u8>);
+
+    #[derive(Valuable)]
+    enum E {
+        S {
+            #[valuable(option)]
+            f: Option<String>,
+        },
+        T(#[valuable(option)] Option<u8>),
+    }
+
+    let s = S { f1: None, f2: None };
+    assert_eq!(
+        format!("{:?}", s.as_value()),
+        "S { f1: Option::None, f2: () }"
+    );
+    let s = S {
+        f1: Some(()),
+        f2: Some(()),
+    };
+    assert_eq!(
+        format!("{:?}", s.as_value()),
+        "S { f1: Option::Some(()), f2: () }"
+    );
+    // The enum only exists during a visit.
+    assert!(s.field("f1").is_none());
+    assert!(s.field_at(0).is_none());
+    assert!(matches!(s.field("f2"), Some(Value::Tuplable(_))));
+
+    assert_eq!(format!("{:?}", T(Some(1)).as_value()), "T(Option::Some(1))");
+    assert_eq!(
+        format!("{:?}", E::S { f: None }.as_value()),
+        "E::S { f: Option::None }"
+    );
+    assert_eq!(
+        format!("{:?}", E::T(Some(2)).as_value()),
+        "E::T(Option::Some(2))"
+    );
+}
+
+#[test]
+fn test_chunk_size() {
+    #[derive(Valuable)]
+    #[valuable(chunk_size = 2)]
+    struct S {
+        f1: u8,
+        f2: u8,
+        #[valuable(skip)]
+        skipped: u8,
+        f3: u8,
+        f4: u8,
+        f5: u8,
+    }
+
+    #[derive(Valuable)]
+    #[valuable(chunk_size = 1)]
+    struct One {
+        f1: u8,
+        f2: &'static str,
+    }
+
+    #[derive(Valuable)]
+    #[valuable(chunk_size = 2)]
+    struct T(u8, u8, u8);
+
+    #[derive(Valuable)]
+    #[valuable(chunk_size = 2)]
+    struct Empty {}
+
+    // Records the calls made by a struct, and breaks after `limit` calls.
+    #[derive(Default)]
+    struct Calls {
+        calls: Vec<String>,
+        limit: Option<usize>,
+    }
+
+    impl Visit for Calls {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(v) = value {
+                v.visit(self);
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            let names: Vec<_> = named_values.iter().map(|(f, _)| f.name()).collect();
+            self.calls.push(format!("named {:?}", names));
+        }
+
+        fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+            self.calls.push(format!("unnamed {:?}", values));
+        }
+
+        fn control_flow(&self) -> std::ops::ControlFlow<()> {
+            match self.limit {
+                Some(limit) if self.calls.len() >= limit => std::ops::ControlFlow::Break(()),
+                _ => std::ops::ControlFlow::Continue(()),
+            }
+        }
+    }
+
+    let s = S {
+        f1: 1,
+        f2: 2,
+        skipped: 0,
+        f3: 3,
+        f4: 4,
+        f5: 5,
+    };
+    assert_eq!(
+        format!("{:?}", s.as_value()),
+        "S { f1: 1, f2: 2, f3: 3, f4: 4, f5: 5 }"
+    );
+
+    let mut calls = Calls::default();
+    valuable::visit(&s, &mut calls);
+    assert_eq!(
+        calls.calls,
+        [
+            r#"named ["f1", "f2"]"#,
+            r#"named ["f3", "f4"]"#,
+            r#"named ["f5"]"#,
+        ]
+    );
+
+    let mut calls = Calls {
+        limit: Some(1),
+        ..Calls::default()
+    };
+    valuable::visit(&s, &mut calls);
+    assert_eq!(calls.calls, [r#"named ["f1", "f2"]"#]);
+
+    // Static fields can be looked up in the chunk containing them.
+    struct Get<'a>(&'a NamedField<'a>, Vec<u8>);
+
+    impl Visit for Get<'_> {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(v) = value {
+                v.visit(self);
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            if let Some(v) = named_values.get(self.0) {
+                self.1.push(v.as_u8().unwrap());
+            }
+        }
+    }
+
+    let def = s.definition();
+    let fields = match def.fields() {
+        Fields::Named(fields) => fields,
+        _ => unreachable!(),
+    };
+    assert_eq!(fields[3].name(), "f4");
+    let mut get = Get(&fields[3], vec![]);
+    valuable::visit(&s, &mut get);
+    assert_eq!(get.1, [4]);
+
+    let one = One { f1: 1, f2: "a" };
+    let mut calls = Calls::default();
+    valuable::visit(&one, &mut calls);
+    assert_eq!(calls.calls, [r#"named ["f1"]"#, r#"named ["f2"]"#]);
+    assert_eq!(format!("{:?}", one.as_value()), r#"One { f1: 1, f2: "a" }"#);
+
+    let mut calls = Calls::default();
+    valuable::visit(&T(1, 2, 3), &mut calls);
+    assert_eq!(calls.calls, ["unnamed [1, 2]", "unnamed [3]"]);
+    assert_eq!(format!("{:?}", T(1, 2, 3).as_value()), "T(1, 2, 3)");
+
+    let mut calls = Calls::default();
+    valuable::visit(&Empty {}, &mut calls);
+    assert_eq!(calls.calls, ["named []"]);
+}
+
+#[test]
+fn test_field() {
+    struct NotValuable;
+
+    #[derive(Valuable)]
+    struct S {
+        f1: u8,
+        #[valuable(skip)]
+        f2: NotValuable,
+        #[valuable(rename = "renamed")]
+        f3: &'static str,
+        #[valuable(bytes)]
+        f4: Vec<u8>,
+        f5: Vec<u8>,
+    }
+
+    #[derive(Valuable)]
+    struct T(#[valuable(skip)] NotValuable, String, ());
+
+    #[derive(Valuable)]
+    struct U;
+
+    let s = S {
+        f1: 1,
+        f2: NotValuable,
+        f3: "three",
+        f4: vec![4],
+        f5: vec![5],
+    };
+    let structable: &dyn Structable = &s;
+    assert!(matches!(structable.field("f1"), Some(Value::U8(1))));
+    assert!(structable.field("f2").is_none());
+    assert!(structable.field("f3").is_none());
+    assert!(matches!(
+        structable.field("renamed"),
+        Some(Value::String("three"))
+    ));
+    assert!(matches!(structable.field("f4"), Some(Value::Bytes([4]))));
+    assert!(matches!(structable.field("f5"), Some(Value::Listable(_))));
+    assert!(matches!(
+        structable.field_at(1),
+        Some(Value::String("three"))
+    ));
+    assert!(structable.field_at(4).is_none());
+
+    let t = T(NotValuable, "one".to_string(), ());
+    assert!(t.field("0").is_none());
+    assert!(matches!(t.field_at(0), Some(Value::String("one"))));
+    assert!(matches!(t.field_at(1), Some(Value::Tuplable(_))));
+    assert!(t.field_at(2).is_none());
+
+    assert!(U.field("f").is_none());
+    assert!(U.field_at(0).is_none());
+}
+
+#[test]
+fn test_downcast() {
+    #[derive(Valuable, Debug, PartialEq)]
+    struct RequestId(u64);
+
+    #[derive(Valuable, Debug, PartialEq)]
+    enum Kind {
+        Get,
+        Post { body: String },
+    }
+
+    #[derive(Valuable)]
+    struct Generic<T: Valuable> {
+        inner: T,
+    }
+
+    #[derive(Valuable)]
+    struct Borrowed<'a> {
+        name: &'a str,
+    }
+
+    let id = RequestId(7);
+    assert_eq!(id.as_value().downcast_ref::<RequestId>(), Some(&id));
+    assert!(id.as_value().downcast_ref::<Kind>().is_none());
+
+    // Through references and smart pointers.
+    let boxed = Box::new(&id);
+    assert_eq!(boxed.as_value().downcast_ref::<RequestId>(), Some(&id));
+
+    let kind = Kind::Post {
+        body: "x".to_string(),
+    };
+    assert_eq!(kind.as_value().downcast_ref::<Kind>(), Some(&kind));
+    assert!(Kind::Get.as_any().is_some());
+
+    // Generic types do not opt in.
+    let generic = Generic { inner: 1 };
+    assert!(generic.as_value().downcast_ref::<Generic<i32>>().is_none());
+    assert!(Borrowed { name: "a" }.as_any().is_none());
+
+    // Transparent structs are represented by their field.
+    #[derive(Valuable)]
+    #[valuable(transparent)]
+    struct Wrapper(RequestId);
+
+    let wrapper = Wrapper(RequestId(8));
+    assert_eq!(
+        wrapper.as_value().downcast_ref::<RequestId>(),
+        Some(&RequestId(8))
+    );
+}
+
 #[rustversion::attr(not(stable), ignore)]
 #[test]
 fn ui() {
@@ -0,0 +1,230 @@
+#![cfg(feature = "std")]
+
+use valuable::diff::{diff, ChangeKind};
+use valuable::path::OwnedSegment;
+use valuable::*;
+
+use std::collections::BTreeMap;
+
+#[test]
+fn test_equal() {
+    let d = diff(&vec![1, 2, 3], &vec![1, 2, 3]);
+    assert!(d.is_empty());
+    assert_eq!(d.to_string(), "");
+    assert_eq!(d.to_json_patch(), "[]");
+}
+
+#[test]
+fn test_primitive() {
+    let d = diff(&1, &2);
+    assert_eq!(d.changes().len(), 1);
+
+    let change = &d.changes()[0];
+    assert_eq!(change.kind(), ChangeKind::Changed);
+    assert!(change.location().is_root());
+    assert_eq!(change.old_value(), Some(&OwnedValue::I32(1)));
+    assert_eq!(change.new_value(), Some(&OwnedValue::I32(2)));
+
+    assert_eq!(d.to_string(), "~ .: 1 -> 2\n");
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"replace","path":"","value":2}]"#
+    );
+
+    let d = diff(&1, &"one");
+    assert_eq!(d.to_string(), "~ .: 1 -> \"one\"\n");
+}
+
+#[test]
+fn test_list() {
+    let d = diff(&vec![1, 2, 3, 4], &vec![1, 5]);
+    assert_eq!(d.to_string(), "~ [1]: 2 -> 5\n- [3]: 4\n- [2]: 3\n");
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"replace","path":"/1","value":5},{"op":"remove","path":"/3"},{"op":"remove","path":"/2"}]"#,
+    );
+
+    let d = diff(&vec![1], &vec![1, 2, 3]);
+    assert_eq!(d.to_string(), "+ [1]: 2\n+ [2]: 3\n");
+    assert_eq!(
+        d.changes()
+            .iter()
+            .map(|change| change.kind())
+            .collect::<Vec<_>>(),
+        [ChangeKind::Added, ChangeKind::Added],
+    );
+    assert_eq!(
+        d.changes()[0].location().segments(),
+        [OwnedSegment::Element(1)]
+    );
+}
+
+#[test]
+fn test_map() {
+    let old = BTreeMap::from([("a", 1), ("b", 2), ("c/d", 3)]);
+    let new = BTreeMap::from([("b", 2), ("c/d", 4), ("e~", 5)]);
+
+    let d = diff(&old, &new);
+    assert_eq!(
+        d.to_string(),
+        "- [\"a\"]: 1\n~ [\"c/d\"]: 3 -> 4\n+ [\"e~\"]: 5\n"
+    );
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"remove","path":"/a"},{"op":"replace","path":"/c~1d","value":4},{"op":"add","path":"/e~0","value":5}]"#,
+    );
+
+    let d = diff(&BTreeMap::from([(1, "x")]), &BTreeMap::from([(1, "y")]));
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"replace","path":"/1","value":"y"}]"#
+    );
+}
+
+#[test]
+fn test_tuple() {
+    let d = diff(&(1, "a", true), &(1, "b", true));
+    assert_eq!(d.to_string(), "~ .1: \"a\" -> \"b\"\n");
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"replace","path":"/1","value":"b"}]"#
+    );
+
+    let d = diff(&(), &(1,));
+    assert_eq!(
+        d.to_json_patch(),
+        r#"[{"op":"replace","path":"","value":[1]}]"#
+    );
+}
+
+#[test]
+fn test_json_values() {
+    let d = diff(
+        &vec![OwnedValue::Unit],
+        &vec![
+            OwnedValue::F64(1.5),
+            OwnedValue::F64(f64::NAN),
+            OwnedValue::Char('"'),
+            OwnedValue::String("a\nb\u{1}".to_string()),
+            OwnedValue::Bytes(vec![1, 2]),
+        ],
+    );
+    assert_eq!(
+        d.to_json_patch(),
+        concat!(
+            r#"[{"op":"replace","path":"/0","value":1.5},"#,
+            r#"{"op":"add","path":"/1","value":null},"#,
+            r#"{"op":"add","path":"/2","value":"\""},"#,
+            r#"{"op":"add","path":"/3","value":"a\nb\u0001"},"#,
+            r#"{"op":"add","path":"/4","value":[1,2]}]"#,
+        ),
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Config {
+        name: &'static str,
+        port: u16,
+        tags: Vec<&'static str>,
+        mode: Mode,
+    }
+
+    #[derive(Valuable)]
+    enum Mode {
+        Off,
+        Fixed(u32),
+        Range { min: u32, max: u32 },
+        Pair(u32, u32),
+    }
+
+    #[derive(Valuable)]
+    struct Id(u64);
+
+    #[derive(Valuable)]
+    struct Wrapper {
+        id: Id,
+    }
+
+    fn config(mode: Mode) -> Config {
+        Config {
+            name: "web",
+            port: 80,
+            tags: vec!["a"],
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_struct() {
+        let old = config(Mode::Off);
+        let new = Config {
+            port: 8080,
+            tags: vec!["a", "b"],
+            ..config(Mode::Off)
+        };
+
+        let d = diff(&old, &new);
+        assert_eq!(d.to_string(), "~ .port: 80 -> 8080\n+ .tags[1]: \"b\"\n");
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/port","value":8080},{"op":"add","path":"/tags/1","value":"b"}]"#,
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        let d = diff(
+            &config(Mode::Range { min: 1, max: 2 }),
+            &config(Mode::Range { min: 1, max: 3 }),
+        );
+        assert_eq!(d.to_string(), "~ .mode::Range.max: 2 -> 3\n");
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/mode/Range/max","value":3}]"#,
+        );
+
+        let d = diff(&Mode::Fixed(1), &Mode::Fixed(2));
+        assert_eq!(d.to_string(), "~ ::Fixed.0: 1 -> 2\n");
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/Fixed","value":2}]"#,
+        );
+
+        let d = diff(&Mode::Pair(1, 2), &Mode::Pair(1, 3));
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/Pair/1","value":3}]"#,
+        );
+    }
+
+    #[test]
+    fn test_enum_variant_changed() {
+        let d = diff(&config(Mode::Off), &config(Mode::Fixed(1)));
+        assert_eq!(d.changes().len(), 1);
+        assert_eq!(d.changes()[0].kind(), ChangeKind::Changed);
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/mode","value":{"Fixed":1}}]"#,
+        );
+
+        let d = diff(&Mode::Fixed(1), &Mode::Off);
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"","value":{"Off":[]}}]"#,
+        );
+    }
+
+    #[test]
+    fn test_newtype() {
+        let d = diff(&Wrapper { id: Id(1) }, &Wrapper { id: Id(2) });
+        assert_eq!(d.to_string(), "~ .id.0: 1 -> 2\n");
+        assert_eq!(
+            d.to_json_patch(),
+            r#"[{"op":"replace","path":"/id","value":2}]"#,
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use valuable::error::ErrorView;
+use valuable::*;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+struct Outer(Middle);
+
+#[derive(Debug)]
+struct Middle(io::Error);
+
+impl fmt::Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl Error for Outer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl fmt::Display for Middle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("middle")
+    }
+}
+
+impl Error for Middle {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn outer() -> Outer {
+    Outer(Middle(io::Error::new(io::ErrorKind::NotFound, "inner")))
+}
+
+#[test]
+fn test_boxed_send_sync() {
+    let err: Box<dyn Error + Send + Sync> = Box::new(outer());
+    assert!(matches!(err.as_value(), Value::Error(_)));
+    assert_eq!(
+        err.as_value().as_error().unwrap().to_string(),
+        err.to_string()
+    );
+
+    let err: Box<dyn Error + Send> = Box::new(outer());
+    assert!(matches!(err.as_value(), Value::Error(_)));
+
+    let err: &(dyn Error + Send + Sync) = &outer();
+    let value = err.as_value();
+    let source = value.as_error().unwrap().source().unwrap();
+    assert_eq!(source.to_string(), "middle");
+}
+
+#[test]
+fn test_view() {
+    let err = outer();
+    let view = ErrorView::new(&err);
+
+    assert_eq!(
+        format!("{:?}", view.as_value()),
+        r#"Error { message: outer, type_name: "error::Outer", sources: [middle, inner] }"#
+    );
+    assert_eq!(view.definition().name(), "Error");
+    assert!(matches!(view.field_at(0), Some(Value::Display(_))));
+    assert!(matches!(view.field("type_name"), Some(Value::String(_))));
+    assert!(view.field_at(3).is_none());
+
+    match view.field("sources") {
+        Some(Value::Listable(v)) => assert_eq!(v.size_hint(), (2, Some(2))),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_sources_break() {
+    struct Stopped(usize);
+
+    impl Visit for Stopped {
+        fn visit_value(&mut self, _: Value<'_>) {
+            self.0 += 1;
+        }
+
+        fn control_flow(&self) -> std::ops::ControlFlow<()> {
+            std::ops::ControlFlow::Break(())
+        }
+    }
+
+    let err = outer();
+    let view = ErrorView::new(&err);
+    let mut visit = Stopped(0);
+    match view.field("sources") {
+        Some(Value::Listable(v)) => v.visit(&mut visit),
+        _ => unreachable!(),
+    }
+    assert_eq!(visit.0, 0);
+}
+
+#[test]
+fn test_view_from_dyn() {
+    let err: Box<dyn Error + Send + Sync> = "no sources".into();
+    let view = ErrorView::from_dyn(&*err);
+
+    assert_eq!(
+        format!("{:?}", view.as_value()),
+        "Error { message: no sources, type_name: (), sources: [] }"
+    );
+    assert!(ptr_eq(view.error(), &*err));
+}
+
+fn ptr_eq(a: &dyn Error, b: &dyn Error) -> bool {
+    std::ptr::eq(
+        a as *const dyn Error as *const u8,
+        b as *const dyn Error as *const u8,
+    )
+}
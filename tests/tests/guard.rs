@@ -0,0 +1,215 @@
+use valuable::guard::{Guard, Guarded, Marker};
+use valuable::*;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// A list which may contain itself.
+struct Node(RefCell<Vec<Rc<Node>>>);
+
+impl Node {
+    fn new() -> Rc<Node> {
+        Rc::new(Node(RefCell::new(vec![])))
+    }
+}
+
+impl Valuable for Node {
+    fn as_value(&self) -> Value<'_> {
+        Value::Listable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for node in self.0.borrow().iter() {
+            visit.visit_value(node.as_value());
+        }
+    }
+}
+
+impl Listable for Node {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.borrow().len();
+        (len, Some(len))
+    }
+}
+
+/// Records visited values, descending into lists.
+#[derive(Default)]
+struct Record(Vec<String>);
+
+impl Visit for Record {
+    fn visit_value(&mut self, value: Value<'_>) {
+        match value {
+            Value::Listable(v) => {
+                self.0.push("list".to_string());
+                v.visit(self);
+            }
+            value => self.0.push(format!("{:?}", value)),
+        }
+    }
+}
+
+#[test]
+fn test_cycle() {
+    let a = Node::new();
+    let b = Node::new();
+    a.0.borrow_mut().push(b.clone());
+    b.0.borrow_mut().push(a.clone());
+
+    assert_eq!(
+        format!("{:?}", Guarded::new(&a).as_value()),
+        "[[Marker::Cycle]]"
+    );
+
+    let mut guard = Guard::new(Record::default());
+    guard.visit_value(a.as_value());
+    assert_eq!(guard.get_ref().0, ["list", "list", "Marker::Cycle"]);
+
+    // Without cycle detection, the depth limit still applies.
+    let guarded = Guarded::new(&a).detect_cycles(false).max_depth(3);
+    assert_eq!(
+        format!("{:?}", guarded.as_value()),
+        "[[[Marker::MaxDepth]]]"
+    );
+
+    a.0.borrow_mut().clear();
+}
+
+#[test]
+fn test_marker() {
+    assert_eq!(format!("{:?}", Marker::Cycle.as_value()), "Marker::Cycle");
+    assert_eq!(
+        format!("{:?}", Marker::MaxDepth.as_value()),
+        "Marker::MaxDepth"
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    let value = vec![vec![vec![1]], vec![]];
+
+    assert_eq!(
+        format!("{:?}", Guarded::new(&value).max_depth(2).as_value()),
+        "[[Marker::MaxDepth], []]"
+    );
+    assert_eq!(
+        format!("{:?}", Guarded::new(&value).max_depth(3).as_value()),
+        "[[[1]], []]"
+    );
+    assert_eq!(
+        format!("{:?}", Guarded::new(&value).max_depth(0).as_value()),
+        "Marker::MaxDepth"
+    );
+
+    // Primitive values are not limited.
+    assert_eq!(
+        format!("{:?}", Guarded::new(&1).max_depth(0).as_value()),
+        "1"
+    );
+
+    let mut guard = Guard::new(Record::default()).max_depth(1);
+    guard.visit_value(value.as_value());
+    assert_eq!(
+        guard.into_inner().0,
+        ["list", "Marker::MaxDepth", "Marker::MaxDepth"]
+    );
+}
+
+#[test]
+fn test_map_and_tuple() {
+    let map = BTreeMap::from([((1, 2), vec![(3, 4)])]);
+    assert_eq!(
+        format!("{:?}", Guarded::new(&map).max_depth(2).as_value()),
+        "{(1, 2): [Marker::MaxDepth]}"
+    );
+}
+
+#[test]
+fn test_no_false_positives() {
+    // Nested values sharing the address of their parent are not cycles.
+    let value = [[[1_u8]]];
+    assert_eq!(format!("{:?}", Guarded::new(&value).as_value()), "[[[1]]]");
+
+    let shared = vec![1];
+    let value = vec![&shared, &shared];
+    assert_eq!(
+        format!("{:?}", Guarded::new(&value).as_value()),
+        "[[1], [1]]"
+    );
+}
+
+#[test]
+fn test_many_fields() {
+    // More fields than are wrapped on the stack.
+    let value: Vec<_> = (0..20).map(|i| (i, vec![i])).collect();
+    let tuple = (
+        vec![0],
+        vec![1],
+        vec![2],
+        vec![3],
+        vec![4],
+        vec![5],
+        vec![6],
+        vec![7],
+        vec![8],
+        vec![9],
+        vec![10],
+        vec![11],
+    );
+
+    let items: Vec<_> = (0..20)
+        .map(|i| format!("({}, Marker::MaxDepth)", i))
+        .collect();
+    assert_eq!(
+        format!("{:?}", Guarded::new(&value).max_depth(2).as_value()),
+        format!("[{}]", items.join(", "))
+    );
+    assert_eq!(
+        format!("{:?}", Guarded::new(&tuple).as_value()),
+        format!("{:?}", tuple)
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Outer {
+        id: Id,
+        inner: Inner,
+    }
+
+    #[derive(Valuable)]
+    struct Id(u32);
+
+    #[derive(Valuable)]
+    struct Inner {
+        values: Vec<u32>,
+    }
+
+    #[test]
+    fn test_struct() {
+        let value = Outer {
+            id: Id(1),
+            inner: Inner { values: vec![2] },
+        };
+
+        // Newtypes share the address of their field.
+        assert_eq!(
+            format!("{:?}", Guarded::new(&value).as_value()),
+            "Outer { id: Id(1), inner: Inner { values: [2] } }"
+        );
+        assert_eq!(
+            format!("{:?}", Guarded::new(&value).max_depth(2).as_value()),
+            "Outer { id: Id(1), inner: Inner { values: Marker::MaxDepth } }"
+        );
+
+        let guarded = Guarded::new(&value).max_depth(2);
+        let fields = match guarded.as_value() {
+            Value::Structable(v) => v,
+            _ => unreachable!(),
+        };
+        assert_eq!(fields.definition().name(), "Outer");
+    }
+}
@@ -0,0 +1,160 @@
+use valuable::limit::Limited;
+use valuable::*;
+
+use std::collections::{BTreeMap, VecDeque};
+
+fn fmt(limited: Limited<'_>) -> String {
+    format!("{:?}", limited.as_value())
+}
+
+#[test]
+fn test_max_items() {
+    let values: Vec<u32> = (0..1_000_000).collect();
+    assert_eq!(
+        fmt(Limited::new(&values).max_items(2)),
+        r#"[0, 1, "… 999,998 more"]"#
+    );
+    assert_eq!(fmt(Limited::new(&&values[..3]).max_items(3)), "[0, 1, 2]");
+    assert_eq!(
+        fmt(Limited::new(&values).max_items(0)),
+        r#"["… 1,000,000 more"]"#
+    );
+
+    // Lists which visit their elements one by one.
+    let deque: VecDeque<_> = (0..5).map(|i| vec![i]).collect();
+    assert_eq!(
+        fmt(Limited::new(&deque).max_items(1)),
+        r#"[[0], "… 4 more"]"#
+    );
+
+    let map: BTreeMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+    assert_eq!(
+        fmt(Limited::new(&map).max_items(2)),
+        r#"{0: 0, 1: 10, "… 3 more": ()}"#
+    );
+}
+
+#[test]
+fn test_max_str_len() {
+    let s = "hello world";
+    assert_eq!(
+        fmt(Limited::new(&s).max_str_len(5)),
+        r#""hello… 6 more bytes""#
+    );
+    assert_eq!(fmt(Limited::new(&s).max_str_len(11)), r#""hello world""#);
+
+    // Strings are cut at character boundaries.
+    let s = "héllo";
+    assert_eq!(fmt(Limited::new(&s).max_str_len(2)), r#""h… 5 more bytes""#);
+
+    let args = format_args!("{}", "formatted");
+    assert_eq!(
+        fmt(Limited::new(&args).max_str_len(4)),
+        r#""form… 5 more bytes""#
+    );
+    assert_eq!(fmt(Limited::new(&args).max_str_len(9)), "formatted");
+
+    let strings = vec!["abc", "de"];
+    assert_eq!(
+        fmt(Limited::new(&strings).max_str_len(2)),
+        r#"["ab… 1 more bytes", "de"]"#
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    let value = vec![vec![vec![1]], vec![]];
+    assert_eq!(fmt(Limited::new(&value).max_depth(2)), r#"[["…"], []]"#);
+    assert_eq!(fmt(Limited::new(&value).max_depth(0)), r#""…""#);
+    assert_eq!(fmt(Limited::new(&1).max_depth(0)), "1");
+}
+
+#[test]
+fn test_max_bytes() {
+    let values: Vec<u32> = (0..1_000).collect();
+    assert_eq!(
+        fmt(Limited::new(&values).max_bytes(12)),
+        r#"[0, 1, 2, 3, "… 996 more"]"#
+    );
+
+    let tuple = (1, "two", 3);
+    assert_eq!(fmt(Limited::new(&tuple).max_bytes(8)), r#"(1, "…", "…")"#);
+
+    // Once a value is omitted, smaller values are omitted as well.
+    let strings = vec!["a", "long string", "b"];
+    assert_eq!(
+        fmt(Limited::new(&strings).max_bytes(10)),
+        r#"["a", "… 2 more"]"#
+    );
+}
+
+#[test]
+fn test_size_hint() {
+    let values: Vec<u32> = (0..100).collect();
+    let limited = Limited::new(&values).max_items(10);
+    match limited.as_value() {
+        Value::Listable(v) => assert_eq!(v.size_hint(), (0, Some(11))),
+        _ => unreachable!(),
+    }
+
+    let limited = Limited::new(&values).max_items(1_000);
+    match limited.as_value() {
+        Value::Listable(v) => assert_eq!(v.size_hint(), (0, Some(100))),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_many_fields() {
+    let tuple = (
+        "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "long",
+    );
+    assert_eq!(
+        fmt(Limited::new(&tuple).max_str_len(2)),
+        r#"("a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "lo… 2 more bytes")"#
+    );
+
+    let values: Vec<_> = (0..20).map(|i| vec![i; 3]).collect();
+    let limited = Limited::new(&values).max_items(1);
+    assert_eq!(fmt(limited), r#"[[0, "… 2 more"], "… 19 more"]"#);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Request {
+        path: String,
+        headers: Vec<(&'static str, &'static str)>,
+        body: Vec<u8>,
+    }
+
+    #[test]
+    fn test_struct() {
+        let request = Request {
+            path: "/".repeat(100),
+            headers: vec![("host", "example.com"), ("accept", "*/*")],
+            body: vec![0; 4096],
+        };
+
+        let limited = Limited::new(&request)
+            .max_items(1)
+            .max_str_len(3)
+            .max_depth(2);
+        assert_eq!(
+            fmt(limited),
+            concat!(
+                r#"Request { path: "///… 97 more bytes", "#,
+                r#"headers: ["…", "… 1 more"], "#,
+                r#"body: [0, "… 4,095 more"] }"#
+            )
+        );
+
+        let limited = Limited::new(&request).max_bytes(20);
+        assert_eq!(
+            fmt(limited),
+            r#"Request { path: "…", headers: "…", body: "…" }"#
+        );
+    }
+}
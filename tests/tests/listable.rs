@@ -181,7 +181,7 @@ test_default! {
 macro_rules! test_primitive {
     (
         $(
-            $name:ident, $variant:ident(Value::$vvariant:ident): $ty:ty => |$x:ident| $b:block;
+            $name:ident, $variant:ident(Value::$vvariant:ident $(($by_ref:tt))?): $ty:ty => |$x:ident| $b:block;
         )*
     ) => {
         $(
@@ -194,7 +194,7 @@ macro_rules! test_primitive {
 
                     loop {
                         match (i.next(), expect.next()) {
-                            (Some(Value::$vvariant(actual)), Some(expect)) => {
+                            (Some(Value::$vvariant($($by_ref)? actual)), Some(expect)) => {
                                 // When testing floating-point values, the
                                 // actual value will be the exact same float
                                 // value as the expected, if everything is
@@ -288,7 +288,7 @@ test_primitive! {
     test_i16, I16(Value::I16): i16 => |x| { x as i16 };
     test_i32, I32(Value::I32): i32 => |x| { x as i32 };
     test_i64, I64(Value::I64): i64 => |x| { x as i64 };
-    test_i128, I128(Value::I128): i128 => |x| { x as i128 };
+    test_i128, I128(Value::I128(&)): i128 => |x| { x as i128 };
     test_isize, Isize(Value::Isize): isize => |x| { x as isize };
     test_str, Str(Value::String): &'static str => |x| { crate::leak(format!("{}", x)) };
     test_string, String(Value::String): String => |x| { format!("{}", x) };
@@ -296,7 +296,7 @@ test_primitive! {
     test_u16, U16(Value::U16): u16 => |x| { x as u16 };
     test_u32, U32(Value::U32): u32 => |x| { x as u32 };
     test_u64, U64(Value::U64): u64 => |x| { x as u64 };
-    test_u128, U128(Value::U128): u128 => |x| { x as u128 };
+    test_u128, U128(Value::U128(&)): u128 => |x| { x as u128 };
     test_usize, Usize(Value::Usize): usize => |x| { x as usize };
     // test_unit, Unit: () => |_x| { () };
 }
@@ -304,3 +304,193 @@ test_primitive! {
 fn leak(s: String) -> &'static str {
     Box::leak(s.into_boxed_str())
 }
+
+/// Records the visited values, and the number of primitive slices.
+#[derive(Default)]
+struct Collect {
+    values: Vec<String>,
+    slices: usize,
+}
+
+impl Visit for Collect {
+    fn visit_value(&mut self, value: Value<'_>) {
+        self.values.push(format!("{:?}", value));
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        self.slices += 1;
+        for value in slice {
+            self.visit_value(value);
+        }
+    }
+}
+
+fn collect_range(listable: &dyn Listable, range: std::ops::Range<usize>) -> Collect {
+    let mut visit = Collect::default();
+    listable.visit_range(range, &mut visit);
+    visit
+}
+
+/// A list stored in chunks, which relies on the default `get` and
+/// `visit_range` implementations.
+struct Chunked<T>(Vec<Vec<T>>);
+
+impl<T: Valuable> Valuable for Chunked<T> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Listable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for chunk in &self.0 {
+            Valuable::visit_slice(chunk, visit);
+        }
+    }
+}
+
+impl<T: Valuable> Listable for Chunked<T> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.iter().map(Vec::len).sum();
+        (len, Some(len))
+    }
+}
+
+#[test]
+fn test_get() {
+    let vec = vec!["a", "b", "c"];
+    assert!(matches!(Listable::get(&vec, 2), Some(Value::String("c"))));
+    assert!(Listable::get(&vec, 3).is_none());
+    assert!(matches!(
+        Listable::get(&&vec[1..], 0),
+        Some(Value::String("b"))
+    ));
+    assert!(matches!(Listable::get(&[1, 2], 1), Some(Value::I32(2))));
+
+    let mut deque = std::collections::VecDeque::new();
+    deque.push_back(2);
+    deque.push_front(1);
+    assert!(matches!(Listable::get(&deque, 0), Some(Value::I32(1))));
+    assert!(matches!(Listable::get(&deque, 1), Some(Value::I32(2))));
+    assert!(Listable::get(&deque, 2).is_none());
+
+    let boxed: Box<dyn Listable> = Box::new(vec!["x".to_string()]);
+    assert!(matches!(boxed.get(0), Some(Value::String("x"))));
+
+    // Default implementation
+    let set = std::collections::BTreeSet::from([10, 20, 30]);
+    assert!(matches!(Listable::get(&set, 1), Some(Value::I32(20))));
+    assert!(Listable::get(&set, 3).is_none());
+
+    let chunked = Chunked(vec![vec![1_u8, 2], vec![], vec![3, 4]]);
+    assert!(matches!(chunked.get(0), Some(Value::U8(1))));
+    assert!(matches!(chunked.get(2), Some(Value::U8(3))));
+    assert!(chunked.get(4).is_none());
+
+    // Borrowed values cannot be returned by the default implementation.
+    let set = std::collections::BTreeSet::from(["a"]);
+    assert!(Listable::get(&set, 0).is_none());
+}
+
+#[test]
+fn test_visit_range() {
+    let vec: Vec<u32> = (0..100).collect();
+
+    let visit = collect_range(&vec, 10..13);
+    assert_eq!(visit.values, ["10", "11", "12"]);
+    assert_eq!(visit.slices, 1);
+
+    assert_eq!(collect_range(&vec, 98..200).values, ["98", "99"]);
+    assert!(collect_range(&vec, 150..200).values.is_empty());
+    #[allow(clippy::reversed_empty_ranges)]
+    let empty = collect_range(&vec, 5..2);
+    assert!(empty.values.is_empty());
+
+    let strings = ["a", "b", "c"];
+    assert_eq!(collect_range(&strings, 1..2).values, [r#""b""#]);
+
+    // Wrapped `VecDeque`
+    let mut deque = std::collections::VecDeque::with_capacity(4);
+    deque.extend([2, 3]);
+    deque.push_front(1);
+    deque.push_front(0);
+    assert_eq!(collect_range(&deque, 0..4).values, ["0", "1", "2", "3"]);
+    assert_eq!(collect_range(&deque, 1..3).values, ["1", "2"]);
+    assert_eq!(collect_range(&deque, 3..10).values, ["3"]);
+
+    // Default implementation
+    let set: std::collections::BTreeSet<_> = (0..10).collect();
+    assert_eq!(collect_range(&set, 8..20).values, ["8", "9"]);
+
+    let chunked = Chunked(vec![vec![1_u8, 2, 3], vec![4, 5], vec![6]]);
+    let visit = collect_range(&chunked, 2..5);
+    assert_eq!(visit.values, ["3", "4", "5"]);
+    assert_eq!(visit.slices, 2);
+}
+
+#[test]
+fn test_visit_range_stops_after_end() {
+    let counts = std::cell::Cell::new(0);
+
+    struct Counting<'a>(&'a std::cell::Cell<usize>);
+
+    impl Valuable for Counting<'_> {
+        fn as_value(&self) -> Value<'_> {
+            self.0.set(self.0.get() + 1);
+            Value::Unit
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_value(self.as_value());
+        }
+    }
+
+    let set: std::collections::LinkedList<_> = (0..10).map(|_| Counting(&counts)).collect();
+    let visit = collect_range(&set, 2..4);
+    assert_eq!(visit.values.len(), 2);
+    assert_eq!(counts.get(), 4);
+}
+
+#[test]
+fn test_as_slice() {
+    let vec = vec![1_u32, 2, 3];
+    assert!(matches!(
+        Listable::as_slice(&vec),
+        Some(Slice::U32([1, 2, 3]))
+    ));
+    assert!(matches!(
+        Listable::as_slice(&[true, false]),
+        Some(Slice::Bool([true, false]))
+    ));
+    assert!(matches!(
+        Listable::as_slice(&&["a", "b"][..]),
+        Some(Slice::Str(["a", "b"]))
+    ));
+
+    let strings = vec!["a".to_string()];
+    match Listable::as_slice(&strings) {
+        Some(Slice::String(s)) => assert_eq!(s, ["a"]),
+        _ => panic!(),
+    }
+
+    let boxed: Box<dyn Listable> = Box::new(vec![1.5_f64]);
+    assert!(matches!(boxed.as_slice(), Some(Slice::F64([v])) if *v == 1.5));
+
+    // Not primitives
+    assert!(Listable::as_slice(&vec![Some(1)]).is_none());
+    assert!(Listable::as_slice(&vec![vec![1]]).is_none());
+
+    // Not contiguous
+    let set: std::collections::BTreeSet<_> = (0..3).collect();
+    assert!(set.as_slice().is_none());
+    let chunked = Chunked(vec![vec![1_u8], vec![2]]);
+    assert!(chunked.as_slice().is_none());
+
+    let mut deque = std::collections::VecDeque::with_capacity(4);
+    deque.extend([1, 2]);
+    assert!(matches!(
+        Listable::as_slice(&deque),
+        Some(Slice::I32([1, 2]))
+    ));
+    deque.push_front(0);
+    let contiguous = deque.as_slices().1.is_empty();
+    assert_eq!(Listable::as_slice(&deque).is_some(), contiguous);
+}
@@ -48,6 +48,32 @@ macro_rules! test_mappable {
                     );
                 }
 
+                #[test]
+                fn test_get() {
+                    let mut map = std::collections::$ty::new();
+                    map.insert("foo", "one");
+                    map.insert("bar", "two");
+
+                    assert!(matches!(
+                        Mappable::get(&map, &Value::String("foo")),
+                        Some(Value::String("one"))
+                    ));
+                    assert!(Mappable::get(&map, &Value::String("baz")).is_none());
+                    assert!(Mappable::get(&map, &Value::I32(1)).is_none());
+
+                    let mut map = std::collections::$ty::new();
+                    map.insert((1, 'a'), HelloWorld { id: 1 });
+                    map.insert((2, 'b'), HelloWorld { id: 2 });
+
+                    let mappable: &dyn Mappable = &map;
+                    let value = mappable.get(&(2, 'b').as_value()).unwrap();
+                    assert_eq!(
+                        format!("{:?}", value),
+                        "HelloWorld { id: 2 }"
+                    );
+                    assert!(mappable.get(&(2, 'a').as_value()).is_none());
+                }
+
                 #[test]
                 fn test_nested_structable() {
                     let mut map = std::collections::$ty::new();
@@ -80,3 +106,85 @@ test_mappable! {
     hash_map => HashMap,
     btree_map => BTreeMap,
 }
+
+#[test]
+fn test_get_default_impl() {
+    struct Entries;
+
+    impl Valuable for Entries {
+        fn as_value(&self) -> Value<'_> {
+            Value::Mappable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_entry(Value::String("a"), Value::U8(1));
+            visit.visit_entry(Value::String("b"), Value::String("two"));
+            visit.visit_entry(Value::String("a"), Value::U8(3));
+        }
+    }
+
+    impl Mappable for Entries {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (3, Some(3))
+        }
+    }
+
+    let mappable: &dyn Mappable = &Entries;
+    assert!(matches!(
+        mappable.get(&Value::String("a")),
+        Some(Value::U8(1))
+    ));
+    // Borrowed values cannot be returned by the default implementation.
+    assert!(mappable.get(&Value::String("b")).is_none());
+    assert!(mappable.get(&Value::String("c")).is_none());
+
+    let boxed: Box<dyn Mappable> = Box::new(Entries);
+    assert!(matches!(boxed.get(&Value::String("a")), Some(Value::U8(1))));
+}
+
+#[test]
+fn test_get_map_keys() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let map: BTreeMap<_, _> = [(1_u32, 1_u8), (2, 2)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &Value::U32(2)),
+        Some(Value::U8(2))
+    ));
+    assert!(Mappable::get(&map, &Value::U32(3)).is_none());
+    // Keys of another type cannot be converted, and are compared instead.
+    assert!(Mappable::get(&map, &Value::U64(2)).is_none());
+
+    let map: BTreeMap<_, _> = [(u128::MAX, 1_u8)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &Value::U128(&u128::MAX)),
+        Some(Value::U8(1))
+    ));
+
+    let map: BTreeMap<_, _> = [("foo", 1_u8)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &Value::String("foo")),
+        Some(Value::U8(1))
+    ));
+
+    let map: BTreeMap<_, _> = [("foo".to_string(), 1_u8)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &Value::String("foo")),
+        Some(Value::U8(1))
+    ));
+    assert!(Mappable::get(&map, &Value::String("bar")).is_none());
+
+    // Tuple keys are compared.
+    let map: BTreeMap<_, _> = [((1_u8, 2_u8), 1_u8)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &(1_u8, 2_u8).as_value()),
+        Some(Value::U8(1))
+    ));
+
+    let map: HashMap<_, _> = [("foo".to_string(), 1_u8)].into_iter().collect();
+    assert!(matches!(
+        Mappable::get(&map, &Value::String("foo")),
+        Some(Value::U8(1))
+    ));
+    assert!(Mappable::get(&map, &Value::String("bar")).is_none());
+}
@@ -31,6 +31,20 @@ fn test_get() {
     assert!(matches!(v.get(&f[0]), Some(Value::I32(v)) if *v == 1));
     assert!(matches!(v.get(&f[1]), Some(Value::I32(v)) if *v == 2));
     assert!(v.get(&bad).is_none());
+
+    // Fields before, after, or inside another set of fields.
+    let f = [
+        NamedField::new("a"),
+        NamedField::new("b"),
+        NamedField::new("c"),
+    ];
+    let v = NamedValues::new(&f[1..2], &[Value::I32(2)]);
+    assert!(v.get(&f[0]).is_none());
+    assert!(matches!(v.get(&f[1]), Some(Value::I32(2))));
+    assert!(v.get(&f[2]).is_none());
+
+    let v = NamedValues::new(&[], &[]);
+    assert!(v.get(&f[0]).is_none());
 }
 
 #[test]
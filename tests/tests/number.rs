@@ -0,0 +1,204 @@
+use valuable::*;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+fn number(value: Value<'_>) -> Number {
+    value.as_number().unwrap()
+}
+
+#[test]
+fn test_as_number() {
+    let values = [
+        Value::I8(1),
+        Value::I16(1),
+        Value::I32(1),
+        Value::I64(1),
+        Value::I128(&1),
+        Value::Isize(1),
+        Value::U8(1),
+        Value::U16(1),
+        Value::U32(1),
+        Value::U64(1),
+        Value::U128(&1),
+        Value::Usize(1),
+        Value::F32(1.0),
+        Value::F64(1.0),
+    ];
+    for a in &values {
+        for b in &values {
+            assert_eq!(number(*a), number(*b), "{:?} == {:?}", a, b);
+        }
+    }
+
+    assert!(number(Value::I8(1)).is_integer());
+    assert!(number(Value::F32(1.0)).is_float());
+    assert!(Value::Bool(true).as_number().is_none());
+    assert!(Value::String("1").as_number().is_none());
+    assert!(Value::Unit.as_number().is_none());
+}
+
+#[test]
+fn test_cmp() {
+    let cases: &[(Number, Number, Option<Ordering>)] = &[
+        (Number::from(-1), Number::from(0_u8), Some(Ordering::Less)),
+        (
+            Number::from(i128::MAX),
+            Number::from(u128::MAX),
+            Some(Ordering::Less),
+        ),
+        (
+            Number::from(u128::MAX),
+            Number::from(u128::MAX - 1),
+            Some(Ordering::Greater),
+        ),
+        (Number::from(1), Number::from(1.5), Some(Ordering::Less)),
+        (Number::from(2), Number::from(1.5), Some(Ordering::Greater)),
+        (Number::from(-2), Number::from(-1.5), Some(Ordering::Less)),
+        (
+            Number::from(-1),
+            Number::from(-1.5),
+            Some(Ordering::Greater),
+        ),
+        (Number::from(0), Number::from(-0.0), Some(Ordering::Equal)),
+        // `u64::MAX as f64` rounds up to 2^64.
+        (
+            Number::from(u64::MAX),
+            Number::from(u64::MAX as f64),
+            Some(Ordering::Less),
+        ),
+        (
+            Number::from(1_u128 << 64),
+            Number::from(u64::MAX as f64),
+            Some(Ordering::Equal),
+        ),
+        (
+            Number::from(i64::MAX),
+            Number::from(i64::MAX as f64),
+            Some(Ordering::Less),
+        ),
+        (
+            Number::from(i128::MIN),
+            Number::from(i128::MIN as f64),
+            Some(Ordering::Equal),
+        ),
+        (
+            Number::from(u128::MAX),
+            Number::from(u128::MAX as f64),
+            Some(Ordering::Less),
+        ),
+        (
+            Number::from(u128::MAX),
+            Number::from(1e38),
+            Some(Ordering::Greater),
+        ),
+        (
+            Number::from(u128::MAX),
+            Number::from(f64::INFINITY),
+            Some(Ordering::Less),
+        ),
+        (
+            Number::from(i128::MIN),
+            Number::from(f64::NEG_INFINITY),
+            Some(Ordering::Greater),
+        ),
+        (
+            Number::from(0.1_f32),
+            Number::from(0.1),
+            Some(Ordering::Greater),
+        ),
+        (Number::from(0), Number::from(f64::NAN), None),
+        (Number::from(u128::MAX), Number::from(f64::NAN), None),
+        (Number::from(f64::NAN), Number::from(f64::NAN), None),
+    ];
+
+    for (a, b, expected) in cases {
+        assert_eq!(a.partial_cmp(b), *expected, "{:?} cmp {:?}", a, b);
+        assert_eq!(
+            b.partial_cmp(a),
+            expected.map(Ordering::reverse),
+            "{:?} cmp {:?}",
+            b,
+            a
+        );
+        assert_eq!(a == b, *expected == Some(Ordering::Equal));
+    }
+}
+
+#[test]
+fn test_try_from_number() {
+    assert_eq!(u8::try_from(Number::from(255_i64)), Ok(255));
+    assert!(u8::try_from(Number::from(256_i64)).is_err());
+    assert!(u8::try_from(Number::from(-1)).is_err());
+    assert_eq!(i128::try_from(Number::from(i128::MIN)), Ok(i128::MIN));
+    assert!(i128::try_from(Number::from(u128::MAX)).is_err());
+    assert_eq!(u128::try_from(Number::from(u128::MAX)), Ok(u128::MAX));
+
+    // Floats convert to integers without fractional part.
+    assert_eq!(i32::try_from(Number::from(-3.0)), Ok(-3));
+    assert!(i32::try_from(Number::from(3.5)).is_err());
+    assert!(i32::try_from(Number::from(f64::NAN)).is_err());
+    assert!(i32::try_from(Number::from(f64::INFINITY)).is_err());
+    assert!(u8::try_from(Number::from(256.0)).is_err());
+    assert_eq!(
+        u128::try_from(Number::from(u128::MAX as f64 / 2.0)),
+        Ok(1 << 127)
+    );
+    assert!(u128::try_from(Number::from(u128::MAX as f64)).is_err());
+
+    // Integers convert to floats without loss of precision.
+    assert_eq!(
+        f64::try_from(Number::from(1_u64 << 53)),
+        Ok(9007199254740992.0)
+    );
+    assert!(f64::try_from(Number::from((1_u64 << 53) + 1)).is_err());
+    assert_eq!(f32::try_from(Number::from(1_u32 << 24)), Ok(16777216.0));
+    assert!(f32::try_from(Number::from((1_u32 << 24) + 1)).is_err());
+    assert!(f64::try_from(Number::from(u128::MAX)).is_err());
+
+    assert_eq!(f32::try_from(Number::from(0.5)), Ok(0.5));
+    assert!(f32::try_from(Number::from(0.1)).is_err());
+    assert!(f32::try_from(Number::from(1e300)).is_err());
+    assert!(f32::try_from(Number::from(f64::NAN)).unwrap().is_nan());
+    assert_eq!(
+        f32::try_from(Number::from(f64::INFINITY)),
+        Ok(f32::INFINITY)
+    );
+}
+
+#[test]
+fn test_try_from_value() {
+    assert_eq!(i64::try_from(Value::U8(1)), Ok(1));
+    assert_eq!(u8::try_from(Value::F64(2.0)), Ok(2));
+    assert_eq!(f64::try_from(Value::I32(-3)), Ok(-3.0));
+    assert_eq!(bool::try_from(Value::Bool(true)), Ok(true));
+    assert_eq!(char::try_from(Value::Char('a')), Ok('a'));
+    assert_eq!(<&str>::try_from(Value::String("a")), Ok("a"));
+
+    let err = u8::try_from(Value::I32(256)).unwrap_err();
+    assert_eq!(err.to_string(), "number out of range for `u8`");
+    let err = i64::try_from(Value::String("1")).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type, expected `i64`");
+    let err = bool::try_from(Value::U8(1)).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type, expected `bool`");
+    let err = <&str>::try_from(Value::Char('a')).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type, expected `&str`");
+
+    fn sink(value: Value<'_>) -> Result<i64, Box<dyn std::error::Error>> {
+        Ok(i64::try_from(value)? * 2)
+    }
+    assert_eq!(sink(Value::U32(21)).unwrap(), 42);
+    assert!(sink(Value::U64(u64::MAX)).is_err());
+}
+
+#[test]
+fn test_fmt() {
+    assert_eq!(format!("{:?}", Number::from(-1_i8)), "-1");
+    assert_eq!(
+        format!("{}", Number::from(u128::MAX)),
+        u128::MAX.to_string()
+    );
+    assert_eq!(format!("{:?}", Number::from(1.0)), "1.0");
+    assert_eq!(format!("{}", Number::from(0.5_f32)), "0.5");
+    assert_eq!(format!("{:?}", Number::from(2.0).as_value()), "2.0");
+}
@@ -0,0 +1,451 @@
+#![cfg(feature = "std")]
+
+use valuable::*;
+
+use std::collections::BTreeMap;
+
+fn assert_send_static<T: Send + Sync + 'static>(_: &T) {}
+
+#[test]
+fn test_capture_primitives() {
+    let values = [
+        OwnedValue::capture(&true),
+        OwnedValue::capture(&'a'),
+        OwnedValue::capture(&1.5_f32),
+        OwnedValue::capture(&-2.5_f64),
+        OwnedValue::capture(&-1_i8),
+        OwnedValue::capture(&-2_i16),
+        OwnedValue::capture(&-3_i32),
+        OwnedValue::capture(&-4_i64),
+        OwnedValue::capture(&-5_i128),
+        OwnedValue::capture(&-6_isize),
+        OwnedValue::capture(&1_u8),
+        OwnedValue::capture(&2_u16),
+        OwnedValue::capture(&3_u32),
+        OwnedValue::capture(&4_u64),
+        OwnedValue::capture(&5_u128),
+        OwnedValue::capture(&6_usize),
+        OwnedValue::capture(&()),
+    ];
+
+    assert_eq!(
+        format!("{:?}", values),
+        "[true, 'a', 1.5, -2.5, -1, -2, -3, -4, -5, -6, 1, 2, 3, 4, 5, 6, ()]"
+    );
+
+    for value in &values {
+        assert_send_static(value);
+    }
+}
+
+#[test]
+fn test_capture_outlives_source() {
+    let captured = {
+        let s = String::from("hello");
+        let path = std::path::PathBuf::from("/tmp/hello");
+        let list = vec![s.as_str(), "world"];
+
+        let mut map = BTreeMap::new();
+        map.insert(s.clone(), vec![1, 2, 3]);
+
+        OwnedValue::capture(&(&s, path, list, map))
+    };
+
+    assert_send_static(&captured);
+    assert_eq!(
+        format!("{:?}", captured),
+        r#"("hello", "/tmp/hello", ["hello", "world"], {"hello": [1, 2, 3]})"#
+    );
+}
+
+#[test]
+fn test_capture_replays_visits() {
+    let source = (vec![1, 2], "three", ());
+    let captured = OwnedValue::capture(&source);
+
+    assert_eq!(
+        tests::visit_counts(&source.as_value()),
+        tests::visit_counts(&captured),
+    );
+
+    let list = vec![OwnedValue::from(Value::U8(1))];
+    assert_eq!(
+        tests::visit_counts(&list),
+        tests::VisitCount {
+            visit_value: 1,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_capture_primitive_slices() {
+    use std::collections::VecDeque;
+
+    // Records the slices passed to the visitor.
+    #[derive(Default)]
+    struct Slices(Vec<String>);
+
+    impl Visit for Slices {
+        fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+            self.0.push(format!("{:?}", slice));
+        }
+
+        fn visit_value(&mut self, _: Value<'_>) {}
+    }
+
+    fn list(value: &OwnedValue) -> &OwnedList {
+        match value {
+            OwnedValue::Listable(list) => list,
+            _ => unreachable!(),
+        }
+    }
+
+    let bytes = OwnedValue::capture(&vec![1_u8, 2, 3]);
+    assert_eq!(
+        tests::visit_counts(list(&bytes)),
+        tests::VisitCount {
+            visit_primitive_slice: 1,
+            ..Default::default()
+        }
+    );
+    assert!(matches!(
+        list(&bytes).as_slice(),
+        Some(Slice::U8([1, 2, 3]))
+    ));
+    assert!(matches!(list(&bytes).get(2), Some(Value::U8(3))));
+    assert_eq!(bytes, OwnedValue::capture(&[1_u8, 2, 3]));
+
+    let strs = OwnedValue::capture(&vec!["a", "b"]);
+    let mut slices = Slices::default();
+    list(&strs).visit(&mut slices);
+    assert_eq!(slices.0, [r#"["a", "b"]"#]);
+
+    // A deque that wraps around is visited as two slices.
+    let mut deque: VecDeque<u32> = VecDeque::with_capacity(4);
+    deque.extend(0..deque.capacity() as u32);
+    deque.rotate_left(2);
+    let captured = OwnedValue::capture(&deque);
+    let (front, back) = deque.as_slices();
+    assert!(!front.is_empty() && !back.is_empty());
+    assert_eq!(
+        tests::visit_counts(list(&captured)),
+        tests::VisitCount {
+            visit_primitive_slice: 2,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        captured,
+        OwnedValue::capture(&deque.iter().collect::<Vec<_>>())
+    );
+
+    // Visiting a range keeps the slices apart.
+    let mut slices = Slices::default();
+    list(&captured).visit_range(front.len() - 1..front.len() + 1, &mut slices);
+    assert_eq!(
+        slices.0,
+        [
+            format!("{:?}", &front[front.len() - 1..]),
+            format!("{:?}", &back[..1])
+        ]
+    );
+}
+
+#[test]
+fn test_capture_dynamic_struct() {
+    struct Dynamic;
+
+    impl Valuable for Dynamic {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_named_fields(&NamedValues::new(
+                &[NamedField::new("foo")],
+                &[Value::U32(1)],
+            ));
+            visit.visit_named_fields(&NamedValues::new(
+                &[NamedField::new("bar")],
+                &[Value::String("two")],
+            ));
+        }
+    }
+
+    impl Structable for Dynamic {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic("Dynamic", Fields::Named(&[]))
+        }
+    }
+
+    let captured = OwnedValue::capture(&Dynamic);
+
+    assert_eq!(
+        format!("{:?}", captured),
+        r#"Dynamic { foo: 1, bar: "two" }"#
+    );
+    match captured {
+        OwnedValue::Structable(s) => {
+            assert_eq!(s.name(), "Dynamic");
+            assert!(s.definition().is_dynamic());
+            assert_eq!(
+                tests::visit_counts(&s),
+                tests::VisitCount {
+                    visit_named_fields: 2,
+                    ..Default::default()
+                }
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_capture_lookups() {
+    #[derive(Valuable)]
+    struct Person {
+        name: String,
+        age: u32,
+        tags: BTreeMap<String, String>,
+    }
+
+    #[derive(Valuable)]
+    struct Point(i32, i32);
+
+    let captured = OwnedValue::capture(&Person {
+        name: "alice".to_string(),
+        age: 42,
+        tags: [("role".to_string(), "admin".to_string())]
+            .into_iter()
+            .collect(),
+    });
+
+    let person = match &captured {
+        OwnedValue::Structable(s) => s,
+        _ => unreachable!(),
+    };
+    assert!(matches!(person.field("name"), Some(Value::String("alice"))));
+    assert!(matches!(person.field_at(1), Some(Value::U32(42))));
+    assert!(person.field("height").is_none());
+    assert!(person.field_at(3).is_none());
+
+    let tags = match person.field("tags") {
+        Some(Value::Mappable(tags)) => tags,
+        _ => unreachable!(),
+    };
+    assert!(matches!(
+        tags.get(&Value::String("role")),
+        Some(Value::String("admin"))
+    ));
+    assert!(tags.get(&Value::String("team")).is_none());
+
+    let captured = OwnedValue::capture(&Point(1, 2));
+    match &captured {
+        OwnedValue::Structable(s) => {
+            assert!(matches!(s.field_at(1), Some(Value::I32(2))));
+            assert!(s.field("0").is_none());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_capture_error() {
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct Inner;
+
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("inner")
+        }
+    }
+
+    impl Error for Inner {}
+
+    #[derive(Debug)]
+    struct Outer(Inner);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let captured = {
+        let error = Outer(Inner);
+        OwnedValue::capture(&(&error as &dyn Error))
+    };
+
+    match &captured {
+        OwnedValue::Error(error) => {
+            assert_eq!(error.to_string(), "outer");
+            assert_eq!(format!("{:?}", error), "Outer(Inner)");
+            assert_eq!(error.source().unwrap().to_string(), "inner");
+            assert!(error.source().unwrap().source().is_none());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Person {
+        name: String,
+        age: u32,
+        tags: Vec<&'static str>,
+        pet: Option<Pet>,
+    }
+
+    #[derive(Valuable)]
+    enum Pet {
+        Dog { name: String },
+        Fish(u8),
+    }
+
+    #[test]
+    fn test_capture_struct() {
+        let person = Person {
+            name: "Alice".to_string(),
+            age: 42,
+            tags: vec!["admin"],
+            pet: Some(Pet::Dog {
+                name: "Rex".to_string(),
+            }),
+        };
+        let expected = format!("{:?}", person.as_value());
+
+        let captured = OwnedValue::capture(&person);
+        drop(person);
+
+        assert_eq!(format!("{:?}", captured), expected);
+
+        let s = match &captured {
+            OwnedValue::Structable(s) => s,
+            _ => unreachable!(),
+        };
+        assert_eq!(s.name(), "Person");
+
+        // Field lookups keep working with the static definition.
+        let fields = match s.definition() {
+            StructDef::Static {
+                fields: Fields::Named(fields),
+                ..
+            } => fields,
+            _ => unreachable!(),
+        };
+
+        struct Age(Option<u32>, &'static NamedField<'static>);
+
+        impl Visit for Age {
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                self.0 = named_values.get(self.1).and_then(Value::as_u32);
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+        }
+
+        let mut age = Age(None, &fields[1]);
+        s.visit(&mut age);
+        assert_eq!(age.0, Some(42));
+    }
+
+    #[test]
+    fn test_capture_chunked_struct() {
+        #[derive(Valuable)]
+        #[valuable(chunk_size = 2)]
+        struct Pairs {
+            a: u8,
+            b: u8,
+            c: u8,
+        }
+
+        #[derive(Valuable)]
+        #[valuable(chunk_size = 1)]
+        struct Single {
+            a: u8,
+            b: u8,
+        }
+
+        // Looks up every statically defined field in each call.
+        struct Lookup {
+            fields: &'static [NamedField<'static>],
+            calls: Vec<Vec<Option<u8>>>,
+        }
+
+        impl Visit for Lookup {
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                let call = self
+                    .fields
+                    .iter()
+                    .map(|field| named_values.get(field).and_then(Value::as_u8))
+                    .collect();
+                self.calls.push(call);
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+        }
+
+        fn lookup(value: &dyn Valuable) -> Vec<Vec<Option<u8>>> {
+            let s = match value.as_value() {
+                Value::Structable(s) => s,
+                _ => unreachable!(),
+            };
+            let fields = match s.definition() {
+                StructDef::Static {
+                    fields: Fields::Named(fields),
+                    ..
+                } => fields,
+                _ => unreachable!(),
+            };
+            let mut lookup = Lookup {
+                fields,
+                calls: vec![],
+            };
+            s.visit(&mut lookup);
+            lookup.calls
+        }
+
+        let pairs = Pairs { a: 1, b: 2, c: 3 };
+        let captured = OwnedValue::capture(&pairs);
+        assert_eq!(
+            lookup(&captured),
+            [vec![Some(1), Some(2), None], vec![None, None, Some(3)]]
+        );
+        assert_eq!(lookup(&captured), lookup(&pairs));
+
+        let single = Single { a: 1, b: 2 };
+        let captured = OwnedValue::capture(&single);
+        assert_eq!(
+            lookup(&captured),
+            [vec![Some(1), None], vec![None, Some(2)]]
+        );
+        assert_eq!(lookup(&captured), lookup(&single));
+    }
+
+    #[test]
+    fn test_capture_enum() {
+        let captured = OwnedValue::capture(&Pet::Fish(3));
+
+        let e = match &captured {
+            OwnedValue::Enumerable(e) => e,
+            _ => unreachable!(),
+        };
+        assert_eq!(e.name(), "Pet");
+        assert_eq!(e.variant_name(), "Fish");
+        assert_eq!(e.definition().variants().len(), 2);
+        assert_eq!(format!("{:?}", captured), "Pet::Fish(3)");
+    }
+}
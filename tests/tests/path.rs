@@ -0,0 +1,353 @@
+#![cfg(feature = "std")]
+
+use valuable::path::{
+    self, Location, OwnedLocation, OwnedSegment, PathVisit, Segment, Selector, WithPath,
+};
+use valuable::*;
+
+use std::collections::{BTreeMap, HashMap};
+
+fn select(selector: &str, value: &dyn Valuable) -> Vec<String> {
+    let mut matches = vec![];
+    Selector::parse(selector)
+        .unwrap()
+        .select(value, |location, value| {
+            matches.push(format!("{} = {:?}", location, value));
+        });
+    matches
+}
+
+struct Dynamic;
+
+impl Valuable for Dynamic {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_named_fields(&NamedValues::new(
+            &[NamedField::new("id")],
+            &[Value::U32(1)],
+        ));
+        visit.visit_named_fields(&NamedValues::new(
+            &[NamedField::new("content type")],
+            &[Value::String("text/plain")],
+        ));
+    }
+}
+
+impl Structable for Dynamic {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_dynamic("Dynamic", Fields::Named(&[]))
+    }
+}
+
+struct DynamicTuple;
+
+impl Valuable for DynamicTuple {
+    fn as_value(&self) -> Value<'_> {
+        Value::Tuplable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_unnamed_fields(&[Value::I8(0), Value::I8(1)]);
+        visit.visit_unnamed_fields(&[Value::I8(2)]);
+    }
+}
+
+impl Tuplable for DynamicTuple {
+    fn definition(&self) -> TupleDef {
+        TupleDef::new_dynamic((0, None))
+    }
+}
+
+#[test]
+fn test_root() {
+    assert_eq!(select("", &1), [" = 1"]);
+}
+
+#[test]
+fn test_lists() {
+    let list = vec![vec![1, 2], vec![3]];
+
+    assert_eq!(select("[1]", &list), ["[1] = [3]"]);
+    assert_eq!(select("[0][1]", &list), ["[0][1] = 2"]);
+    assert_eq!(select("[2]", &list), Vec::<String>::new());
+    assert_eq!(
+        select("[*][*]", &list),
+        ["[0][0] = 1", "[0][1] = 2", "[1][0] = 3"]
+    );
+    // Field steps do not apply to lists.
+    assert!(select(".0", &list).is_empty());
+}
+
+#[test]
+fn test_maps() {
+    let mut map = HashMap::new();
+    map.insert("host", "example.com");
+    assert_eq!(select(r#"["host"]"#, &map), [r#"["host"] = "example.com""#]);
+    assert!(select(r#"["other"]"#, &map).is_empty());
+
+    let map = BTreeMap::from([(1_u8, "one"), (2_u8, "two")]);
+    assert_eq!(select("[2]", &map), [r#"[2] = "two""#]);
+    assert_eq!(select("[*]", &map), [r#"[1] = "one""#, r#"[2] = "two""#]);
+}
+
+#[test]
+fn test_tuples() {
+    let tuple = (1, ("a", 'b'));
+    assert_eq!(select(".1.0", &tuple), [r#".1.0 = "a""#]);
+    assert_eq!(select(".*", &tuple), [".0 = 1", r#".1 = ("a", 'b')"#]);
+
+    // Indices are counted across multiple `visit_unnamed_fields` calls.
+    assert_eq!(select(".2", &DynamicTuple), [".2 = 2"]);
+}
+
+#[test]
+fn test_dynamic_struct() {
+    assert_eq!(select("id", &Dynamic), [".id = 1"]);
+    assert_eq!(
+        select(r#"."content type""#, &Dynamic),
+        [r#"."content type" = "text/plain""#]
+    );
+    assert_eq!(select(".*", &Dynamic).len(), 2);
+}
+
+#[test]
+fn test_parse() {
+    for (input, display) in [
+        ("", ""),
+        ("a", ".a"),
+        ("a.b_2[3].0", ".a.b_2[3].0"),
+        (r#"a["k\"ey"]::V.*[*]"#, r#".a["k\"ey"]::V.*[*]"#),
+        (r#"."a b""#, r#"."a b""#),
+    ] {
+        let selector: Selector = input.parse().unwrap();
+        assert_eq!(selector.to_string(), display);
+        assert_eq!(Selector::parse(&selector.to_string()).unwrap(), selector);
+    }
+
+    for (input, position) in [
+        ("[", 1),
+        ("[1", 2),
+        ("a..b", 2),
+        ("a:b", 2),
+        ("a::", 3),
+        (r#"["a"#, 1),
+        (r#"["\q"]"#, 4),
+        ("a b", 1),
+        ("[99999999999999999999999999]", 1),
+    ] {
+        let err = Selector::parse(input).unwrap_err();
+        assert_eq!(err.position(), position, "{}: {}", input, err);
+    }
+}
+
+#[test]
+fn test_location() {
+    let root = Location::root();
+    let a = root.child(Segment::Field("a"));
+    let key = a.child(Segment::Key(Value::String("k")));
+    let index = key.child(Segment::Index(0));
+    let variant = index.child(Segment::Variant("V"));
+    let element = variant.child(Segment::Element(1));
+
+    assert_eq!(element.to_string(), r#".a["k"].0::V[1]"#);
+    assert_eq!(element.depth(), 5);
+    assert!(!element.is_root());
+    assert!(matches!(element.segment(), Some(Segment::Element(1))));
+    assert_eq!(element.parent().unwrap().to_string(), r#".a["k"].0::V"#);
+    assert!(root.parent().is_none());
+
+    let owned = OwnedLocation::from(&element);
+    assert_eq!(owned.to_string(), element.to_string());
+    assert_eq!(owned.segments().len(), 5);
+    assert_eq!(
+        owned.segments()[1],
+        OwnedSegment::Key(OwnedValue::from(Value::String("k")))
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Order {
+        id: u32,
+        items: Vec<Item>,
+        status: Status,
+    }
+
+    #[derive(Valuable)]
+    struct Item {
+        name: &'static str,
+        price: u32,
+    }
+
+    #[derive(Valuable)]
+    enum Status {
+        Pending,
+        Shipped { carrier: &'static str },
+    }
+
+    fn order() -> Order {
+        Order {
+            id: 7,
+            items: vec![
+                Item {
+                    name: "apple",
+                    price: 3,
+                },
+                Item {
+                    name: "pear",
+                    price: 5,
+                },
+            ],
+            status: Status::Shipped { carrier: "post" },
+        }
+    }
+
+    #[test]
+    fn test_static_struct() {
+        let order = order();
+
+        assert_eq!(select("id", &order), [".id = 7"]);
+        assert_eq!(select("items[1].price", &order), [".items[1].price = 5"]);
+        assert_eq!(
+            select("items[*].name", &order),
+            [r#".items[0].name = "apple""#, r#".items[1].name = "pear""#]
+        );
+        assert!(select("missing", &order).is_empty());
+    }
+
+    #[test]
+    fn test_variants() {
+        let order = order();
+
+        assert_eq!(
+            select("status::Shipped.carrier", &order),
+            [r#".status::Shipped.carrier = "post""#]
+        );
+        assert!(select("status::Pending", &order).is_empty());
+        assert_eq!(select("status.carrier", &order).len(), 1);
+
+        let statuses = vec![Status::Pending, Status::Shipped { carrier: "ship" }];
+        assert_eq!(
+            select("[*]::Pending", &statuses),
+            ["[0]::Pending = Status::Pending"]
+        );
+    }
+}
+
+// Records the location of every leaf value, stopping after `limit` values.
+struct Leaves {
+    leaves: Vec<String>,
+    limit: usize,
+}
+
+impl Leaves {
+    fn new(limit: usize) -> Leaves {
+        Leaves {
+            leaves: vec![],
+            limit,
+        }
+    }
+}
+
+impl PathVisit for Leaves {
+    fn visit_value(&mut self, location: &Location<'_>, value: Value<'_>) {
+        match value {
+            Value::Listable(_)
+            | Value::Mappable(_)
+            | Value::Structable(_)
+            | Value::Enumerable(_)
+            | Value::Tuplable(_) => path::visit_nested(self, location, value),
+            value => self.leaves.push(format!("{} = {:?}", location, value)),
+        }
+    }
+
+    fn control_flow(&self) -> std::ops::ControlFlow<()> {
+        if self.leaves.len() < self.limit {
+            std::ops::ControlFlow::Continue(())
+        } else {
+            std::ops::ControlFlow::Break(())
+        }
+    }
+}
+
+fn leaves(value: &dyn Valuable) -> Vec<String> {
+    let mut visit = Leaves::new(usize::MAX);
+    visit.visit_value(&Location::root(), value.as_value());
+    visit.leaves
+}
+
+#[test]
+fn test_path_visit() {
+    #[derive(Valuable)]
+    struct Order {
+        tags: BTreeMap<&'static str, Vec<u32>>,
+        items: Vec<Item>,
+        status: Status,
+    }
+
+    #[derive(Valuable)]
+    struct Item {
+        name: &'static str,
+        price: u32,
+    }
+
+    #[derive(Valuable)]
+    enum Status {
+        Pending,
+        Split(u32, &'static str),
+    }
+
+    let mut tags = BTreeMap::new();
+    tags.insert("a", vec![1, 2]);
+
+    let order = Order {
+        tags,
+        items: vec![Item {
+            name: "x",
+            price: 10,
+        }],
+        status: Status::Split(7, "y"),
+    };
+
+    assert_eq!(
+        leaves(&order),
+        [
+            r#".tags["a"][0] = 1"#,
+            r#".tags["a"][1] = 2"#,
+            r#".items[0].name = "x""#,
+            ".items[0].price = 10",
+            ".status::Split.0 = 7",
+            r#".status::Split.1 = "y""#,
+        ]
+    );
+    assert_eq!(leaves(&(1, (2,))), [".0 = 1", ".1.0 = 2"]);
+    assert_eq!(
+        leaves(&Dynamic),
+        [".id = 1", r#"."content type" = "text/plain""#]
+    );
+    assert_eq!(leaves(&"root"), [r#" = "root""#]);
+    assert!(leaves(&Status::Pending).is_empty());
+}
+
+#[test]
+fn test_path_visit_control_flow() {
+    let mut visit = Leaves::new(3);
+    let list = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+    visit.visit_value(&Location::root(), list.as_value());
+    assert_eq!(visit.leaves, ["[0][0] = 1", "[0][1] = 2", "[1][0] = 3"]);
+}
+
+#[test]
+fn test_with_path() {
+    let mut visit = Leaves::new(usize::MAX);
+    let root = Location::root();
+    let items = root.child(Segment::Field("items"));
+    vec!["a", "b"].visit(&mut WithPath::new(&mut visit, &items));
+    assert_eq!(visit.leaves, [r#".items[0] = "a""#, r#".items[1] = "b""#]);
+}
@@ -0,0 +1,111 @@
+use valuable::limit::Limited;
+use valuable::*;
+
+struct Rope(Vec<&'static str>);
+
+impl Valuable for Rope {
+    fn as_value(&self) -> Value<'_> {
+        Value::Stringable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for chunk in &self.0 {
+            visit.visit_str_chunk(chunk);
+        }
+    }
+}
+
+impl Stringable for Rope {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.iter().map(|chunk| chunk.len()).sum();
+        (len, Some(len))
+    }
+}
+
+#[derive(Default)]
+struct Chunks(Vec<String>);
+
+impl Visit for Chunks {
+    fn visit_value(&mut self, value: Value<'_>) {
+        match value {
+            Value::Stringable(v) => v.visit(self),
+            _ => panic!("unexpected value {:?}", value),
+        }
+    }
+
+    fn visit_str_chunk(&mut self, chunk: &str) {
+        self.0.push(chunk.to_string());
+    }
+}
+
+#[test]
+fn test_visit_str_chunk() {
+    let rope = Rope(vec!["hello", "", " ", "world"]);
+
+    let mut chunks = Chunks::default();
+    valuable::visit(&rope, &mut chunks);
+    assert_eq!(chunks.0, ["hello", "", " ", "world"]);
+    assert_eq!(rope.size_hint(), (11, Some(11)));
+
+    // By default, chunks are passed to `visit_value`.
+    struct Strings(Vec<String>);
+
+    impl Visit for Strings {
+        fn visit_value(&mut self, value: Value<'_>) {
+            match value {
+                Value::String(v) => self.0.push(v.to_string()),
+                value => panic!("unexpected value {:?}", value),
+            }
+        }
+    }
+
+    let mut strings = Strings(vec![]);
+    rope.visit(&mut strings);
+    assert_eq!(strings.0, ["hello", "", " ", "world"]);
+}
+
+#[test]
+fn test_fmt() {
+    let rope = Rope(vec!["a \"quoted\"", " 'word'\n", "", "tab\t"]);
+    let value = rope.as_value();
+
+    let stringable = value.as_stringable().unwrap();
+    assert_eq!(stringable.to_string(), "a \"quoted\" 'word'\ntab\t");
+    assert_eq!(
+        format!("{:?}", value),
+        format!("{:?}", "a \"quoted\" 'word'\ntab\t")
+    );
+
+    assert_eq!(format!("{:?}", Rope(vec![]).as_value()), r#""""#);
+    assert!(Value::String("a").as_stringable().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_owned() {
+    let rope = Rope(vec!["ab", "c"]);
+    assert_eq!(
+        OwnedValue::capture(&rope),
+        OwnedValue::String("abc".to_string())
+    );
+
+    // Strings compare equal however they are chunked.
+    assert_eq!(rope.as_value(), Rope(vec!["a", "bc"]).as_value());
+    assert_ne!(rope.as_value(), Rope(vec!["ab"]).as_value());
+}
+
+#[test]
+fn test_limited() {
+    let rope = Rope(vec!["hé", "llo", " world"]);
+    let fmt = |limited: Limited<'_>| format!("{:?}", limited.as_value());
+
+    assert_eq!(
+        fmt(Limited::new(&rope).max_str_len(5)),
+        r#""héll… 7 more bytes""#
+    );
+    assert_eq!(
+        fmt(Limited::new(&rope).max_str_len(2)),
+        r#""h… 11 more bytes""#
+    );
+    assert_eq!(fmt(Limited::new(&rope).max_str_len(12)), r#""héllo world""#);
+}
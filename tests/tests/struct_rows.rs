@@ -0,0 +1,299 @@
+#![cfg(feature = "std")]
+
+use valuable::*;
+
+use std::collections::VecDeque;
+use std::ops::ControlFlow;
+
+#[derive(Valuable)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Valuable)]
+struct Pair(u8, &'static str);
+
+fn points(len: i32) -> Vec<Point> {
+    (0..len).map(|i| Point { x: i, y: -i }).collect()
+}
+
+// Records the calls made by lists of structs.
+#[derive(Default)]
+struct Record {
+    batches: Vec<(String, usize)>,
+    rows: Vec<String>,
+    values: usize,
+    limit: Option<usize>,
+}
+
+impl Visit for Record {
+    fn visit_value(&mut self, value: Value<'_>) {
+        match value {
+            Value::Listable(v) => v.visit(self),
+            value => {
+                self.values += 1;
+                self.rows.push(format!("{:?}", value));
+            }
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        let fields: Vec<_> = named_values
+            .iter()
+            .map(|(field, value)| format!("{}={:?}", field.name(), value))
+            .collect();
+        self.rows.push(fields.join(" "));
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        self.rows.push(format!("{:?}", values));
+    }
+
+    fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+        self.batches
+            .push((rows.definition().name().to_string(), rows.len()));
+        assert!(rows.columns().is_none());
+        rows.visit_rows(self);
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self.limit {
+            Some(limit) if self.rows.len() >= limit => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[test]
+fn test_derived_rows() {
+    let mut record = Record::default();
+    valuable::visit(&points(3), &mut record);
+
+    assert_eq!(record.batches, [("Point".to_string(), 3)]);
+    assert_eq!(record.values, 0);
+    assert_eq!(record.rows, ["x=0 y=0", "x=1 y=-1", "x=2 y=-2"]);
+
+    let mut record = Record::default();
+    valuable::visit(&[Pair(1, "a"), Pair(2, "b")], &mut record);
+    assert_eq!(record.batches, [("Pair".to_string(), 2)]);
+    assert_eq!(record.rows, [r#"[1, "a"]"#, r#"[2, "b"]"#]);
+
+    // Empty lists do not visit anything.
+    let mut record = Record::default();
+    valuable::visit(&points(0), &mut record);
+    assert!(record.batches.is_empty());
+}
+
+#[test]
+fn test_rows_called_per_slice() {
+    let mut deque = VecDeque::with_capacity(4);
+    deque.extend(points(2));
+    deque.push_front(Point { x: 9, y: 9 });
+
+    let mut record = Record::default();
+    valuable::visit(&deque, &mut record);
+
+    let total: usize = record.batches.iter().map(|(_, len)| len).sum();
+    assert_eq!(total, 3);
+    assert_eq!(record.rows.len(), 3);
+    assert_eq!(record.rows[0], "x=9 y=9");
+}
+
+#[test]
+fn test_rows_control_flow() {
+    let mut record = Record {
+        limit: Some(2),
+        ..Record::default()
+    };
+    valuable::visit(&points(5), &mut record);
+    assert_eq!(record.rows.len(), 2);
+}
+
+#[test]
+fn test_default_visits_values() {
+    struct Count(usize);
+
+    impl Visit for Count {
+        fn visit_value(&mut self, value: Value<'_>) {
+            match value {
+                Value::Listable(v) => v.visit(self),
+                Value::Structable(v) => {
+                    assert_eq!(v.definition().name(), "Point");
+                    self.0 += 1;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        fn control_flow(&self) -> ControlFlow<()> {
+            if self.0 < 4 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    let mut count = Count(0);
+    valuable::visit(&points(3), &mut count);
+    assert_eq!(count.0, 3);
+
+    let mut count = Count(0);
+    valuable::visit(&points(10), &mut count);
+    assert_eq!(count.0, 4);
+
+    assert_eq!(
+        format!("{:?}", points(2).as_value()),
+        "[Point { x: 0, y: 0 }, Point { x: 1, y: -1 }]"
+    );
+}
+
+#[test]
+fn test_dynamic_rows() {
+    struct Dynamic(u32);
+
+    impl Valuable for Dynamic {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_unnamed_fields(&[Value::U32(self.0)]);
+        }
+
+        fn visit_slice(slice: &[Self], visit: &mut dyn Visit) {
+            StructRows::visit_slice(slice, visit);
+        }
+    }
+
+    impl Structable for Dynamic {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic("Dynamic", Fields::Unnamed(1))
+        }
+    }
+
+    let mut record = Record::default();
+    valuable::visit(&vec![Dynamic(1), Dynamic(2)], &mut record);
+    assert!(record.batches.is_empty());
+    assert_eq!(record.values, 2);
+}
+
+static XYZ: &[NamedField<'static>] = &[
+    NamedField::new("x"),
+    NamedField::new("y"),
+    NamedField::new("z"),
+];
+
+#[test]
+fn test_columns() {
+    let xs = [1, 2];
+    let ys = ["a", "b"];
+    let zs = [true, false];
+    let columns = [Slice::I32(&xs), Slice::Str(&ys), Slice::Bool(&zs)];
+    let rows = StructRows::from_columns(StructDef::new_static("Xyz", Fields::Named(XYZ)), &columns);
+
+    assert_eq!(rows.len(), 2);
+    assert!(!rows.is_empty());
+    assert_eq!(rows.columns().unwrap().len(), 3);
+    assert_eq!(
+        format!("{:?}", rows),
+        r#"[Xyz { x: 1, y: "a", z: true }, Xyz { x: 2, y: "b", z: false }]"#
+    );
+
+    struct Collect(Vec<String>);
+
+    impl Visit for Collect {
+        fn visit_value(&mut self, value: Value<'_>) {
+            let row = value.as_structable().unwrap();
+            assert_eq!(row.definition().name(), "Xyz");
+            assert!(matches!(row.field_at(1), Some(Value::String(_))));
+            assert!(row.field_at(3).is_none());
+            row.visit(self);
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+    }
+
+    // The default implementation passes each row to `visit_value`.
+    let mut fields = Collect(vec![]);
+    fields.visit_struct_rows(&rows);
+    assert_eq!(
+        fields.0,
+        ["x=1", r#"y="a""#, "z=true", "x=2", r#"y="b""#, "z=false"]
+    );
+
+    let empty = StructRows::from_columns(
+        StructDef::new_static("Xyz", Fields::Named(XYZ)),
+        &[Slice::I32(&[]), Slice::Str(&[]), Slice::Bool(&[])],
+    );
+    assert!(empty.is_empty());
+    assert_eq!(format!("{:?}", empty), "[]");
+}
+
+#[test]
+fn test_columns_many_fields() {
+    static NAMES: [&str; 20] = [
+        "f0", "f1", "f2", "f3", "f4", "f5", "f6", "f7", "f8", "f9", "f10", "f11", "f12", "f13",
+        "f14", "f15", "f16", "f17", "f18", "f19",
+    ];
+    let fields: Vec<_> = NAMES.iter().map(|name| NamedField::new(name)).collect();
+    let data: Vec<[u32; 1]> = (0..20).map(|i| [i]).collect();
+    let columns: Vec<_> = data.iter().map(|column| Slice::U32(column)).collect();
+
+    let rows = StructRows::from_columns(
+        StructDef::new_dynamic("Wide", Fields::Named(&fields)),
+        &columns,
+    );
+
+    let mut record = Record::default();
+    rows.visit_rows(&mut record);
+    // Fields are passed in chunks of 16.
+    assert_eq!(record.rows.len(), 2);
+    assert!(record.rows[0].starts_with("f0=0 "));
+    assert!(record.rows[1].ends_with(" f19=19"));
+
+    // The remaining chunks are skipped once the visitor breaks.
+    let mut record = Record {
+        limit: Some(1),
+        ..Record::default()
+    };
+    rows.visit_rows(&mut record);
+    assert_eq!(record.rows.len(), 1);
+
+    let unnamed = StructRows::from_columns(StructDef::new_static("Unit", Fields::Unnamed(0)), &[]);
+    assert_eq!(unnamed.len(), 0);
+}
+
+#[test]
+fn test_columns_unnamed() {
+    let columns = [Slice::U8(&[1, 2]), Slice::Str(&["a", "b"])];
+    let rows =
+        StructRows::from_columns(StructDef::new_static("Pair", Fields::Unnamed(2)), &columns);
+
+    let mut record = Record::default();
+    rows.visit_rows(&mut record);
+    assert_eq!(record.rows, [r#"[1, "a"]"#, r#"[2, "b"]"#]);
+}
+
+#[test]
+#[should_panic(expected = "expected one column per field of `Xyz`")]
+fn test_columns_wrong_count() {
+    StructRows::from_columns(
+        StructDef::new_static("Xyz", Fields::Named(XYZ)),
+        &[Slice::I32(&[1])],
+    );
+}
+
+#[test]
+#[should_panic(expected = "columns have different lengths")]
+fn test_columns_wrong_length() {
+    StructRows::from_columns(
+        StructDef::new_static("Pair", Fields::Unnamed(2)),
+        &[Slice::U8(&[1, 2]), Slice::U8(&[1])],
+    );
+}
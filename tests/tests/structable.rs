@@ -128,6 +128,74 @@ fn test_manual_dyn_impl() {
     );
 }
 
+#[test]
+fn test_field_default_impl() {
+    struct Named;
+
+    impl Valuable for Named {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_named_fields(&NamedValues::new(
+                &[NamedField::new("foo"), NamedField::new("bar")],
+                &[Value::U32(1), Value::Bool(true)],
+            ));
+            visit.visit_named_fields(&NamedValues::new(
+                &[NamedField::new("baz")],
+                &[Value::String("three")],
+            ));
+        }
+    }
+
+    impl Structable for Named {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic("Named", Fields::Named(&[]))
+        }
+    }
+
+    struct Unnamed;
+
+    impl Valuable for Unnamed {
+        fn as_value(&self) -> Value<'_> {
+            Value::Structable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_unnamed_fields(&[Value::I8(-1)]);
+            visit.visit_unnamed_fields(&[Value::Char('a'), Value::Unit]);
+        }
+    }
+
+    impl Structable for Unnamed {
+        fn definition(&self) -> StructDef<'_> {
+            StructDef::new_dynamic("Unnamed", Fields::Unnamed(3))
+        }
+    }
+
+    let named: &dyn Structable = &Named;
+    assert!(matches!(named.field("foo"), Some(Value::U32(1))));
+    assert!(matches!(named.field("bar"), Some(Value::Bool(true))));
+    // Borrowed values cannot be returned by the default implementation.
+    assert!(named.field("baz").is_none());
+    assert!(named.field("qux").is_none());
+    assert!(matches!(named.field_at(1), Some(Value::Bool(true))));
+    assert!(named.field_at(2).is_none());
+    assert!(named.field_at(3).is_none());
+
+    let unnamed: &dyn Structable = &Unnamed;
+    assert!(unnamed.field("0").is_none());
+    assert!(matches!(unnamed.field_at(0), Some(Value::I8(-1))));
+    assert!(matches!(unnamed.field_at(1), Some(Value::Char('a'))));
+    assert!(matches!(unnamed.field_at(2), Some(Value::Unit)));
+    assert!(unnamed.field_at(3).is_none());
+
+    // Forwarded through smart pointers.
+    let boxed: Box<dyn Structable> = Box::new(Named);
+    assert!(matches!(boxed.field("foo"), Some(Value::U32(1))));
+}
+
 #[test]
 fn test_named_field() {
     let name = "hello".to_string();
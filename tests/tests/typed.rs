@@ -0,0 +1,212 @@
+use valuable::typed::{Typed, TypedVisit};
+use valuable::*;
+
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+
+/// Records the method each value is passed to, descending into containers.
+#[derive(Default)]
+struct Record(Vec<String>);
+
+impl TypedVisit for Record {
+    fn visit_other(&mut self, value: Value<'_>) {
+        self.0.push(format!("other({:?})", value));
+    }
+
+    fn visit_bool(&mut self, v: bool) {
+        self.0.push(format!("bool({})", v));
+    }
+
+    fn visit_char(&mut self, v: char) {
+        self.0.push(format!("char({})", v));
+    }
+
+    fn visit_i64(&mut self, v: i64) {
+        self.0.push(format!("i64({})", v));
+    }
+
+    fn visit_u64(&mut self, v: u64) {
+        self.0.push(format!("u64({})", v));
+    }
+
+    fn visit_f64(&mut self, v: f64) {
+        self.0.push(format!("f64({})", v));
+    }
+
+    fn visit_str(&mut self, v: &str) {
+        self.0.push(format!("str({})", v));
+    }
+
+    fn visit_bytes(&mut self, v: &[u8]) {
+        self.0.push(format!("bytes({:?})", v));
+    }
+
+    fn visit_unit(&mut self) {
+        self.0.push("unit".to_string());
+    }
+
+    fn visit_list(&mut self, v: &dyn Listable) {
+        self.0.push("list".to_string());
+        v.visit(&mut Typed::new(self));
+    }
+
+    fn visit_map(&mut self, v: &dyn Mappable) {
+        self.0.push("map".to_string());
+        v.visit(&mut Typed::new(self));
+    }
+
+    fn visit_tuple(&mut self, v: &dyn Tuplable) {
+        self.0.push("tuple".to_string());
+        v.visit(&mut Typed::new(self));
+    }
+}
+
+fn record(value: &dyn Valuable) -> Vec<String> {
+    let mut record = Record::default();
+    Typed::new(&mut record).visit_value(value.as_value());
+    record.0
+}
+
+#[test]
+fn test_primitives() {
+    let cases: &[(&dyn Valuable, &str)] = &[
+        (&true, "bool(true)"),
+        (&'x', "char(x)"),
+        (&-1_i8, "i64(-1)"),
+        (&-1_i16, "i64(-1)"),
+        (&-1_i32, "i64(-1)"),
+        (&-1_i64, "i64(-1)"),
+        (&-1_i128, "i64(-1)"),
+        (&-1_isize, "i64(-1)"),
+        (
+            &i128::MIN,
+            "other(-170141183460469231731687303715884105728)",
+        ),
+        (&1_u8, "u64(1)"),
+        (&1_u16, "u64(1)"),
+        (&1_u32, "u64(1)"),
+        (&1_u64, "u64(1)"),
+        (&1_u128, "u64(1)"),
+        (&1_usize, "u64(1)"),
+        (&u128::MAX, "other(340282366920938463463374607431768211455)"),
+        (&0.5_f32, "f64(0.5)"),
+        (&0.5_f64, "f64(0.5)"),
+        (&"s", "str(s)"),
+        (&Bytes(b"b"), "bytes([98])"),
+        (&None::<u8>, "unit"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(record(*value), [*expected]);
+    }
+
+    let args = format_args!("display");
+    assert_eq!(record(&args), ["other(display)"]);
+}
+
+#[test]
+fn test_containers() {
+    assert_eq!(record(&vec![1, 2]), ["list", "i64(1)", "i64(2)"]);
+    assert_eq!(
+        record(&BTreeMap::from([("a", 1_u8)])),
+        ["map", "str(a)", "u64(1)"]
+    );
+    assert_eq!(
+        record(&(1, ("a", ()))),
+        ["tuple", "i64(1)", "tuple", "str(a)", "tuple"]
+    );
+}
+
+#[test]
+fn test_defaults() {
+    // Only handles numbers and strings.
+    struct Sink(Vec<String>);
+
+    impl TypedVisit for Sink {
+        fn visit_i64(&mut self, v: i64) {
+            self.0.push(v.to_string());
+        }
+
+        fn visit_str(&mut self, v: &str) {
+            self.0.push(v.to_string());
+        }
+    }
+
+    let mut sink = Sink(vec![]);
+    (1_u8, "a", true, -2_i128, vec![3]).visit(&mut Typed::new(&mut sink));
+    assert_eq!(sink.0, ["a", "-2"]);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct S {
+        f: u8,
+    }
+
+    #[derive(Valuable)]
+    enum E {
+        V(i8),
+    }
+
+    #[test]
+    fn test_struct_and_enum() {
+        assert_eq!(record(&S { f: 1 }), ["other(S { f: 1 })"]);
+        assert_eq!(record(&E::V(1)), ["other(E::V(1))"]);
+
+        struct Fields(Vec<String>);
+
+        impl TypedVisit for Fields {
+            fn visit_struct(&mut self, v: &dyn Structable) {
+                self.0.push(v.definition().name().to_string());
+                v.visit(&mut Typed::new(self));
+            }
+
+            fn visit_enum(&mut self, v: &dyn Enumerable) {
+                self.0.push(v.variant().name().to_string());
+                v.visit(&mut Typed::new(self));
+            }
+
+            fn visit_u64(&mut self, v: u64) {
+                self.0.push(v.to_string());
+            }
+
+            fn visit_i64(&mut self, v: i64) {
+                self.0.push(v.to_string());
+            }
+        }
+
+        let mut fields = Fields(vec![]);
+        let mut typed = Typed::new(&mut fields);
+        typed.visit_value(S { f: 1 }.as_value());
+        typed.visit_value(E::V(-1).as_value());
+        assert_eq!(fields.0, ["S", "1", "V", "-1"]);
+    }
+}
+
+#[test]
+fn test_control_flow() {
+    struct First(Option<i64>);
+
+    impl TypedVisit for First {
+        fn visit_i64(&mut self, v: i64) {
+            self.0 = Some(v);
+        }
+
+        fn control_flow(&self) -> ControlFlow<()> {
+            match self.0 {
+                Some(_) => ControlFlow::Break(()),
+                None => ControlFlow::Continue(()),
+            }
+        }
+    }
+
+    let mut first = First(None);
+    ("a", 1, 2).visit(&mut Typed::new(&mut first));
+    assert_eq!(first.0, Some(1));
+
+    let mut first = First(None);
+    [3, 4].visit(&mut Typed::new(&mut first));
+    assert_eq!(first.0, Some(3));
+}
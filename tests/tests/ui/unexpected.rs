@@ -50,4 +50,53 @@ struct Skip3 {
     f: (),
 }
 
+#[derive(Valuable)]
+#[valuable(bytes)]
+struct Bytes1 {
+    f: Vec<u8>,
+}
+#[derive(Valuable)]
+struct Bytes2 {
+    #[valuable(skip, bytes)]
+    f: Vec<u8>,
+}
+
+#[derive(Valuable)]
+#[valuable(option)]
+struct Option1 {
+    f: Option<u8>,
+}
+#[derive(Valuable)]
+struct Option2 {
+    #[valuable(bytes, option)]
+    f: Option<Vec<u8>>,
+}
+#[derive(Valuable)]
+#[valuable(transparent)]
+struct Option3 {
+    #[valuable(option)]
+    f: Option<u8>,
+}
+
+#[derive(Valuable)]
+#[valuable(chunk_size = 0)]
+struct ChunkSize1 {
+    f: (),
+}
+#[derive(Valuable)]
+#[valuable(chunk_size = "2")]
+struct ChunkSize2 {
+    f: (),
+}
+#[derive(Valuable)]
+#[valuable(chunk_size = 2)]
+enum ChunkSize3 {
+    V(()),
+}
+#[derive(Valuable)]
+#[valuable(transparent, chunk_size = 2)]
+struct ChunkSize4 {
+    f: (),
+}
+
 fn main() {}
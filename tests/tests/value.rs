@@ -46,7 +46,7 @@ macro_rules! assert_as {
 
 macro_rules! assert_value {
     (
-        $ty:ty: $variant:ident, $as:ident, $eq:ident => $( $values:expr ),*
+        $ty:ty: $variant:ident $(($by_ref:tt))?, $as:ident, $eq:ident => $( $values:expr ),*
     ) => {{
         use Value::*;
 
@@ -54,7 +54,7 @@ macro_rules! assert_value {
 
         impl<'a> Visit for VisitValue<'a> {
             fn visit_value(&mut self, val: Value<'_>) {
-                assert!(matches!(val, $variant(v) if $eq(&v, &self.0)));
+                assert!(matches!(val, $variant($($by_ref)? v) if $eq(&v, &self.0)));
             }
         }
 
@@ -64,7 +64,7 @@ macro_rules! assert_value {
             let mut visit = VisitValue(src, std::marker::PhantomData);
             src.visit(&mut visit);
 
-            let val = Value::from(src);
+            let val = Value::from($($by_ref)? src);
 
             // Visit the converted value
             assert_visit_call!(&val);
@@ -78,13 +78,13 @@ macro_rules! assert_value {
             );
 
             // Test conversion
-            assert!(matches!(val, $variant(v) if $eq(&v, &src)));
+            assert!(matches!(val, $variant($($by_ref)? v) if $eq(&v, &src)));
 
             // Test `as_value()`
-            assert!(matches!(Valuable::as_value(&val), $variant(v) if $eq(&v, &src)));
+            assert!(matches!(Valuable::as_value(&val), $variant($($by_ref)? v) if $eq(&v, &src)));
 
             // Test clone()
-            assert!(matches!(val.clone(), $variant(v) if $eq(&v, &src)));
+            assert!(matches!(val.clone(), $variant($($by_ref)? v) if $eq(&v, &src)));
 
             // Test self as_*() conversion
             assert!($eq(&val.$as().unwrap(), &src));
@@ -120,7 +120,7 @@ macro_rules! assert_value {
 
 macro_rules! ints {
     (
-        $( $n:expr ),*
+        $( $n:expr, $n128:expr ),*
      ) => {{
         vec![
             $(
@@ -128,13 +128,13 @@ macro_rules! ints {
                 <u16>::try_from($n).ok().map(Value::from),
                 <u32>::try_from($n).ok().map(Value::from),
                 <u64>::try_from($n).ok().map(Value::from),
-                <u128>::try_from($n).ok().map(Value::from),
+                $n128.0.as_ref().map(Value::from),
                 <usize>::try_from($n).ok().map(Value::from),
                 <i8>::try_from($n).ok().map(Value::from),
                 <i16>::try_from($n).ok().map(Value::from),
                 <i32>::try_from($n).ok().map(Value::from),
                 <i64>::try_from($n).ok().map(Value::from),
-                <i128>::try_from($n).ok().map(Value::from),
+                $n128.1.as_ref().map(Value::from),
                 <isize>::try_from($n).ok().map(Value::from),
             )*
         ]
@@ -147,7 +147,7 @@ macro_rules! ints {
 macro_rules! test_num {
     (
         $(
-            $name:ident($as:ident, $ty:ty, $variant:ident);
+            $name:ident($as:ident, $ty:ty, $variant:ident $(($by_ref:tt))?);
         )*
      ) => {
          // Stringify all variants
@@ -172,21 +172,28 @@ macro_rules! test_num {
                     Value::from("hello world"),
                 ];
 
-                for &shift in &[
+                // `Value` borrows 128-bit integers, so they are kept alive
+                // for the whole test.
+                let wide: Vec<u128> = [
                     0, 8, 16, 24, 32, 48, 64, 72, 80, 88, 96, 104, 112, 120, 126, 127
-                ] {
-                    let actual = u128::MAX.checked_shr(shift).unwrap();
+                ]
+                .iter()
+                .map(|&shift| u128::MAX.checked_shr(shift).unwrap())
+                .collect();
 
-                    match <$ty>::try_from(actual) {
+                for actual in &wide {
+                    match <$ty>::try_from(*actual) {
                         Ok(v) => valid.push(v),
                         Err(_) => invalid.push(Value::from(actual)),
                     }
                 }
 
                 for &n in &valid {
-                    assert_value!($ty: $variant, $as, eq => n);
+                    assert_value!($ty: $variant $(($by_ref))?, $as, eq => n);
 
-                    for val in ints!(n) {
+                    let n128 = (<u128>::try_from(n).ok(), <i128>::try_from(n).ok());
+
+                    for val in ints!(n, n128) {
                         assert_eq!(Some(n), val.$as());
                     }
                 }
@@ -236,6 +243,111 @@ fn test_str() {
     assert_value!(&'a str: String, as_str, eq => "hello world", &string);
 }
 
+#[test]
+fn test_bytes() {
+    let val = Value::from(&b"a\x00\"\xff"[..]);
+
+    assert_visit_call!(&val);
+    assert!(matches!(val, Value::Bytes(b) if b == b"a\x00\"\xff"));
+    assert_eq!(val.as_bytes(), Some(&b"a\x00\"\xff"[..]));
+    assert_eq!(format!("{:?}", val), r#"b"a\x00\"\xff""#);
+    assert!(val.as_listable().is_none());
+
+    let bytes = Bytes(vec![1_u8, 2]);
+    assert_visit_call!(&bytes);
+    assert_eq!(bytes.as_value().as_bytes(), Some(&[1, 2][..]));
+}
+
+#[test]
+fn test_display() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    let args = format_args!("{}-{}", "a", 1);
+    let val = args.as_value();
+    assert_visit_call!(&args);
+    assert!(matches!(val, Value::Display(_)));
+    assert_eq!(val.as_display().unwrap().to_string(), "a-1");
+    assert_eq!(format!("{:?}", val), "a-1");
+    assert!(val.as_str().is_none());
+    assert!(val.as_debug().is_none());
+
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 80);
+    assert_eq!(format!("{:?}", addr.as_value()), "127.0.0.1:80");
+    assert_eq!(format!("{:?}", vec![addr].as_value()), "[127.0.0.1:80]");
+}
+
+#[test]
+fn test_debug() {
+    use std::time::Duration;
+
+    let timeout = Duration::from_millis(1500);
+    let val = Value::Debug(&timeout);
+    assert_eq!(format!("{:?}", val), "1.5s");
+    assert!(val.as_debug().is_some());
+    assert!(val.as_display().is_none());
+
+    // Formatted values are captured, and compared, as their output.
+    assert_eq!(OwnedValue::capture(&val), OwnedValue::String("1.5s".into()));
+    assert_eq!(Value::Display(&1), Value::Display(&"1"));
+    assert_eq!(Value::Display(&1), Value::Debug(&1));
+    assert_eq!(Value::Display(&"a"), Value::String("a"));
+    assert_ne!(Value::Display(&"a"), Value::Debug(&"a"));
+    assert!(Value::Display(&"a") < Value::String("b"));
+
+    // Whether they are nested or not.
+    let nested = OwnedValue::capture(&vec![Value::Display(&"a")]);
+    assert_eq!(nested.as_value(), vec![Value::String("a")].as_value());
+    assert_eq!(
+        hash(&Value::Display(&"a")),
+        hash(&OwnedValue::capture(&Value::Display(&"a")).as_value())
+    );
+    assert_eq!(hash(&Value::Display(&"a")), hash(&Value::String("a")));
+}
+
+fn hash(value: &Value<'_>) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_downcast_ref() {
+    use std::any::Any;
+
+    struct Ids(Vec<u64>);
+
+    impl Valuable for Ids {
+        fn as_value(&self) -> Value<'_> {
+            Value::Listable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            self.0.visit(visit);
+        }
+    }
+
+    impl Listable for Ids {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.0.size_hint()
+        }
+
+        fn as_any(&self) -> Option<&dyn Any> {
+            Some(self)
+        }
+    }
+
+    let ids = Ids(vec![1, 2]);
+    let val = Value::Listable(&ids);
+    assert_eq!(val.downcast_ref::<Ids>().unwrap().0, [1, 2]);
+    assert!(val.downcast_ref::<Vec<u64>>().is_none());
+
+    // Types which do not opt in, and primitives.
+    assert!(ids.0.as_value().downcast_ref::<Vec<u64>>().is_none());
+    assert!(Value::U64(1).downcast_ref::<u64>().is_none());
+}
+
 #[test]
 fn test_path() {
     use std::path;
@@ -265,13 +377,13 @@ test_num! {
     test_u16(as_u16, u16, U16);
     test_u32(as_u32, u32, U32);
     test_u64(as_u64, u64, U64);
-    test_u128(as_u128, u128, U128);
+    test_u128(as_u128, u128, U128(&));
     test_usize(as_usize, usize, Usize);
     test_i8(as_i8, i8, I8);
     test_i16(as_i16, i16, I16);
     test_i32(as_i32, i32, I32);
     test_i64(as_i64, i64, I64);
-    test_i128(as_i128, i128, I128);
+    test_i128(as_i128, i128, I128(&));
     test_isize(as_isize, isize, Isize);
 }
 
@@ -326,6 +438,29 @@ fn test_atomic() {
     assert!(matches!(val.as_value(), Value::Usize(v) if v == usize::MAX));
 }
 
+#[test]
+fn test_size() {
+    // `Value` is no larger than two pointers and a tag, so it is cheap to
+    // copy. 128-bit integers are stored by reference to keep it this size.
+    assert!(core::mem::size_of::<Value<'_>>() <= 3 * core::mem::size_of::<usize>());
+    assert!(core::mem::size_of::<Option<Value<'_>>>() <= 3 * core::mem::size_of::<usize>());
+}
+
+#[test]
+fn test_128_bit_by_ref() {
+    use core::num::{NonZeroI128, NonZeroU128};
+
+    let n = i128::MIN;
+    assert!(matches!(Value::from(&n), Value::I128(v) if *v == i128::MIN));
+    assert!(matches!(n.as_value(), Value::I128(v) if core::ptr::eq(v, &n)));
+
+    let n = NonZeroI128::new(-1).unwrap();
+    assert!(matches!(n.as_value(), Value::I128(&-1)));
+    let n = NonZeroU128::new(u128::MAX).unwrap();
+    assert!(matches!(n.as_value(), Value::U128(&u128::MAX)));
+    assert_eq!(n.as_value().as_u128(), Some(u128::MAX));
+}
+
 fn eq<T: PartialEq>(a: &T, b: &T) -> bool {
     *a == *b
 }
@@ -0,0 +1,109 @@
+use valuable::*;
+
+use core::ops::ControlFlow;
+
+/// Records visited values and breaks once `limit` values have been seen.
+struct Take {
+    limit: usize,
+    values: Vec<String>,
+}
+
+impl Take {
+    fn new(limit: usize) -> Take {
+        Take {
+            limit,
+            values: vec![],
+        }
+    }
+}
+
+impl Visit for Take {
+    fn visit_value(&mut self, value: Value<'_>) {
+        self.values.push(format!("{:?}", value));
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        self.values.push(format!("{:?}: {:?}", key, value));
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        if self.values.len() >= self.limit {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+#[test]
+fn test_default_continue() {
+    struct Noop;
+
+    impl Visit for Noop {
+        fn visit_value(&mut self, _: Value<'_>) {}
+    }
+
+    assert_eq!(Noop.control_flow(), ControlFlow::Continue(()));
+}
+
+#[test]
+fn test_break_primitive_slice() {
+    let mut visit = Take::new(2);
+    [1_u8, 2, 3, 4].visit(&mut visit);
+    assert_eq!(visit.values, ["1", "2"]);
+
+    let mut visit = Take::new(0);
+    [1_u8, 2, 3, 4].visit(&mut visit);
+    assert!(visit.values.is_empty());
+}
+
+#[test]
+fn test_break_slice() {
+    let list = ["a", "b", "c"];
+
+    let mut visit = Take::new(1);
+    list.visit(&mut visit);
+    assert_eq!(visit.values, [r#""a""#]);
+}
+
+#[test]
+fn test_break_collections() {
+    let mut visit = Take::new(2);
+    std::collections::BTreeSet::from([1, 2, 3]).visit(&mut visit);
+    assert_eq!(visit.values, ["1", "2"]);
+
+    let mut deque = std::collections::VecDeque::new();
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+
+    let mut visit = Take::new(1);
+    deque.visit(&mut visit);
+    assert_eq!(visit.values, ["1"]);
+}
+
+#[test]
+fn test_break_maps() {
+    let map = std::collections::BTreeMap::from([("a", 1), ("b", 2), ("c", 3)]);
+
+    let mut visit = Take::new(2);
+    map.visit(&mut visit);
+    assert_eq!(visit.values, [r#""a": 1"#, r#""b": 2"#]);
+
+    let map: std::collections::HashMap<_, _> = map.into_iter().collect();
+
+    let mut visit = Take::new(1);
+    map.visit(&mut visit);
+    assert_eq!(visit.values.len(), 1);
+}
+
+#[test]
+fn test_control_flow_forwarded() {
+    let mut visit = Take::new(1);
+    vec![1, 2, 3].visit(&mut &mut visit);
+    assert_eq!(visit.values, ["1"]);
+
+    let mut visit: Box<dyn Visit> = Box::new(Take::new(0));
+    assert_eq!(visit.control_flow(), ControlFlow::Break(()));
+    vec![1, 2, 3].visit(&mut visit);
+}
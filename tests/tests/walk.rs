@@ -0,0 +1,285 @@
+use valuable::path::Location;
+use valuable::walk::{walk, Action, Event, Handler};
+use valuable::*;
+
+use std::collections::BTreeMap;
+
+/// Formats the events of walking `value` as `<location> <event>` lines.
+fn events(value: &dyn Valuable) -> Vec<String> {
+    events_with(value, |_, _| Action::Continue)
+}
+
+fn events_with(
+    value: &dyn Valuable,
+    mut action: impl FnMut(&Location<'_>, &Event<'_>) -> Action,
+) -> Vec<String> {
+    let mut events = vec![];
+    walk(value, &mut |location: &Location<'_>, event: Event<'_>| {
+        let description = match event {
+            Event::Value(value) => format!("{:?}", value),
+            Event::EnterStruct(v) => format!("struct {}", v.definition().name()),
+            Event::EnterEnum(v) => {
+                format!("enum {}::{}", v.definition().name(), v.variant().name())
+            }
+            Event::EnterTuple(_) => "tuple".to_string(),
+            Event::EnterList(_) => "list".to_string(),
+            Event::EnterMap(_) => "map".to_string(),
+            Event::Field(name) => format!("field {}", name),
+            Event::Entry(key) => format!("entry {:?}", key),
+            Event::Leave => "leave".to_string(),
+            _ => unreachable!(),
+        };
+        events.push(format!("{} {}", location, description).trim().to_string());
+        action(location, &event)
+    });
+    events
+}
+
+#[test]
+fn test_primitive() {
+    assert_eq!(events(&1_u8), ["1"]);
+    assert_eq!(events(&"hello"), [r#""hello""#]);
+}
+
+#[test]
+fn test_list() {
+    assert_eq!(
+        events(&vec![vec![1], vec![]]),
+        [
+            "list",
+            "[0] list",
+            "[0][0] 1",
+            "[0] leave",
+            "[1] list",
+            "[1] leave",
+            "leave"
+        ],
+    );
+
+    assert_eq!(
+        events(&vec!["a", "b"]),
+        ["list", r#"[0] "a""#, r#"[1] "b""#, "leave"],
+    );
+}
+
+#[test]
+fn test_map() {
+    let map = BTreeMap::from([("a", (1, true))]);
+    assert_eq!(
+        events(&map),
+        [
+            "map",
+            r#"["a"] entry "a""#,
+            r#"["a"] tuple"#,
+            r#"["a"].0 1"#,
+            r#"["a"].1 true"#,
+            r#"["a"] leave"#,
+            "leave",
+        ],
+    );
+}
+
+#[test]
+fn test_skip() {
+    let value = vec![vec![1, 2], vec![3]];
+    let events = events_with(&value, |location, event| match event {
+        Event::EnterList(_) if location.depth() == 1 => Action::Skip,
+        _ => Action::Continue,
+    });
+    assert_eq!(
+        events,
+        [
+            "list",
+            "[0] list",
+            "[0] leave",
+            "[1] list",
+            "[1] leave",
+            "leave"
+        ]
+    );
+
+    let map = BTreeMap::from([("a", 1), ("b", 2)]);
+    let events = events_with(&map, |_, event| match event {
+        Event::Entry(key) if key.as_str() == Some("a") => Action::Skip,
+        _ => Action::Continue,
+    });
+    assert_eq!(
+        events,
+        [
+            "map",
+            r#"["a"] entry "a""#,
+            r#"["b"] entry "b""#,
+            r#"["b"] 2"#,
+            "leave"
+        ]
+    );
+}
+
+#[test]
+fn test_stop() {
+    let value = vec![vec![1, 2, 3], vec![4]];
+    let events = events_with(&value, |_, event| match event {
+        Event::Value(Value::I32(2)) => Action::Stop,
+        _ => Action::Continue,
+    });
+    assert_eq!(events, ["list", "[0] list", "[0][0] 1", "[0][1] 2"]);
+
+    let map = BTreeMap::from([("a", 1), ("b", 2)]);
+    let events = events_with(&map, |_, event| match event {
+        Event::Entry(_) => Action::Stop,
+        _ => Action::Continue,
+    });
+    assert_eq!(events, ["map", r#"["a"] entry "a""#]);
+}
+
+#[test]
+fn test_stop_ignored() {
+    // Calls every `Visit` method, whatever `control_flow()` returns.
+    struct Eager;
+
+    impl Valuable for Eager {
+        fn as_value(&self) -> Value<'_> {
+            Value::Listable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            visit.visit_value(Value::I32(1));
+            visit.visit_value(Value::I32(2));
+            visit.visit_entry(Value::I32(3), Value::I32(4));
+        }
+    }
+
+    impl Listable for Eager {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (2, Some(2))
+        }
+    }
+
+    let events = events_with(&Eager, |_, event| match event {
+        Event::Value(Value::I32(1)) => Action::Stop,
+        _ => Action::Continue,
+    });
+    assert_eq!(events, ["list", "[0] 1"]);
+}
+
+struct Depths {
+    max_depth: usize,
+    deepest: usize,
+    leaves: usize,
+}
+
+impl Depths {
+    fn new(max_depth: usize) -> Depths {
+        Depths {
+            max_depth,
+            deepest: 0,
+            leaves: 0,
+        }
+    }
+}
+
+impl Handler for Depths {
+    fn event(&mut self, location: &Location<'_>, event: Event<'_>) -> Action {
+        self.deepest = self.deepest.max(location.depth());
+        if let Event::Leave = event {
+            self.leaves += 1;
+        }
+        Action::Continue
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+#[test]
+fn test_deep() {
+    // Values nested deeper than a visitor would usually handle.
+    let mut value = OwnedValue::I32(0);
+    for _ in 0..1_000 {
+        value = OwnedValue::Listable(OwnedList::from(vec![value]));
+    }
+
+    let mut depths = Depths::new(1_000);
+    walk(&value, &mut depths);
+    assert_eq!(depths.deepest, 1_000);
+    assert_eq!(depths.leaves, 1_000);
+
+    // By default, the lists below depth 128 are not entered.
+    let mut deepest = 0;
+    let mut leaves = 0;
+    walk(&value, &mut |location: &Location<'_>, event: Event<'_>| {
+        deepest = deepest.max(location.depth());
+        if let Event::Leave = event {
+            leaves += 1;
+        }
+        Action::Continue
+    });
+    assert_eq!(deepest, 128);
+    assert_eq!(leaves, 129);
+
+    let mut depths = Depths::new(0);
+    walk(&value, &mut depths);
+    assert_eq!(depths.deepest, 0);
+    assert_eq!(depths.leaves, 1);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use super::*;
+
+    #[derive(Valuable)]
+    struct Person {
+        name: &'static str,
+        pet: Option<Pet>,
+    }
+
+    #[derive(Valuable)]
+    enum Pet {
+        Dog { name: &'static str },
+        Fish(u8),
+    }
+
+    #[test]
+    fn test_struct() {
+        let person = Person {
+            name: "Ferris",
+            pet: Some(Pet::Dog { name: "Rex" }),
+        };
+        assert_eq!(
+            events(&person),
+            [
+                "struct Person",
+                ".name field name",
+                r#".name "Ferris""#,
+                ".pet field pet",
+                ".pet enum Pet::Dog",
+                ".pet::Dog.name field name",
+                r#".pet::Dog.name "Rex""#,
+                ".pet leave",
+                "leave",
+            ],
+        );
+
+        let person = Person {
+            name: "Ferris",
+            pet: Some(Pet::Fish(3)),
+        };
+        let events = events_with(&person, |_, event| match event {
+            Event::Field("name") => Action::Skip,
+            _ => Action::Continue,
+        });
+        assert_eq!(
+            events,
+            [
+                "struct Person",
+                ".name field name",
+                ".pet field pet",
+                ".pet enum Pet::Fish",
+                ".pet::Fish.0 3",
+                ".pet leave",
+                "leave",
+            ],
+        );
+    }
+}
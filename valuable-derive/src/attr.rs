@@ -30,6 +30,13 @@ static ATTRS: &[AttrDef] = &[
         ],
         style: &[MetaStyle::Ident],
     },
+    // #[valuable(chunk_size = ...)]
+    AttrDef {
+        name: "chunk_size",
+        conflicts_with: &["transparent"],
+        position: &[Position::Struct],
+        style: &[MetaStyle::NameValue],
+    },
     // #[valuable(skip)]
     AttrDef {
         name: "skip",
@@ -42,12 +49,29 @@ static ATTRS: &[AttrDef] = &[
         ],
         style: &[MetaStyle::Ident],
     },
+    // #[valuable(bytes)]
+    AttrDef {
+        name: "bytes",
+        conflicts_with: &["skip"],
+        position: &[Position::NamedField, Position::UnnamedField],
+        style: &[MetaStyle::Ident],
+    },
+    // #[valuable(option)]
+    AttrDef {
+        name: "option",
+        conflicts_with: &["skip", "bytes"],
+        position: &[Position::NamedField, Position::UnnamedField],
+        style: &[MetaStyle::Ident],
+    },
 ];
 
 pub(crate) struct Attrs {
     rename: Option<(syn::MetaNameValue, syn::LitStr)>,
     transparent: Option<Span>,
+    chunk_size: Option<usize>,
     skip: Option<Span>,
+    bytes: Option<Span>,
+    option: Option<Span>,
 }
 
 impl Attrs {
@@ -62,15 +86,30 @@ impl Attrs {
         self.transparent.is_some()
     }
 
+    pub(crate) fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size
+    }
+
     pub(crate) fn skip(&self) -> bool {
         self.skip.is_some()
     }
+
+    pub(crate) fn bytes(&self) -> bool {
+        self.bytes.is_some()
+    }
+
+    pub(crate) fn option(&self) -> bool {
+        self.option.is_some()
+    }
 }
 
 pub(crate) fn parse_attrs(cx: &Context, attrs: &[syn::Attribute], pos: Position) -> Attrs {
     let mut rename = None;
     let mut transparent = None;
+    let mut chunk_size = None;
     let mut skip = None;
+    let mut bytes = None;
+    let mut option = None;
 
     let attrs = filter_attrs(cx, attrs, pos);
     for (def, meta) in &attrs {
@@ -102,8 +141,34 @@ pub(crate) fn parse_attrs(cx: &Context, attrs: &[syn::Attribute], pos: Position)
             "rename" => lit_str!(rename),
             // #[valuable(transparent)]
             "transparent" => transparent = Some(meta.span()),
+            // #[valuable(chunk_size = ...)]
+            "chunk_size" => {
+                let m = match meta {
+                    Meta::NameValue(m) => m,
+                    _ => unreachable!(),
+                };
+                let lit = match &m.value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(l),
+                        ..
+                    }) => l,
+                    l => {
+                        cx.error(format_err!(l, "expected integer literal"));
+                        continue;
+                    }
+                };
+                match lit.base10_parse::<usize>() {
+                    Ok(0) => cx.error(format_err!(lit, "chunk_size must be greater than zero")),
+                    Ok(n) => chunk_size = Some(n),
+                    Err(e) => cx.error(e),
+                }
+            }
             // #[valuable(skip)]
             "skip" => skip = Some(meta.span()),
+            // #[valuable(bytes)]
+            "bytes" => bytes = Some(meta.span()),
+            // #[valuable(option)]
+            "option" => option = Some(meta.span()),
 
             _ => unreachable!("{}", def.name),
         }
@@ -112,7 +177,10 @@ pub(crate) fn parse_attrs(cx: &Context, attrs: &[syn::Attribute], pos: Position)
     Attrs {
         rename,
         transparent,
+        chunk_size,
         skip,
+        bytes,
+        option,
     }
 }
 
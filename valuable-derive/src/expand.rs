@@ -31,6 +31,12 @@ fn derive_struct(
         .iter()
         .map(|f| parse_attrs(&cx, &f.attrs, Position::from(&data.fields)))
         .collect();
+    if struct_attrs.transparent() && field_attrs.iter().any(Attrs::option) {
+        cx.error(Error::new_spanned(
+            input,
+            "#[valuable(option)] may not be used on the field of a #[valuable(transparent)] struct",
+        ));
+    }
     if struct_attrs.transparent() && data.fields.len() != 1 {
         cx.error(Error::new_spanned(
             input,
@@ -55,15 +61,21 @@ fn derive_struct(
             ToTokens::to_token_stream,
         );
         let access = respan(quote! { &self.#access }, &field.ty);
+        let value = field_value(&access, &field_attrs[0]);
+        let visit = if field_attrs[0].bytes() {
+            quote! { visitor.visit_value(#value); }
+        } else {
+            quote! { ::valuable::Valuable::visit(#access, visitor); }
+        };
         let valuable_impl = quote! {
             #[automatically_derived]
             impl #impl_generics ::valuable::Valuable for #name #ty_generics #where_clause {
                 fn as_value(&self) -> ::valuable::Value<'_> {
-                    ::valuable::Valuable::as_value(#access)
+                    #value
                 }
 
                 fn visit(&self, visitor: &mut dyn ::valuable::Visit) {
-                    ::valuable::Valuable::visit(#access, visitor);
+                    #visit
                 }
             }
         };
@@ -79,6 +91,8 @@ fn derive_struct(
     let visit_fields;
     let struct_def;
     let mut named_fields_statics = None;
+    let mut field_fn = None;
+    let field_at_fn;
 
     match &data.fields {
         syn::Fields::Named(_) => {
@@ -97,26 +111,73 @@ fn derive_struct(
                 )
             };
 
-            let fields = data
+            let fields: Vec<_> = data
                 .fields
                 .iter()
                 .enumerate()
                 .filter(|(i, _)| !field_attrs[*i].skip())
-                .map(|(_, field)| {
+                .map(|(i, field)| {
                     let f = field.ident.as_ref();
                     let tokens = quote! {
                         &self.#f
                     };
-                    respan(tokens, &field.ty)
+                    field_value(&respan(tokens, &field.ty), &field_attrs[i])
+                })
+                .collect();
+            let lookups: Vec<_> = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !field_attrs[*i].skip())
+                .zip(&fields)
+                .map(|((i, _), value)| field_lookup(value, &field_attrs[i]))
+                .collect();
+            let field_names = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !field_attrs[*i].skip())
+                .map(|(i, field)| field_attrs[i].rename(field.ident.as_ref().unwrap()));
+
+            if !fields.is_empty() {
+                field_fn = Some(quote! {
+                    fn field(&self, name: &str) -> ::core::option::Option<::valuable::Value<'_>> {
+                        match name {
+                            #(#field_names => #lookups,)*
+                            _ => ::core::option::Option::None,
+                        }
+                    }
                 });
-            visit_fields = quote! {
-                visitor.visit_named_fields(&::valuable::NamedValues::new(
-                    #named_fields_static_name,
-                    &[
-                        #(::valuable::Valuable::as_value(#fields),)*
-                    ],
-                ));
             }
+            field_at_fn = field_at(&lookups);
+
+            visit_fields = match struct_attrs.chunk_size() {
+                Some(1) => visit_in_chunks(&fields, 1, |start, values| {
+                    let value = &values[0];
+                    quote! {
+                        visitor.visit_named_field(&#named_fields_static_name[#start], #value);
+                    }
+                }),
+                Some(chunk_size) => visit_in_chunks(&fields, chunk_size, |start, values| {
+                    let end = start + values.len();
+                    quote! {
+                        visitor.visit_named_fields(&::valuable::NamedValues::new(
+                            &#named_fields_static_name[#start..#end],
+                            &[
+                                #(#values,)*
+                            ],
+                        ));
+                    }
+                }),
+                None => quote! {
+                    visitor.visit_named_fields(&::valuable::NamedValues::new(
+                        #named_fields_static_name,
+                        &[
+                            #(#fields,)*
+                        ],
+                    ));
+                },
+            };
         }
         syn::Fields::Unnamed(_) | syn::Fields::Unit => {
             let indices: Vec<_> = data
@@ -129,9 +190,17 @@ fn derive_struct(
                     let tokens = quote! {
                         &self.#index
                     };
-                    respan(tokens, &field.ty)
+                    field_value(&respan(tokens, &field.ty), &field_attrs[i])
                 })
                 .collect();
+            let lookups: Vec<_> = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !field_attrs[*i].skip())
+                .zip(&indices)
+                .map(|((i, _), value)| field_lookup(value, &field_attrs[i]))
+                .collect();
 
             let len = indices.len();
             struct_def = quote! {
@@ -141,22 +210,37 @@ fn derive_struct(
                 )
             };
 
-            visit_fields = quote! {
-                visitor.visit_unnamed_fields(
-                    &[
-                        #(::valuable::Valuable::as_value(#indices),)*
-                    ],
-                );
+            field_at_fn = field_at(&lookups);
+
+            let visit_chunk = |_, values: &[TokenStream]| {
+                quote! {
+                    visitor.visit_unnamed_fields(
+                        &[
+                            #(#values,)*
+                        ],
+                    );
+                }
+            };
+            visit_fields = match struct_attrs.chunk_size() {
+                Some(chunk_size) => visit_in_chunks(&indices, chunk_size, visit_chunk),
+                None => visit_chunk(0, &indices),
             };
         }
     }
 
+    let as_any_fn = as_any(&input.generics);
     let structable_impl = quote! {
         #[automatically_derived]
         impl #impl_generics ::valuable::Structable for #name #ty_generics #where_clause {
             fn definition(&self) -> ::valuable::StructDef<'_> {
                 #struct_def
             }
+
+            #field_fn
+
+            #field_at_fn
+
+            #as_any_fn
         }
     };
 
@@ -170,6 +254,13 @@ fn derive_struct(
             fn visit(&self, visitor: &mut dyn ::valuable::Visit) {
                 #visit_fields
             }
+
+            fn visit_slice(slice: &[Self], visitor: &mut dyn ::valuable::Visit)
+            where
+                Self: ::core::marker::Sized,
+            {
+                ::valuable::StructRows::visit_slice(slice, visitor);
+            }
         }
     };
 
@@ -243,7 +334,7 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
 
                 let mut fields = Vec::with_capacity(variant.fields.len());
                 let mut as_value = Vec::with_capacity(variant.fields.len());
-                for (_, field) in variant
+                for (i, field) in variant
                     .fields
                     .iter()
                     .enumerate()
@@ -256,7 +347,10 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
                         // but is needed to emulate multi-token span on stable Rust.
                         &#f
                     };
-                    as_value.push(respan(tokens, &field.ty));
+                    as_value.push(field_value(
+                        &respan(tokens, &field.ty),
+                        &field_attrs[variant_index][i],
+                    ));
                 }
                 let skipped = if fields.len() == variant.fields.len() {
                     quote! {}
@@ -269,7 +363,7 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
                             &::valuable::NamedValues::new(
                                 #named_fields_static_name,
                                 &[
-                                    #(::valuable::Valuable::as_value(#as_value),)*
+                                    #(#as_value,)*
                                 ],
                             ),
                         );
@@ -291,13 +385,13 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
                     .zip(&variant.fields)
                     .enumerate()
                     .filter(|(i, _)| !field_attrs[variant_index][*i].skip())
-                    .map(|(_, (binding, field))| {
+                    .map(|(i, (binding, field))| {
                         let tokens = quote! {
                             // HACK(taiki-e): This `&` is not actually needed to calling as_value,
                             // but is needed to emulate multi-token span on stable Rust.
                             &#binding
                         };
-                        respan(tokens, &field.ty)
+                        field_value(&respan(tokens, &field.ty), &field_attrs[variant_index][i])
                     })
                     .collect();
 
@@ -313,7 +407,7 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
                     Self::#variant_name(#(#bindings),*) => {
                         visitor.visit_unnamed_fields(
                             &[
-                                #(::valuable::Valuable::as_value(#as_value),)*
+                                #(#as_value,)*
                             ],
                         );
                     }
@@ -351,6 +445,7 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
     };
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let as_any_fn = as_any(&input.generics);
     let enumerable_impl = quote! {
         #[automatically_derived]
         impl #impl_generics ::valuable::Enumerable for #name #ty_generics #where_clause {
@@ -366,6 +461,8 @@ fn derive_enum(cx: Context, input: &syn::DeriveInput, data: &syn::DataEnum) -> R
                     #(#variant_fn)*
                 }
             }
+
+            #as_any_fn
         }
     };
 
@@ -416,6 +513,99 @@ fn named_fields_static(name: &Ident, fields: &syn::Fields, field_attrs: &[Attrs]
     }
 }
 
+// Visits the fields of a `#[valuable(chunk_size = ...)]` struct, by calling
+// `visit_chunk` with the index of the first field of each chunk and the values
+// of its fields. The visitor's `control_flow` is checked between chunks.
+fn visit_in_chunks(
+    values: &[TokenStream],
+    chunk_size: usize,
+    visit_chunk: impl Fn(usize, &[TokenStream]) -> TokenStream,
+) -> TokenStream {
+    if values.is_empty() {
+        return visit_chunk(0, values);
+    }
+    let chunks = values
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(i, chunk)| visit_chunk(i * chunk_size, chunk));
+    let mut tokens = TokenStream::new();
+    for (i, chunk) in chunks.enumerate() {
+        if i != 0 {
+            tokens.extend(quote! {
+                if let ::core::ops::ControlFlow::Break(()) = visitor.control_flow() {
+                    return;
+                }
+            });
+        }
+        tokens.extend(chunk);
+    }
+    tokens
+}
+
+// `Structable::field_at`, given the lookups of the visited fields. Returns
+// nothing for structs without fields, for which the default implementation is
+// sufficient.
+fn field_at(lookups: &[TokenStream]) -> Option<TokenStream> {
+    if lookups.is_empty() {
+        return None;
+    }
+    let indices = 0..lookups.len();
+    Some(quote! {
+        fn field_at(&self, index: usize) -> ::core::option::Option<::valuable::Value<'_>> {
+            match index {
+                #(#indices => #lookups,)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    })
+}
+
+// `as_any`, for types without generic parameters. Only those are known to be
+// `'static`.
+fn as_any(generics: &syn::Generics) -> Option<TokenStream> {
+    if !generics.params.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        fn as_any(&self) -> ::core::option::Option<&dyn ::core::any::Any> {
+            ::core::option::Option::Some(self)
+        }
+    })
+}
+
+// The `Value` passed to the visitor for a field, given an expression that
+// borrows the field.
+fn field_value(access: &TokenStream, attrs: &Attrs) -> TokenStream {
+    if attrs.bytes() {
+        quote! {
+            ::valuable::Value::Bytes(::core::convert::AsRef::<[u8]>::as_ref(#access))
+        }
+    } else if attrs.option() {
+        // The `Optional` is a temporary, so this value can only be used while
+        // visiting.
+        quote! {
+            ::valuable::Value::Enumerable(
+                &::valuable::Optional(::core::option::Option::as_ref(#access)),
+            )
+        }
+    } else {
+        quote! {
+            ::valuable::Valuable::as_value(#access)
+        }
+    }
+}
+
+// The `Option<Value>` returned by `Structable::field` and `field_at` for a
+// field, given its value. Optional fields are only represented as an enum
+// during a visit, so they cannot be returned.
+fn field_lookup(value: &TokenStream, attrs: &Attrs) -> TokenStream {
+    if attrs.option() {
+        quote! { ::core::option::Option::None }
+    } else {
+        quote! { ::core::option::Option::Some(#value) }
+    }
+}
+
 // Returns attributes that should be applied to generated code.
 fn allowed_lints() -> TokenStream {
     quote! {
@@ -23,10 +23,41 @@ use syn::parse_macro_input;
 ///
 /// This attribute can only be used on a struct that has a single field.
 ///
+/// ## `#[valuable(chunk_size = N)]`
+///
+/// Visit the fields of the struct in chunks of at most `N` fields, instead of
+/// all at once. With `N = 1`, named fields are passed one at a time to
+/// `Visit::visit_named_field`. The visitor's `control_flow` is checked
+/// between chunks.
+///
+/// This avoids building an array of all field values for structs with many
+/// fields, and lets visitors stop early. This attribute can only be used on a
+/// struct.
+///
 /// ## `#[valuable(skip)]`
 ///
 /// Skip the field.
 ///
+/// ## `#[valuable(bytes)]`
+///
+/// Represent the field as `Value::Bytes`, as with the `valuable::Bytes`
+/// wrapper, instead of as a list of `u8` values.
+///
+/// The field's type must implement `AsRef<[u8]>`, as `Vec<u8>`, `[u8; N]`,
+/// and `&[u8]` do.
+///
+/// ## `#[valuable(option)]`
+///
+/// Represent the field, which must be an `Option<T>`, as an enum named
+/// `Option` with the variants `None` and `Some`, as with the
+/// `valuable::Optional` wrapper. By default, `Some(value)` is represented by
+/// the value itself and `None` by `Value::Unit`, so that `None` cannot be
+/// told apart from `()`.
+///
+/// The enum only exists while the field is visited, so `Structable::field`
+/// and `Structable::field_at` return `None` for the field. This attribute
+/// cannot be combined with `bytes`, nor used in a `transparent` struct.
+///
 /// # Examples
 ///
 /// ```
@@ -43,6 +74,30 @@ use syn::parse_macro_input;
 ///     Custom(String),
 /// }
 /// ```
+///
+/// Binary data and optional fields:
+///
+/// ```
+/// use valuable::Valuable;
+///
+/// #[derive(Valuable)]
+/// struct Packet {
+///     #[valuable(bytes)]
+///     payload: Vec<u8>,
+///     #[valuable(option)]
+///     checksum: Option<u32>,
+/// }
+///
+/// let packet = Packet {
+///     payload: vec![0xca, 0xfe],
+///     checksum: None,
+/// };
+///
+/// assert_eq!(
+///     format!("{:?}", packet.as_value()),
+///     r#"Packet { payload: b"\xca\xfe", checksum: Option::None }"#,
+/// );
+/// ```
 #[proc_macro_derive(Valuable, attributes(valuable))]
 pub fn derive_valuable(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as syn::DeriveInput);
@@ -41,7 +41,8 @@
 //! );
 //! ```
 
-use core::{fmt, mem};
+use core::ops::ControlFlow;
+use core::{fmt, mem, ptr};
 
 use serde::ser::{
     Error, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
@@ -49,7 +50,8 @@ use serde::ser::{
 };
 use serde::{Serialize, Serializer};
 use valuable::{
-    EnumDef, Fields, NamedValues, StructDef, TupleDef, Valuable, Value, Variant, VariantDef, Visit,
+    error::Error as StdError, EnumDef, Fields, NamedField, NamedValues, StructDef, TupleDef,
+    Valuable, Value, Variant, VariantDef, Visit, OPTION_VARIANTS,
 };
 
 /// A wrapper around [`Valuable`] types that implements [`Serialize`].
@@ -116,18 +118,22 @@ where
             Value::I16(n) => serializer.serialize_i16(n),
             Value::I32(n) => serializer.serialize_i32(n),
             Value::I64(n) => serializer.serialize_i64(n),
-            Value::I128(n) => serializer.serialize_i128(n),
+            Value::I128(&n) => serializer.serialize_i128(n),
             Value::Isize(n) => serializer.serialize_i64(n as _),
             Value::U8(n) => serializer.serialize_u8(n),
             Value::U16(n) => serializer.serialize_u16(n),
             Value::U32(n) => serializer.serialize_u32(n),
             Value::U64(n) => serializer.serialize_u64(n),
-            Value::U128(n) => serializer.serialize_u128(n),
+            Value::U128(&n) => serializer.serialize_u128(n),
             Value::Usize(n) => serializer.serialize_u64(n as _),
             Value::F32(n) => serializer.serialize_f32(n),
             Value::F64(n) => serializer.serialize_f64(n),
             Value::Char(c) => serializer.serialize_char(c),
             Value::String(s) => serializer.serialize_str(s),
+            Value::Bytes(b) => serializer.serialize_bytes(b),
+            Value::Display(d) => serializer.collect_str(d),
+            Value::Debug(d) => serializer.collect_str(&DisplayDebug(d)),
+            Value::Stringable(s) => serializer.collect_str(s),
             Value::Unit => serializer.serialize_unit(),
             Value::Listable(l) => {
                 let size_hint = l.size_hint();
@@ -157,10 +163,7 @@ where
                         serializer,
                     };
                     s.visit(&mut visitor);
-                    match visitor {
-                        VisitStaticStruct::End(res) => res,
-                        _ => unreachable!(),
-                    }
+                    visitor.end()
                 }
                 StructDef::Dynamic { fields, .. } => {
                     if fields.is_named() {
@@ -263,10 +266,11 @@ where
             }
             #[cfg(feature = "std")]
             Value::Path(p) => Serialize::serialize(p, serializer),
-            #[cfg(feature = "std")]
-            Value::Error(e) => SerializeError(e).serialize(serializer),
-
-            v => unimplemented!("{:?}", v),
+            // `Value::Error` only exists on some compilers and features.
+            v => match v.as_error() {
+                Some(e) => SerializeError(e).serialize(serializer),
+                None => unimplemented!("{:?}", v),
+            },
         }
     }
 }
@@ -302,6 +306,13 @@ impl<S: Serializer> Visit for VisitList<'_, S> {
             *self = Self::Error(S::Error::custom("visit_unnamed_fields in list"));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::Error(..) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
 }
 
 enum VisitMap<'a, S: Serializer> {
@@ -335,60 +346,113 @@ impl<S: Serializer> Visit for VisitMap<'_, S> {
             *self = Self::Error(S::Error::custom("visit_unnamed_fields in map"));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::Error(..) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
 }
 
+// Static structs usually visit all of their fields at once, but wide structs
+// may visit them in several chunks, so the serializer is kept between calls.
 enum VisitStaticStruct<S: Serializer> {
     Start {
         name: &'static str,
         fields: Fields<'static>,
         serializer: S,
     },
+    Named {
+        fields: &'static [NamedField<'static>],
+        // Index of the next field, used to find field names quickly.
+        next: usize,
+        ser: S::SerializeStruct,
+    },
+    Unnamed(S::SerializeTupleStruct),
     End(Result<S::Ok, S::Error>),
     Tmp,
 }
 
+impl<S: Serializer> VisitStaticStruct<S> {
+    fn end(self) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Named { ser, .. } => ser.end(),
+            Self::Unnamed(ser) => ser.end(),
+            Self::End(res) => res,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl<S: Serializer> Visit for VisitStaticStruct<S> {
     fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
-        let (name, fields, serializer) = match mem::replace(self, Self::Tmp) {
+        let (fields, mut next, mut ser) = match mem::replace(self, Self::Tmp) {
             Self::Start {
                 name,
                 fields: Fields::Named(fields),
                 serializer,
-            } => (name, fields, serializer),
-            mut res @ Self::End(..) => {
-                if matches!(res, Self::End(Ok(..))) {
-                    res = Self::End(Err(S::Error::custom(
-                        "visit_named_fields called multiple times in static struct",
-                    )));
+            } => match serializer.serialize_struct(name, fields.len()) {
+                Ok(ser) => (fields, 0, ser),
+                Err(e) => {
+                    *self = Self::End(Err(e));
+                    return;
                 }
+            },
+            Self::Named { fields, next, ser } => (fields, next, ser),
+            res @ Self::End(..) => {
                 *self = res;
                 return;
             }
             _ => unreachable!(),
         };
-        let mut ser = match serializer.serialize_struct(name, named_values.len()) {
-            Ok(ser) => ser,
-            Err(e) => {
-                *self = Self::End(Err(e));
-                return;
-            }
-        };
-        for (i, (_, v)) in named_values.iter().enumerate() {
-            if let Err(e) = ser.serialize_field(fields[i].name(), &Serializable(v)) {
+        for (field, v) in named_values {
+            // Serializers need the `'static` name from the definition.
+            let index = match fields.get(next) {
+                Some(f) if f.name() == field.name() => Some(next),
+                _ => fields.iter().position(|f| f.name() == field.name()),
+            };
+            let index = match index {
+                Some(index) => index,
+                None => {
+                    *self = Self::End(Err(S::Error::custom(format_args!(
+                        "unknown field `{}` in static struct",
+                        field.name()
+                    ))));
+                    return;
+                }
+            };
+            if let Err(e) = ser.serialize_field(fields[index].name(), &Serializable(v)) {
                 *self = Self::End(Err(e));
                 return;
             }
+            next = index + 1;
         }
-        *self = Self::End(ser.end());
+        *self = Self::Named { fields, next, ser };
     }
 
     fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
-        let (name, serializer) = match mem::replace(self, Self::Tmp) {
+        let mut ser = match mem::replace(self, Self::Tmp) {
             Self::Start {
                 name,
-                fields: Fields::Unnamed(_),
+                fields: Fields::Unnamed(len),
                 serializer,
-            } => (name, serializer),
+            } => {
+                if len == 1 && values.len() == 1 {
+                    *self = Self::End(
+                        serializer.serialize_newtype_struct(name, &Serializable(values[0])),
+                    );
+                    return;
+                }
+                match serializer.serialize_tuple_struct(name, len) {
+                    Ok(ser) => ser,
+                    Err(e) => {
+                        *self = Self::End(Err(e));
+                        return;
+                    }
+                }
+            }
+            Self::Unnamed(ser) => ser,
             mut res @ Self::End(..) => {
                 if matches!(res, Self::End(Ok(..))) {
                     res = Self::End(Err(S::Error::custom(
@@ -400,24 +464,13 @@ impl<S: Serializer> Visit for VisitStaticStruct<S> {
             }
             _ => unreachable!(),
         };
-        if values.len() == 1 {
-            *self = Self::End(serializer.serialize_newtype_struct(name, &Serializable(values[0])));
-            return;
-        }
-        let mut ser = match serializer.serialize_tuple_struct(name, values.len()) {
-            Ok(ser) => ser,
-            Err(e) => {
-                *self = Self::End(Err(e));
-                return;
-            }
-        };
         for v in values {
             if let Err(e) = ser.serialize_field(&Serializable(v)) {
                 *self = Self::End(Err(e));
                 return;
             }
         }
-        *self = Self::End(ser.end());
+        *self = Self::Unnamed(ser);
     }
 
     fn visit_entry(&mut self, _: Value<'_>, _: Value<'_>) {
@@ -431,6 +484,13 @@ impl<S: Serializer> Visit for VisitStaticStruct<S> {
             *self = Self::End(Err(S::Error::custom("visit_value in struct")));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::End(Err(..)) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
 }
 
 enum VisitStaticEnum<S: Serializer> {
@@ -512,6 +572,14 @@ impl<S: Serializer> Visit for VisitStaticEnum<S> {
             _ => unreachable!(),
         };
         let variant_name = variant.name();
+        if is_option(def) {
+            *self = Self::End(match (variant_name, values) {
+                ("None", []) => serializer.serialize_none(),
+                ("Some", [value]) => serializer.serialize_some(&Serializable(*value)),
+                _ => Err(S::Error::custom("invalid fields in Option variant")),
+            });
+            return;
+        }
         let variant_index = def.iter().position(|v| v.name() == variant_name).unwrap();
         assert!(variant_index <= u32::MAX as usize);
         if values.len() == 1 {
@@ -555,6 +623,21 @@ impl<S: Serializer> Visit for VisitStaticEnum<S> {
             *self = Self::End(Err(S::Error::custom("visit_value in enum")));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::End(Err(..)) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+// Returns `true` if the variants are those of an `Option`, such as those of
+// `valuable::Optional`, which are serialized as a serde option.
+// `Optional` is recognized by its variant table, so that user enums that
+// happen to look like `Option` are still serialized as enums.
+fn is_option(def: &[VariantDef<'_>]) -> bool {
+    ptr::eq(def, OPTION_VARIANTS)
 }
 
 enum VisitStaticTuple<S: Serializer> {
@@ -604,6 +687,13 @@ impl<S: Serializer> Visit for VisitStaticTuple<S> {
             *self = Self::End(Err(S::Error::custom("visit_value in tuple")));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::End(Err(..)) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
 }
 
 // Dynamic struct, variant of dynamic enum, and dynamic tuple will be serialized as map or sequence.
@@ -667,15 +757,29 @@ impl<S: Serializer> Visit for VisitDynamic<'_, S> {
             *self = Self::Error(S::Error::custom("visit_value in dynamic struct/variant"));
         }
     }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self {
+            Self::Error(..) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+// Formats a `Debug` value through `Serializer::collect_str`.
+struct DisplayDebug<'a>(&'a dyn fmt::Debug);
+
+impl fmt::Display for DisplayDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
 }
 
-#[cfg(feature = "std")]
-struct SerializeError<'a>(&'a dyn std::error::Error);
+struct SerializeError<'a>(&'a dyn StdError);
 
-#[cfg(feature = "std")]
 impl Serialize for SerializeError<'_> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        struct CollectStr<'a>(&'a dyn std::error::Error);
+        struct CollectStr<'a>(&'a dyn StdError);
         impl Serialize for CollectStr<'_> {
             fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
                 serializer.collect_str(&self.0)
@@ -66,15 +66,112 @@ fn test_str() {
     assert_ser_eq!("a".to_string(), &[Token::String("a")]);
 }
 
-// TODO:
-// - valuable treats Option<T> as T or unit
-// - serde treats Option<T> as Some(T) or None
+#[test]
+fn test_display() {
+    let addr = std::net::Ipv4Addr::LOCALHOST;
+    assert_ser_tokens(&Serializable::new(addr), &[Token::Str("127.0.0.1")]);
+    assert_ser_tokens(
+        &Serializable::new(Value::Debug(&Some(1))),
+        &[Token::Str("Some(1)")],
+    );
+    assert_eq!(
+        serde_json::to_string(&Serializable::new(format_args!("{}\"", 1))).unwrap(),
+        r#""1\"""#
+    );
+}
+
+#[test]
+fn test_bytes() {
+    assert_ser_tokens(&Serializable::new(Bytes(b"ab")), &[Token::Bytes(b"ab")]);
+    assert_ser_tokens(
+        &Serializable::new(Bytes(vec![1_u8, 2])),
+        &[Token::BorrowedBytes(&[1, 2])],
+    );
+
+    #[derive(Valuable)]
+    struct S {
+        #[valuable(bytes)]
+        f: Vec<u8>,
+    }
+    assert_ser_tokens(
+        &Serializable::new(S { f: vec![1, 2] }),
+        &[
+            Token::Struct { name: "S", len: 1 },
+            Token::Str("f"),
+            Token::Bytes(&[1, 2]),
+            Token::StructEnd,
+        ],
+    );
+
+    // Without opting in, bytes are serialized as a sequence.
+    assert_ser_tokens(
+        &Serializable::new(vec![1_u8, 2]),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+// By default, valuable treats Option<T> as T or unit, while serde treats it as
+// Some(T) or None. `Optional` and `#[valuable(option)]` opt into the latter.
 #[test]
 fn test_option() {
     assert_ser_tokens(&Serializable::new(None::<u8>), &[Token::Unit]);
     assert_ser_tokens(&None::<u8>, &[Token::None]);
     assert_ser_tokens(&Serializable::new(Some(1)), &[Token::I32(1)]);
     assert_ser_tokens(&Some(1), &[Token::Some, Token::I32(1)]);
+
+    assert_ser_tokens(&Serializable::new(Optional::<u8>(None)), &[Token::None]);
+    assert_ser_tokens(
+        &Serializable::new(Optional(Some(1))),
+        &[Token::Some, Token::I32(1)],
+    );
+    assert_ser_tokens(
+        &Serializable::new(Optional(Some(()))),
+        &[Token::Some, Token::Unit],
+    );
+    assert_ser_tokens(
+        &Serializable::new(Optional(Some(None::<u8>))),
+        &[Token::Some, Token::Unit],
+    );
+
+    #[derive(Valuable)]
+    struct S {
+        #[valuable(option)]
+        f: Option<u8>,
+    }
+    assert_ser_tokens(
+        &Serializable::new(S { f: None }),
+        &[
+            Token::Struct { name: "S", len: 1 },
+            Token::Str("f"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+
+    // Enums are only serialized as options when they come from `Optional`.
+    mod lookalike {
+        #[derive(valuable::Valuable)]
+        pub enum Option {
+            #[allow(dead_code)]
+            None,
+            Some(u8),
+        }
+    }
+    assert_ser_tokens(
+        &Serializable::new(lookalike::Option::Some(1)),
+        &[
+            Token::NewtypeVariant {
+                name: "Option",
+                variant: "Some",
+            },
+            Token::U8(1),
+        ],
+    );
 }
 
 #[test]
@@ -213,6 +310,52 @@ fn test_tuple_struct() {
     );
 }
 
+#[test]
+fn test_chunked_struct() {
+    #[derive(Debug, PartialEq, Valuable, Serialize)]
+    #[valuable(chunk_size = 2)]
+    struct S {
+        f1: u8,
+        f2: &'static str,
+        f3: bool,
+    }
+    #[derive(Debug, PartialEq, Valuable, Serialize)]
+    #[valuable(chunk_size = 1)]
+    struct S1(u8, u8, u8);
+    #[derive(Debug, PartialEq, Valuable, Serialize)]
+    #[valuable(chunk_size = 1)]
+    struct S2(u8);
+
+    assert_ser_eq!(
+        S {
+            f1: 1,
+            f2: "a",
+            f3: true
+        },
+        &[
+            Token::Struct { name: "S", len: 3 },
+            Token::Str("f1"),
+            Token::U8(1),
+            Token::Str("f2"),
+            Token::Str("a"),
+            Token::Str("f3"),
+            Token::Bool(true),
+            Token::StructEnd
+        ]
+    );
+    assert_ser_eq!(
+        S1(1, 2, 3),
+        &[
+            Token::TupleStruct { name: "S1", len: 3 },
+            Token::U8(1),
+            Token::U8(2),
+            Token::U8(3),
+            Token::TupleStructEnd
+        ]
+    );
+    assert_ser_eq!(S2(1), &[Token::NewtypeStruct { name: "S2" }, Token::U8(1)]);
+}
+
 #[test]
 fn test_tuple_variant() {
     #[derive(Debug, PartialEq, Valuable, Serialize)]
@@ -519,6 +662,37 @@ fn test_dyn_enum() {
     );
 }
 
+#[test]
+fn test_stringable() {
+    struct Rope(Vec<&'static str>);
+
+    impl Valuable for Rope {
+        fn as_value(&self) -> Value<'_> {
+            Value::Stringable(self)
+        }
+
+        fn visit(&self, visit: &mut dyn Visit) {
+            for chunk in &self.0 {
+                visit.visit_str_chunk(chunk);
+            }
+        }
+    }
+
+    impl Stringable for Rope {
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.0.iter().map(|chunk| chunk.len()).sum();
+            (len, Some(len))
+        }
+    }
+
+    let rope = Rope(vec!["hello", " ", "\"world\""]);
+    assert_ser_tokens(&Serializable::new(&rope), &[Token::Str("hello \"world\"")]);
+    assert_eq!(
+        serde_json::to_string(&Serializable::new(&rope)).unwrap(),
+        r#""hello \"world\"""#
+    );
+}
+
 #[test]
 fn test_errors() {
     use std::{error::Error, fmt};
@@ -589,4 +763,232 @@ fn test_errors() {
             Token::StructEnd
         ]
     );
+
+    let boxed: Box<dyn Error + Send + Sync> = "a boxed error".into();
+    assert_ser_tokens(
+        &Serializable::new(&boxed),
+        &[
+            Token::Struct {
+                name: "Error",
+                len: 2,
+            },
+            Token::Str("message"),
+            Token::Str("a boxed error"),
+            Token::Str("source"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_error_view() {
+    use std::error::Error;
+    use valuable::error::ErrorView;
+
+    let err = std::io::Error::new(std::io::ErrorKind::NotFound, "inner");
+    let err: Box<dyn Error + Send + Sync> = Box::new(err);
+    let view = ErrorView::from_dyn(&*err);
+    assert_eq!(
+        serde_json::to_string(&Serializable::new(view)).unwrap(),
+        r#"{"message":"inner","type_name":null,"sources":[]}"#
+    );
+
+    let err = "a".parse::<u32>().unwrap_err();
+    assert_ser_tokens(
+        &Serializable::new(ErrorView::new(&err)),
+        &[
+            Token::Struct {
+                name: "Error",
+                len: 3,
+            },
+            Token::Str("message"),
+            Token::Str("invalid digit found in string"),
+            Token::Str("type_name"),
+            Token::Str(std::any::type_name::<std::num::ParseIntError>()),
+            Token::Str("sources"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_limited() {
+    use valuable::limit::Limited;
+
+    let values: Vec<u32> = (0..1_000).collect();
+    let map = BTreeMap::from([("a", &values)]);
+
+    let limited = Limited::new(&map).max_items(2);
+    assert_eq!(
+        serde_json::to_string(&Serializable::new(limited)).unwrap(),
+        r#"{"a":[0,1,"… 998 more"]}"#
+    );
+}
+
+// Applies a JSON Patch with `add`, `remove` and `replace` operations.
+fn apply_json_patch(doc: &mut serde_json::Value, patch: &str) {
+    let patch: Vec<serde_json::Value> = serde_json::from_str(patch).unwrap();
+    for op in patch {
+        let path = op["path"].as_str().unwrap();
+        let (parent, last) = path.rsplit_once('/').unwrap();
+        let last = last.replace("~1", "/").replace("~0", "~");
+        let parent = doc.pointer_mut(parent).unwrap();
+        match op["op"].as_str().unwrap() {
+            "replace" => *doc.pointer_mut(path).unwrap() = op["value"].clone(),
+            "add" => match parent {
+                serde_json::Value::Object(o) => {
+                    o.insert(last, op["value"].clone());
+                }
+                serde_json::Value::Array(a) => a.insert(last.parse().unwrap(), op["value"].clone()),
+                _ => panic!("invalid path {}", path),
+            },
+            "remove" => match parent {
+                serde_json::Value::Object(o) => {
+                    o.remove(&last);
+                }
+                serde_json::Value::Array(a) => {
+                    a.remove(last.parse().unwrap());
+                }
+                _ => panic!("invalid path {}", path),
+            },
+            op => panic!("unexpected op {}", op),
+        }
+    }
+}
+
+#[test]
+fn test_diff_json_patch() {
+    #[derive(Valuable)]
+    struct Inner {
+        n: u8,
+    }
+
+    #[derive(Valuable)]
+    struct S {
+        #[valuable(option)]
+        a: Option<u8>,
+        #[valuable(option)]
+        b: Option<Inner>,
+        c: Vec<Optional<&'static str>>,
+    }
+
+    let check = |old: &S, new: &S| {
+        let patch = valuable::diff::diff(old, new).to_json_patch();
+        let mut doc = serde_json::to_value(Serializable::new(old)).unwrap();
+        apply_json_patch(&mut doc, &patch);
+        assert_eq!(
+            doc,
+            serde_json::to_value(Serializable::new(new)).unwrap(),
+            "{}",
+            patch
+        );
+        patch
+    };
+
+    let none = S {
+        a: None,
+        b: None,
+        c: vec![],
+    };
+    let some = S {
+        a: Some(1),
+        b: Some(Inner { n: 1 }),
+        c: vec![Optional(Some("x")), Optional(None)],
+    };
+    let other = S {
+        a: Some(2),
+        b: Some(Inner { n: 2 }),
+        c: vec![Optional(None)],
+    };
+
+    assert_eq!(
+        check(&none, &some),
+        r#"[{"op":"replace","path":"/a","value":1},{"op":"replace","path":"/b","value":{"n":1}},{"op":"add","path":"/c/0","value":"x"},{"op":"add","path":"/c/1","value":null}]"#
+    );
+    assert_eq!(
+        check(&some, &other),
+        r#"[{"op":"replace","path":"/a","value":2},{"op":"replace","path":"/b/n","value":2},{"op":"replace","path":"/c/0","value":null},{"op":"remove","path":"/c/1"}]"#
+    );
+    check(&other, &none);
+    check(&some, &none);
+}
+
+#[test]
+fn test_diff_json_patch_unit_and_newtype() {
+    #[derive(Valuable)]
+    struct Unit;
+
+    #[derive(Valuable)]
+    struct Newtype(u8);
+
+    #[derive(Valuable)]
+    enum E {
+        Unit,
+        Newtype(u8),
+        Tuple(u8, u8),
+    }
+
+    #[derive(Valuable)]
+    struct S {
+        u: Unit,
+        n: Newtype,
+        e: E,
+        t: (),
+    }
+
+    #[derive(Valuable)]
+    struct Wrap {
+        s: Option<S>,
+    }
+
+    let check = |old: &Wrap, new: &Wrap| {
+        let patch = valuable::diff::diff(old, new).to_json_patch();
+        let mut doc = serde_json::to_value(Serializable::new(old)).unwrap();
+        apply_json_patch(&mut doc, &patch);
+        assert_eq!(
+            doc,
+            serde_json::to_value(Serializable::new(new)).unwrap(),
+            "{}",
+            patch
+        );
+    };
+
+    let none = Wrap { s: None };
+    let unit = Wrap {
+        s: Some(S {
+            u: Unit,
+            n: Newtype(1),
+            e: E::Unit,
+            t: (),
+        }),
+    };
+    let newtype = Wrap {
+        s: Some(S {
+            u: Unit,
+            n: Newtype(2),
+            e: E::Newtype(1),
+            t: (),
+        }),
+    };
+    let tuple = Wrap {
+        s: Some(S {
+            u: Unit,
+            n: Newtype(2),
+            e: E::Tuple(1, 2),
+            t: (),
+        }),
+    };
+
+    for (old, new) in [
+        (&none, &unit),
+        (&unit, &newtype),
+        (&newtype, &tuple),
+        (&tuple, &unit),
+        (&unit, &none),
+    ] {
+        check(old, new);
+    }
 }
@@ -122,6 +122,20 @@ fn criterion_benchmark(c: &mut Criterion) {
             black_box(v.0);
         })
     });
+
+    c.bench_function("field", |b| {
+        b.iter(|| {
+            let mut num = 0;
+            for _ in 0..NUM {
+                num += match structable.field("six") {
+                    Some(Value::Usize(v)) => v,
+                    _ => 0,
+                };
+            }
+
+            black_box(num);
+        })
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
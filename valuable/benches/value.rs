@@ -0,0 +1,115 @@
+use valuable::*;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// A wide struct mixing small and 128-bit integers, as derived code would
+// visit it.
+#[derive(Default)]
+struct Record {
+    id: u128,
+    offset: i128,
+    a: u64,
+    b: u64,
+    c: u32,
+    d: u32,
+    e: i64,
+    f: i64,
+    name: &'static str,
+    flag: bool,
+    g: u16,
+    h: u8,
+}
+
+static FIELDS: &[NamedField<'static>] = &[
+    NamedField::new("id"),
+    NamedField::new("offset"),
+    NamedField::new("a"),
+    NamedField::new("b"),
+    NamedField::new("c"),
+    NamedField::new("d"),
+    NamedField::new("e"),
+    NamedField::new("f"),
+    NamedField::new("name"),
+    NamedField::new("flag"),
+    NamedField::new("g"),
+    NamedField::new("h"),
+];
+
+impl Structable for Record {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("Record", Fields::Named(FIELDS))
+    }
+}
+
+impl Valuable for Record {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, v: &mut dyn Visit) {
+        v.visit_named_fields(&NamedValues::new(
+            FIELDS,
+            &[
+                Value::U128(&self.id),
+                Value::I128(&self.offset),
+                Value::U64(self.a),
+                Value::U64(self.b),
+                Value::U32(self.c),
+                Value::U32(self.d),
+                Value::I64(self.e),
+                Value::I64(self.f),
+                Value::String(self.name),
+                Value::Bool(self.flag),
+                Value::U16(self.g),
+                Value::U8(self.h),
+            ],
+        ));
+    }
+}
+
+// Sums the integer fields of a struct.
+struct Sum(i128);
+
+impl Visit for Sum {
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (_, value) in named_values {
+            if let Some(v) = value.as_i128() {
+                self.0 = self.0.wrapping_add(v);
+            }
+        }
+    }
+
+    fn visit_value(&mut self, value: Value<'_>) {
+        if let Value::Structable(v) = value {
+            v.visit(self);
+        }
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    const NUM: usize = 50;
+
+    let records: Vec<Record> = (0..NUM).map(|_| Record::default()).collect();
+    let records = black_box(records);
+
+    c.bench_function("visit_named_fields", |b| {
+        b.iter(|| {
+            let mut sum = Sum(0);
+            for record in &records {
+                record.visit(&mut sum);
+            }
+            black_box(sum.0);
+        })
+    });
+
+    c.bench_function("copy_values", |b| {
+        let values = black_box([Value::U64(1); 256]);
+        b.iter(|| {
+            let copy = values;
+            black_box(copy);
+        })
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -1,6 +1,8 @@
 #![warn(rust_2018_idioms, single_use_lifetimes)]
 
 use std::env;
+use std::process::Command;
+use std::str;
 
 include!("no_atomic.rs");
 
@@ -9,7 +11,15 @@ include!("no_atomic.rs");
 // these cfgs other than by executing our build script.
 fn main() {
     println!("cargo:rerun-if-changed=no_atomic.rs");
-    println!("cargo:rustc-check-cfg=cfg(valuable_no_atomic_cas,valuable_no_atomic,valuable_no_atomic_64)");
+    println!("cargo:rustc-check-cfg=cfg(valuable_no_atomic_cas,valuable_no_atomic,valuable_no_atomic_64,valuable_no_core_error)");
+
+    // `core::error::Error` was stabilized in Rust 1.81. As with the atomic
+    // cfgs, a failed probe is treated as a recent compiler.
+    if let Some(minor) = rustc_minor_version() {
+        if minor < 81 {
+            println!("cargo:rustc-cfg=valuable_no_core_error");
+        }
+    }
 
     let target = match env::var("TARGET") {
         Ok(target) => target,
@@ -38,3 +48,14 @@ fn main() {
         // Otherwise, assuming `"max-atomic-width" == 64`.
     }
 }
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
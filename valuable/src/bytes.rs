@@ -0,0 +1,35 @@
+use crate::*;
+
+/// A wrapper that represents binary data as [`Value::Bytes`].
+///
+/// By default, byte slices and vectors are [`Listable`] and reach the visitor
+/// as lists of `u8` values. Wrapping the data in `Bytes` makes it visible as
+/// an opaque byte string instead, which is what hashes, packet buffers, and
+/// similar binary payloads usually are.
+///
+/// When deriving [`Valuable`], the `#[valuable(bytes)]` field attribute has
+/// the same effect without changing the field's type. Converting a byte slice
+/// with [`Value::from`] also produces a [`Value::Bytes`].
+///
+/// # Examples
+///
+/// ```
+/// use valuable::{Bytes, Valuable, Value};
+///
+/// let hash = Bytes([0xde, 0xad, 0xbe, 0xef]);
+///
+/// assert!(matches!(hash.as_value(), Value::Bytes(b) if b == [0xde, 0xad, 0xbe, 0xef]));
+/// assert_eq!(format!("{:?}", hash.as_value()), r#"b"\xde\xad\xbe\xef""#);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes<T: ?Sized>(pub T);
+
+impl<T: ?Sized + AsRef<[u8]>> Valuable for Bytes<T> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Bytes(self.0.as_ref())
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
@@ -0,0 +1,166 @@
+// Comparison helpers shared by the structural `Eq`, `Ord`, and `Hash`
+// implementations of `Value` (see `owned/cmp.rs`) and by key lookups, which
+// must also work without the `alloc` feature.
+
+use crate::*;
+
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::hash::{Hash, Hasher};
+
+// Position of the value's kind in the order documented on `Value`, which is
+// not the declaration order of the variants. String-like values are compared
+// as the string they format to, so they share the position of
+// `Value::String`.
+pub(crate) fn rank(value: &Value<'_>) -> u8 {
+    match value {
+        Value::Bool(_) => 0,
+        Value::Char(_) => 1,
+        Value::F32(_) => 2,
+        Value::F64(_) => 3,
+        Value::I8(_) => 4,
+        Value::I16(_) => 5,
+        Value::I32(_) => 6,
+        Value::I64(_) => 7,
+        Value::I128(_) => 8,
+        Value::Isize(_) => 9,
+        Value::String(_) | Value::Stringable(_) | Value::Display(_) | Value::Debug(_) => 10,
+        Value::U8(_) => 11,
+        Value::U16(_) => 12,
+        Value::U32(_) => 13,
+        Value::U64(_) => 14,
+        Value::U128(_) => 15,
+        Value::Usize(_) => 16,
+        #[cfg(feature = "std")]
+        Value::Path(_) => 17,
+        #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+        Value::Error(_) => 18,
+        Value::Listable(_) => 19,
+        Value::Mappable(_) => 20,
+        Value::Structable(_) => 21,
+        Value::Enumerable(_) => 22,
+        Value::Tuplable(_) => 23,
+        Value::Bytes(_) => 24,
+        Value::Unit => 25,
+    }
+}
+
+macro_rules! float {
+    ($cmp:ident, $hash:ident, $ty:ident) => {
+        fn $cmp(a: $ty, b: $ty) -> Ordering {
+            match a.partial_cmp(&b) {
+                Some(ordering) => ordering,
+                None => a.is_nan().cmp(&b.is_nan()),
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        fn $hash<H: Hasher>(v: $ty, state: &mut H) {
+            let v = if v.is_nan() {
+                $ty::NAN
+            } else if v == 0.0 {
+                0.0
+            } else {
+                v
+            };
+            v.to_bits().hash(state);
+        }
+    };
+}
+
+float!(cmp_f32, hash_f32, f32);
+float!(cmp_f64, hash_f64, f64);
+
+// Compares two values without looking into nested values. Returns `None` if
+// both values are of a variant whose contents must be captured to compare.
+pub(crate) fn cmp_shallow(a: &Value<'_>, b: &Value<'_>) -> Option<Ordering> {
+    let ordering = match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Char(a), Value::Char(b)) => a.cmp(b),
+        (Value::F32(a), Value::F32(b)) => cmp_f32(*a, *b),
+        (Value::F64(a), Value::F64(b)) => cmp_f64(*a, *b),
+        (Value::I8(a), Value::I8(b)) => a.cmp(b),
+        (Value::I16(a), Value::I16(b)) => a.cmp(b),
+        (Value::I32(a), Value::I32(b)) => a.cmp(b),
+        (Value::I64(a), Value::I64(b)) => a.cmp(b),
+        (Value::I128(a), Value::I128(b)) => a.cmp(b),
+        (Value::Isize(a), Value::Isize(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::U8(a), Value::U8(b)) => a.cmp(b),
+        (Value::U16(a), Value::U16(b)) => a.cmp(b),
+        (Value::U32(a), Value::U32(b)) => a.cmp(b),
+        (Value::U64(a), Value::U64(b)) => a.cmp(b),
+        (Value::U128(a), Value::U128(b)) => a.cmp(b),
+        (Value::Usize(a), Value::Usize(b)) => a.cmp(b),
+        #[cfg(feature = "std")]
+        (Value::Path(a), Value::Path(b)) => a.cmp(b),
+        (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+        (Value::Unit, Value::Unit) => Ordering::Equal,
+        // Nested values, and string-like values that must be formatted.
+        _ => match rank(a).cmp(&rank(b)) {
+            Ordering::Equal => return None,
+            ordering => ordering,
+        },
+    };
+    Some(ordering)
+}
+
+#[cfg(feature = "alloc")]
+// Hashes a value without looking into nested values. Returns `false` if the
+// value's contents must be captured to hash it.
+pub(crate) fn hash_shallow<H: Hasher>(value: &Value<'_>, state: &mut H) -> bool {
+    match value {
+        #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+        Value::Error(_) => return false,
+        Value::Listable(_)
+        | Value::Mappable(_)
+        | Value::Structable(_)
+        | Value::Enumerable(_)
+        | Value::Tuplable(_)
+        | Value::Stringable(_)
+        | Value::Display(_)
+        | Value::Debug(_) => return false,
+        _ => {}
+    }
+
+    rank(value).hash(state);
+    match value {
+        Value::Bool(v) => v.hash(state),
+        Value::Char(v) => v.hash(state),
+        Value::F32(v) => hash_f32(*v, state),
+        Value::F64(v) => hash_f64(*v, state),
+        Value::I8(v) => v.hash(state),
+        Value::I16(v) => v.hash(state),
+        Value::I32(v) => v.hash(state),
+        Value::I64(v) => v.hash(state),
+        Value::I128(v) => v.hash(state),
+        Value::Isize(v) => v.hash(state),
+        Value::String(v) => v.hash(state),
+        Value::U8(v) => v.hash(state),
+        Value::U16(v) => v.hash(state),
+        Value::U32(v) => v.hash(state),
+        Value::U64(v) => v.hash(state),
+        Value::U128(v) => v.hash(state),
+        Value::Usize(v) => v.hash(state),
+        #[cfg(feature = "std")]
+        Value::Path(v) => v.hash(state),
+        Value::Bytes(v) => v.hash(state),
+        _ => {}
+    }
+    true
+}
+
+// Returns `true` if the two values are structurally equal. Without the `alloc`
+// feature, nested values cannot be captured for comparison and are never
+// considered equal.
+pub(crate) fn eq(a: &Value<'_>, b: &Value<'_>) -> bool {
+    #[cfg(feature = "alloc")]
+    {
+        a == b
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    {
+        cmp_shallow(a, b) == Some(Ordering::Equal)
+    }
+}
@@ -0,0 +1,566 @@
+//! Structural differences between two values.
+//!
+//! [`diff()`] compares two [`Valuable`] values and reports every place where
+//! they differ as a [`Change`]: fields, list elements, and map entries that
+//! were added or removed, and values that changed. Each change records the
+//! [location](crate::path) of the difference.
+//!
+//! Values are compared using the structural equality of [`Value`]. Struct
+//! fields are matched by name, list elements and tuple fields by index, and
+//! map entries by key. When an enum switches to another variant, or a value
+//! changes type, the whole value is reported as changed.
+//!
+//! A [`Diff`] is displayed as one line per change, and can be exported as a
+//! [JSON Patch] document with [`Diff::to_json_patch()`].
+//!
+//! [JSON Patch]: https://www.rfc-editor.org/rfc/rfc6902
+//!
+//! # Examples
+//!
+//! ```
+//! use valuable::diff::{diff, ChangeKind};
+//! use valuable::Valuable;
+//!
+//! #[derive(Valuable)]
+//! struct Config {
+//!     name: &'static str,
+//!     ports: Vec<u16>,
+//! }
+//!
+//! let old = Config { name: "web", ports: vec![80, 443] };
+//! let new = Config { name: "api", ports: vec![80] };
+//!
+//! let diff = diff(&old, &new);
+//!
+//! assert_eq!(diff.changes().len(), 2);
+//! assert_eq!(diff.changes()[0].kind(), ChangeKind::Changed);
+//! assert_eq!(diff.changes()[0].location().to_string(), ".name");
+//!
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "~ .name: \"web\" -> \"api\"\n\
+//!      - .ports[1]: 443\n",
+//! );
+//!
+//! assert_eq!(
+//!     diff.to_json_patch(),
+//!     r#"[{"op":"replace","path":"/name","value":"api"},{"op":"remove","path":"/ports/1"}]"#,
+//! );
+//! ```
+
+use crate::owned::FieldsView;
+use crate::path::{OwnedLocation, OwnedSegment};
+use crate::*;
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// Compares `old` to `new` and returns their differences.
+///
+/// See the [module documentation](self) for details.
+pub fn diff(old: &dyn Valuable, new: &dyn Valuable) -> Diff {
+    let old = OwnedValue::capture(old);
+    let new = OwnedValue::capture(new);
+
+    let mut differ = Differ {
+        location: OwnedLocation::root(),
+        pointer: String::new(),
+        changes: Vec::new(),
+    };
+    differ.values(&old, &new);
+
+    Diff {
+        changes: differ.changes,
+    }
+}
+
+/// The differences between two values, returned by [`diff()`].
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+/// A single difference between two values.
+#[derive(Clone, Debug)]
+pub struct Change {
+    kind: ChangeKind,
+    location: OwnedLocation,
+    // JSON pointer to the value, following the representation used by
+    // `valuable-serde`.
+    pointer: String,
+    old: Option<OwnedValue>,
+    new: Option<OwnedValue>,
+}
+
+/// The kind of a [`Change`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The value only exists in the new value.
+    Added,
+
+    /// The value only exists in the old value.
+    Removed,
+
+    /// The value exists in both, but is different.
+    Changed,
+}
+
+impl Diff {
+    /// Returns the changes, in the order the values were visited.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns `true` if the two values are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the differences as a [JSON Patch] document.
+    ///
+    /// Values are represented the same way `valuable-serde` serializes them
+    /// to JSON. Struct fields and map entries become object members, lists
+    /// and tuples become arrays, and statically defined enums are externally
+    /// tagged. [`Optional`] values become `null` or their inner value.
+    /// Applying the patch to the JSON representation of the old value
+    /// produces the JSON representation of the new value.
+    ///
+    /// [JSON Patch]: https://www.rfc-editor.org/rfc/rfc6902
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// let old = BTreeMap::from([("a/b", 1)]);
+    /// let new = BTreeMap::from([("a/b", 2), ("c", 3)]);
+    ///
+    /// assert_eq!(
+    ///     valuable::diff::diff(&old, &new).to_json_patch(),
+    ///     r#"[{"op":"replace","path":"/a~1b","value":2},{"op":"add","path":"/c","value":3}]"#,
+    /// );
+    /// ```
+    pub fn to_json_patch(&self) -> String {
+        let mut out = String::from("[");
+        for (i, change) in self.changes.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            let op = match change.kind {
+                ChangeKind::Added => "add",
+                ChangeKind::Removed => "remove",
+                ChangeKind::Changed => "replace",
+            };
+            out.push_str("{\"op\":\"");
+            out.push_str(op);
+            out.push_str("\",\"path\":");
+            write_json_str(&change.pointer, &mut out);
+            if let Some(new) = &change.new {
+                out.push_str(",\"value\":");
+                write_json(new, &mut out);
+            }
+            out.push('}');
+        }
+        out.push(']');
+        out
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(fmt, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl Change {
+    /// Returns the kind of change.
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// Returns the location of the changed value.
+    pub fn location(&self) -> &OwnedLocation {
+        &self.location
+    }
+
+    /// Returns the old value, or `None` if the value was added.
+    pub fn old_value(&self) -> Option<&OwnedValue> {
+        self.old.as_ref()
+    }
+
+    /// Returns the new value, or `None` if the value was removed.
+    pub fn new_value(&self) -> Option<&OwnedValue> {
+        self.new.as_ref()
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = if self.location.is_root() {
+            ".".to_string()
+        } else {
+            self.location.to_string()
+        };
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(fmt, "~ {}: {:?} -> {:?}", location, old, new),
+            (None, Some(new)) => write!(fmt, "+ {}: {:?}", location, new),
+            (Some(old), None) => write!(fmt, "- {}: {:?}", location, old),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+struct Differ {
+    location: OwnedLocation,
+    pointer: String,
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn values(&mut self, old: &OwnedValue, new: &OwnedValue) {
+        if old == new {
+            return;
+        }
+
+        match (old, new) {
+            (OwnedValue::Structable(a), OwnedValue::Structable(b))
+                if a.name() == b.name() && a.is_static() == b.is_static() =>
+            {
+                if !self.fields(a.fields(), b.fields(), a.is_static()) {
+                    self.changed(old, new);
+                }
+            }
+            (OwnedValue::Enumerable(a), OwnedValue::Enumerable(b))
+                if a.name() == b.name()
+                    && a.variant_name() == b.variant_name()
+                    && a.is_static() == b.is_static() =>
+            {
+                let segment = OwnedSegment::Variant(a.variant_name().to_string());
+                // Dynamic enums and options are serialized without the
+                // variant name.
+                let component = if a.is_static() && !a.is_option() {
+                    Some(a.variant_name())
+                } else {
+                    None
+                };
+                let same_shape = self.enter(segment, component, |this| {
+                    this.fields(a.fields(), b.fields(), a.is_static())
+                });
+                if !same_shape {
+                    self.changed(old, new);
+                }
+            }
+            (OwnedValue::Listable(a), OwnedValue::Listable(b)) => {
+                let (a, b): (Vec<_>, Vec<_>) = (a.values().collect(), b.values().collect());
+                let a: Vec<_> = a.iter().map(|v| &**v).collect();
+                let b: Vec<_> = b.iter().map(|v| &**v).collect();
+                self.elements(&a, &b, OwnedSegment::Element);
+            }
+            (OwnedValue::Tuplable(a), OwnedValue::Tuplable(b))
+                if !a.is_empty() && !b.is_empty() =>
+            {
+                let a: Vec<_> = a.values().collect();
+                let b: Vec<_> = b.values().collect();
+                self.elements(&a, &b, OwnedSegment::Index);
+            }
+            (OwnedValue::Mappable(a), OwnedValue::Mappable(b)) => {
+                self.entries(a.entries(), b.entries());
+            }
+            _ => self.changed(old, new),
+        }
+    }
+
+    // Returns `false` if the fields have a different shape, in which case the
+    // whole value should be replaced.
+    fn fields(&mut self, old: FieldsView<'_>, new: FieldsView<'_>, is_static: bool) -> bool {
+        match (old, new) {
+            (FieldsView::Named(old), FieldsView::Named(new)) => {
+                for &(name, value) in &old {
+                    let segment = OwnedSegment::Field(name.to_string());
+                    match new.iter().find(|(n, _)| *n == name) {
+                        Some((_, new)) => {
+                            self.enter(segment, Some(name), |this| this.values(value, new))
+                        }
+                        None => self.enter(segment, Some(name), |this| this.removed(value)),
+                    }
+                }
+                for &(name, value) in &new {
+                    if !old.iter().any(|(n, _)| *n == name) {
+                        let segment = OwnedSegment::Field(name.to_string());
+                        self.enter(segment, Some(name), |this| this.added(value));
+                    }
+                }
+                true
+            }
+            (FieldsView::Unnamed(old), FieldsView::Unnamed(new)) => {
+                if is_static && old.len() == 1 && new.len() == 1 {
+                    // Newtypes are serialized as the inner value.
+                    self.location.push(OwnedSegment::Index(0));
+                    self.values(old[0], new[0]);
+                    self.location.pop();
+                    true
+                } else if is_static && old.len() != new.len() {
+                    false
+                } else {
+                    self.elements(&old, &new, OwnedSegment::Index);
+                    true
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn elements(
+        &mut self,
+        old: &[&OwnedValue],
+        new: &[&OwnedValue],
+        segment: fn(usize) -> OwnedSegment,
+    ) {
+        for (i, (old, new)) in old.iter().zip(new).enumerate() {
+            let index = i.to_string();
+            self.enter(segment(i), Some(&index), |this| this.values(old, new));
+        }
+
+        // Removals are reported from the back, so that indices stay valid
+        // when the changes are applied in order.
+        for (i, value) in old.iter().enumerate().skip(new.len()).rev() {
+            let index = i.to_string();
+            self.enter(segment(i), Some(&index), |this| this.removed(value));
+        }
+
+        for (i, value) in new.iter().enumerate().skip(old.len()) {
+            let index = i.to_string();
+            self.enter(segment(i), Some(&index), |this| this.added(value));
+        }
+    }
+
+    fn entries(&mut self, old: &[(OwnedValue, OwnedValue)], new: &[(OwnedValue, OwnedValue)]) {
+        let old_keys: BTreeMap<_, _> = old.iter().map(|(k, v)| (k, v)).collect();
+        let new_keys: BTreeMap<_, _> = new.iter().map(|(k, v)| (k, v)).collect();
+
+        for (key, value) in old {
+            let segment = OwnedSegment::Key(key.clone());
+            let component = json_key(key);
+            match new_keys.get(key) {
+                Some(new) => self.enter(segment, Some(&component), |this| this.values(value, new)),
+                None => self.enter(segment, Some(&component), |this| this.removed(value)),
+            }
+        }
+
+        for (key, value) in new {
+            if !old_keys.contains_key(key) {
+                let segment = OwnedSegment::Key(key.clone());
+                let component = json_key(key);
+                self.enter(segment, Some(&component), |this| this.added(value));
+            }
+        }
+    }
+
+    // Runs `f` with `segment` appended to the location, and `component`
+    // appended to the JSON pointer.
+    fn enter<R>(
+        &mut self,
+        segment: OwnedSegment,
+        component: Option<&str>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let len = self.pointer.len();
+        if let Some(component) = component {
+            self.pointer.push('/');
+            for c in component.chars() {
+                match c {
+                    '~' => self.pointer.push_str("~0"),
+                    '/' => self.pointer.push_str("~1"),
+                    c => self.pointer.push(c),
+                }
+            }
+        }
+        self.location.push(segment);
+
+        let ret = f(self);
+
+        self.location.pop();
+        self.pointer.truncate(len);
+        ret
+    }
+
+    fn added(&mut self, new: &OwnedValue) {
+        self.push(ChangeKind::Added, None, Some(new));
+    }
+
+    fn removed(&mut self, old: &OwnedValue) {
+        self.push(ChangeKind::Removed, Some(old), None);
+    }
+
+    fn changed(&mut self, old: &OwnedValue, new: &OwnedValue) {
+        self.push(ChangeKind::Changed, Some(old), Some(new));
+    }
+
+    fn push(&mut self, kind: ChangeKind, old: Option<&OwnedValue>, new: Option<&OwnedValue>) {
+        self.changes.push(Change {
+            kind,
+            location: self.location.clone(),
+            pointer: self.pointer.clone(),
+            old: old.cloned(),
+            new: new.cloned(),
+        });
+    }
+}
+
+// The string used for a map key in JSON.
+fn json_key(key: &OwnedValue) -> String {
+    match key {
+        OwnedValue::String(s) => s.clone(),
+        OwnedValue::Char(c) => c.to_string(),
+        OwnedValue::Bool(b) => b.to_string(),
+        key => {
+            let mut out = String::new();
+            write_json(key, &mut out);
+            out
+        }
+    }
+}
+
+fn write_json(value: &OwnedValue, out: &mut String) {
+    match value {
+        OwnedValue::Bool(v) => out.push_str(if *v { "true" } else { "false" }),
+        OwnedValue::Char(v) => write_json_str(v.encode_utf8(&mut [0; 4]), out),
+        OwnedValue::F32(v) if v.is_finite() => {
+            let _ = write!(out, "{:?}", v);
+        }
+        OwnedValue::F64(v) if v.is_finite() => {
+            let _ = write!(out, "{:?}", v);
+        }
+        OwnedValue::F32(_) | OwnedValue::F64(_) => out.push_str("null"),
+        OwnedValue::I8(v) => write_display(v, out),
+        OwnedValue::I16(v) => write_display(v, out),
+        OwnedValue::I32(v) => write_display(v, out),
+        OwnedValue::I64(v) => write_display(v, out),
+        OwnedValue::I128(v) => write_display(v, out),
+        OwnedValue::Isize(v) => write_display(v, out),
+        OwnedValue::U8(v) => write_display(v, out),
+        OwnedValue::U16(v) => write_display(v, out),
+        OwnedValue::U32(v) => write_display(v, out),
+        OwnedValue::U64(v) => write_display(v, out),
+        OwnedValue::U128(v) => write_display(v, out),
+        OwnedValue::Usize(v) => write_display(v, out),
+        OwnedValue::String(v) => write_json_str(v, out),
+        OwnedValue::Bytes(v) => write_json_array(v.iter().map(|b| OwnedValue::U8(*b)), out),
+        #[cfg(feature = "std")]
+        OwnedValue::Path(v) => write_json_str(&v.to_string_lossy(), out),
+        #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+        OwnedValue::Error(v) => write_json_error(v, out),
+        OwnedValue::Listable(v) => write_json_array(v.values(), out),
+        OwnedValue::Mappable(v) => {
+            write_json_object(v.entries().iter().map(|(k, v)| (json_key(k), v)), out)
+        }
+        OwnedValue::Structable(v) => write_json_fields(v.fields(), v.is_static(), out),
+        OwnedValue::Enumerable(v) => {
+            if v.is_option() {
+                // `None` is `null`, and `Some` is the inner value.
+                match v.fields() {
+                    FieldsView::Unnamed(fields) if fields.len() == 1 => write_json(fields[0], out),
+                    _ => out.push_str("null"),
+                }
+            } else if !v.is_static() {
+                write_json_fields(v.fields(), false, out);
+            } else {
+                out.push('{');
+                write_json_str(v.variant_name(), out);
+                out.push(':');
+                write_json_fields(v.fields(), true, out);
+                out.push('}');
+            }
+        }
+        OwnedValue::Tuplable(v) if v.is_empty() => out.push_str("null"),
+        OwnedValue::Tuplable(v) => write_json_array(v.values(), out),
+        OwnedValue::Unit => out.push_str("null"),
+    }
+}
+
+fn write_display(value: &impl fmt::Display, out: &mut String) {
+    let _ = write!(out, "{}", value);
+}
+
+fn write_json_fields(fields: FieldsView<'_>, is_static: bool, out: &mut String) {
+    match fields {
+        FieldsView::Named(fields) => write_json_object(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value)),
+            out,
+        ),
+        // Newtypes. Unit structs and unit variants are empty tuple structs
+        // and tuple variants, and become empty arrays.
+        FieldsView::Unnamed(fields) if is_static && fields.len() == 1 => write_json(fields[0], out),
+        FieldsView::Unnamed(fields) => write_json_array(fields.into_iter(), out),
+    }
+}
+
+fn write_json_array<V: core::borrow::Borrow<OwnedValue>>(
+    values: impl Iterator<Item = V>,
+    out: &mut String,
+) {
+    out.push('[');
+    for (i, value) in values.enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        write_json(value.borrow(), out);
+    }
+    out.push(']');
+}
+
+fn write_json_object<'a>(
+    members: impl Iterator<Item = (String, &'a OwnedValue)>,
+    out: &mut String,
+) {
+    out.push('{');
+    for (i, (name, value)) in members.enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        write_json_str(&name, out);
+        out.push(':');
+        write_json(value, out);
+    }
+    out.push('}');
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+fn write_json_error(error: &dyn crate::Error, out: &mut String) {
+    out.push_str("{\"message\":");
+    write_json_str(&error.to_string(), out);
+    out.push_str(",\"source\":");
+    match error.source() {
+        Some(source) => write_json_error(source, out),
+        None => out.push_str("null"),
+    }
+    out.push('}');
+}
+
+fn write_json_str(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -3,6 +3,7 @@ use crate::*;
 
 #[cfg(feature = "alloc")]
 use alloc::format;
+use core::any::Any;
 use core::fmt;
 
 /// An enum-like [`Valuable`] sub-type.
@@ -132,6 +133,15 @@ pub trait Enumerable: Valuable {
     /// assert_eq!("Foo", my_enum.variant().name());
     /// ```
     fn variant(&self) -> Variant<'_>;
+
+    /// Returns `self` as [`Any`], if the type opts in to downcasting.
+    ///
+    /// The default implementation returns `None`. `#[derive(Valuable)]`
+    /// overrides this method for enums without generic parameters. See
+    /// [`Structable::as_any()`] for details.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 /// An enum's variants, variant fields, and other enum-level information.
@@ -625,6 +635,10 @@ macro_rules! deref {
                 fn variant(&self) -> Variant<'_> {
                     T::variant(&**self)
                 }
+
+                fn as_any(&self) -> Option<&dyn Any> {
+                    T::as_any(&**self)
+                }
             }
         )*
     };
@@ -0,0 +1,261 @@
+//! A structured view of errors.
+//!
+//! Errors are represented by [`Value::Error`], which gives visitors access to
+//! the [`Error`] itself. Rendering it is left to the visitor, and all a
+//! generic visitor can do is format its message. [`ErrorView`] exposes an
+//! error as a struct instead, with its message, its type name when known, and
+//! the messages of its [sources](Error::source), so that it can be rendered
+//! like any other struct, for example by `valuable-serde`.
+//!
+//! [`Error`] is the trait held by [`Value::Error`]: `core::error::Error`, or
+//! `std::error::Error` on compilers older than Rust 1.81.
+//!
+//! # Examples
+//!
+//! ```
+//! use valuable::error::ErrorView;
+//! use valuable::Valuable;
+//!
+//! use std::fmt;
+//!
+//! #[derive(Debug)]
+//! struct ConnectError(std::io::Error);
+//!
+//! impl fmt::Display for ConnectError {
+//!     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         f.write_str("failed to connect")
+//!     }
+//! }
+//!
+//! impl std::error::Error for ConnectError {
+//!     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+//!         Some(&self.0)
+//!     }
+//! }
+//!
+//! let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "connection refused");
+//! let err = ConnectError(io);
+//!
+//! let view = ErrorView::from_dyn(&err);
+//! assert_eq!(
+//!     format!("{:?}", view.as_value()),
+//!     "Error { message: failed to connect, type_name: (), sources: [connection refused] }",
+//! );
+//! ```
+
+use crate::*;
+
+use core::fmt;
+use core::ops::ControlFlow;
+
+// `core::error::Error` is only available since Rust 1.81. On older compilers,
+// errors are supported with the `std` feature only.
+#[cfg(not(valuable_no_core_error))]
+pub use core::error::Error;
+#[cfg(all(valuable_no_core_error, feature = "std"))]
+pub use std::error::Error;
+
+/// A stand-in for the error trait on compilers without `core::error::Error`
+/// when the `std` feature is disabled.
+///
+/// [`Value::Error`] is not available in this configuration, so no type
+/// implements this trait and [`Value::as_error()`] always returns `None`.
+#[cfg(all(valuable_no_core_error, not(feature = "std")))]
+pub trait Error: fmt::Debug + fmt::Display {
+    /// Returns the lower-level source of this error, if any.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+static FIELDS: &[NamedField<'static>] = &[
+    NamedField::new("message"),
+    NamedField::new("type_name"),
+    NamedField::new("sources"),
+];
+
+/// An error viewed as a struct.
+///
+/// The struct is named `Error` and has three fields:
+///
+/// * `message`: the [`Display`](fmt::Display) output of the error, as a
+///   [`Value::Display`].
+/// * `type_name`: the name of the error type as a [`Value::String`], or
+///   [`Value::Unit`] if the error was passed as a trait object.
+/// * `sources`: a list with the message of each error in the
+///   [`source`](Error::source) chain, starting with the direct source.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorView<'a> {
+    message: Message<'a>,
+    type_name: Option<&'static str>,
+    sources: Sources<'a>,
+}
+
+impl<'a> ErrorView<'a> {
+    /// Creates a view of an error, including the name of its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::error::ErrorView;
+    /// use valuable::Structable;
+    ///
+    /// let err = "a".parse::<u32>().unwrap_err();
+    /// let view = ErrorView::new(&err);
+    ///
+    /// assert_eq!(
+    ///     view.field("type_name").unwrap().as_str(),
+    ///     Some(std::any::type_name::<std::num::ParseIntError>()),
+    /// );
+    /// ```
+    pub fn new<E: Error + 'static>(error: &'a E) -> ErrorView<'a> {
+        ErrorView {
+            type_name: Some(core::any::type_name::<E>()),
+            ..ErrorView::from_dyn(error)
+        }
+    }
+
+    /// Creates a view of an error trait object.
+    ///
+    /// The concrete type of the error is not known, so the `type_name` field
+    /// is [`Value::Unit`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::error::ErrorView;
+    /// use valuable::{Valuable, Value, Visit};
+    ///
+    /// // Renders errors as structs.
+    /// struct Print(String);
+    ///
+    /// impl Visit for Print {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         match value {
+    ///             Value::Error(err) => {
+    ///                 let view = ErrorView::from_dyn(err);
+    ///                 self.0 = format!("{:?}", view.as_value());
+    ///             }
+    ///             value => self.0 = format!("{:?}", value),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let err: Box<dyn std::error::Error + Send + Sync> = "oops".into();
+    /// let mut print = Print(String::new());
+    /// valuable::visit(&err, &mut print);
+    ///
+    /// assert_eq!(print.0, "Error { message: oops, type_name: (), sources: [] }");
+    /// ```
+    pub fn from_dyn(error: &'a (dyn Error + 'static)) -> ErrorView<'a> {
+        ErrorView {
+            message: Message(error),
+            type_name: None,
+            sources: Sources(error),
+        }
+    }
+
+    /// Returns the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::error::ErrorView;
+    ///
+    /// let err = "a".parse::<u32>().unwrap_err();
+    /// let view = ErrorView::new(&err);
+    ///
+    /// assert_eq!(view.error().to_string(), err.to_string());
+    /// ```
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.message.0
+    }
+
+    fn type_name_value(&self) -> Value<'_> {
+        match self.type_name {
+            Some(name) => Value::String(name),
+            None => Value::Unit,
+        }
+    }
+}
+
+impl Valuable for ErrorView<'_> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_named_fields(&NamedValues::new(
+            FIELDS,
+            &[
+                Value::Display(&self.message),
+                self.type_name_value(),
+                Value::Listable(&self.sources),
+            ],
+        ));
+    }
+}
+
+impl Structable for ErrorView<'_> {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("Error", Fields::Named(FIELDS))
+    }
+
+    fn field(&self, name: &str) -> Option<Value<'_>> {
+        match name {
+            "message" => Some(Value::Display(&self.message)),
+            "type_name" => Some(self.type_name_value()),
+            "sources" => Some(Value::Listable(&self.sources)),
+            _ => None,
+        }
+    }
+
+    fn field_at(&self, index: usize) -> Option<Value<'_>> {
+        FIELDS.get(index).and_then(|f| self.field(f.name()))
+    }
+}
+
+// Formats the message of an error.
+#[derive(Clone, Copy, Debug)]
+struct Message<'a>(&'a (dyn Error + 'static));
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+// The source chain of an error, excluding the error itself.
+#[derive(Clone, Copy, Debug)]
+struct Sources<'a>(&'a (dyn Error + 'static));
+
+impl<'a> Sources<'a> {
+    fn iter(&self) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+        core::iter::successors(self.0.source(), |&e| e.source())
+    }
+}
+
+impl Valuable for Sources<'_> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Listable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for source in self.iter() {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
+            visit.visit_value(Value::Display(&Message(source)));
+        }
+    }
+}
+
+impl Listable for Sources<'_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter().count();
+        (len, Some(len))
+    }
+}
@@ -1,5 +1,5 @@
 /// Data stored within a `Structable` or  an `Enumerable`.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Fields<'a> {
     /// Named fields
     Named(&'a [NamedField<'a>]),
@@ -0,0 +1,585 @@
+//! Protection against deeply nested and cyclic values.
+//!
+//! Visiting a value descends into its nested values through calls to
+//! [`Valuable::visit`], and implementations forward through references and
+//! smart pointers. A value that contains itself, for example through
+//! `Rc<RefCell<..>>` handles exposed by a hand-written implementation, makes
+//! the traversal recurse until the stack overflows. So does a value that is
+//! simply nested too deeply.
+//!
+//! This module provides two adapters that bound the traversal:
+//!
+//! * [`Guard`] wraps a [`Visit`] implementation. The visitor sees the same
+//!   values it would otherwise see, except for the nested values that would
+//!   exceed the limits.
+//! * [`Guarded`] wraps a [`Valuable`] value. Use it with code that visits
+//!   values on its own, such as the `Debug` implementations of this crate or
+//!   `valuable-serde`.
+//!
+//! Both enforce a maximum nesting depth and detect values that contain
+//! themselves, by comparing the address of each struct, enum, tuple, list,
+//! and map with the addresses of the values enclosing it. When either limit
+//! is hit, the nested value is replaced with a [`Marker`] instead of being
+//! visited.
+//!
+//! # Examples
+//!
+//! ```
+//! use valuable::guard::Guarded;
+//! use valuable::Valuable;
+//!
+//! let nested = vec![vec![vec![1, 2], vec![3]]];
+//!
+//! let guarded = Guarded::new(&nested).max_depth(2);
+//! assert_eq!(
+//!     format!("{:?}", guarded.as_value()),
+//!     "[[Marker::MaxDepth, Marker::MaxDepth]]",
+//! );
+//! ```
+
+use crate::*;
+
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use core::ptr;
+
+// Nesting depth allowed when no maximum is configured.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+// Number of field values that are wrapped on the stack. Visiting a struct with
+// more fields allocates.
+const STACK_VALUES: usize = 16;
+
+/// A [`Visit`] adapter that limits the nesting depth and detects cycles.
+///
+/// Values passed to the guard are forwarded to the wrapped visitor, with
+/// their nested values wrapped so that the limits also apply when the visitor
+/// descends into them. Values passed to the guard directly are at depth 1.
+///
+/// See the [module documentation](self) for details.
+///
+/// # Examples
+///
+/// ```
+/// use valuable::guard::Guard;
+/// use valuable::{Valuable, Value, Visit};
+///
+/// // Counts the lists it visits.
+/// struct CountLists(usize);
+///
+/// impl Visit for CountLists {
+///     fn visit_value(&mut self, value: Value<'_>) {
+///         if let Value::Listable(v) = value {
+///             self.0 += 1;
+///             v.visit(self);
+///         }
+///     }
+/// }
+///
+/// let nested = vec![vec![vec![vec![1]]]];
+///
+/// let mut guard = Guard::new(CountLists(0)).max_depth(2);
+/// valuable::visit(&nested, &mut guard);
+///
+/// assert_eq!(guard.into_inner().0, 2);
+/// ```
+#[derive(Debug)]
+pub struct Guard<V> {
+    visit: V,
+    limits: Limits,
+}
+
+/// A [`Valuable`] adapter that limits the nesting depth and detects cycles.
+///
+/// The value returned by [`as_value()`] is the wrapped value, with nested
+/// values that exceed the limits replaced by a [`Marker`]. The wrapped value
+/// itself is at depth 1.
+///
+/// See the [module documentation](self) for details.
+///
+/// [`as_value()`]: Valuable::as_value
+///
+/// # Examples
+///
+/// ```
+/// use valuable::guard::Guarded;
+/// use valuable::{Listable, Valuable, Value, Visit};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// // A list node which may point back to itself.
+/// struct Node(RefCell<Vec<Rc<Node>>>);
+///
+/// impl Valuable for Node {
+///     fn as_value(&self) -> Value<'_> {
+///         Value::Listable(self)
+///     }
+///
+///     fn visit(&self, visit: &mut dyn Visit) {
+///         for node in self.0.borrow().iter() {
+///             visit.visit_value(node.as_value());
+///         }
+///     }
+/// }
+///
+/// impl Listable for Node {
+///     fn size_hint(&self) -> (usize, Option<usize>) {
+///         let len = self.0.borrow().len();
+///         (len, Some(len))
+///     }
+/// }
+///
+/// let node = Rc::new(Node(RefCell::new(vec![])));
+/// node.0.borrow_mut().push(node.clone());
+///
+/// assert_eq!(
+///     format!("{:?}", Guarded::new(&node).as_value()),
+///     "[Marker::Cycle]",
+/// );
+/// # node.0.borrow_mut().clear();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Guarded<'a> {
+    inner: Wrapped<'a>,
+}
+
+/// The value substituted for a nested value that exceeds the limits of a
+/// [`Guard`] or [`Guarded`].
+///
+/// `Marker` is [`Enumerable`], so it is formatted as `Marker::MaxDepth` or
+/// `Marker::Cycle`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Marker {
+    /// The value is nested deeper than the maximum depth.
+    MaxDepth,
+
+    /// The value is contained in itself.
+    Cycle,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    max_depth: usize,
+    detect_cycles: bool,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max_depth: DEFAULT_MAX_DEPTH,
+            detect_cycles: true,
+        }
+    }
+}
+
+impl<V: Visit> Guard<V> {
+    /// Wraps `visit`.
+    ///
+    /// By default, the maximum depth is 128 and cycle detection is enabled.
+    pub fn new(visit: V) -> Guard<V> {
+        Guard {
+            visit,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Sets the maximum nesting depth.
+    ///
+    /// Structs, enums, tuples, lists, and maps nested deeper than
+    /// `max_depth` are replaced with [`Marker::MaxDepth`]. With a maximum
+    /// depth of 0, all of them are replaced.
+    pub fn max_depth(mut self, max_depth: usize) -> Guard<V> {
+        self.limits.max_depth = max_depth;
+        self
+    }
+
+    /// Enables or disables cycle detection.
+    ///
+    /// When enabled, values that are contained in themselves are replaced
+    /// with [`Marker::Cycle`].
+    pub fn detect_cycles(mut self, enabled: bool) -> Guard<V> {
+        self.limits.detect_cycles = enabled;
+        self
+    }
+
+    /// Returns a reference to the wrapped visitor.
+    pub fn get_ref(&self) -> &V {
+        &self.visit
+    }
+
+    /// Returns a mutable reference to the wrapped visitor.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.visit
+    }
+
+    /// Returns the wrapped visitor.
+    pub fn into_inner(self) -> V {
+        self.visit
+    }
+
+    fn layer(&mut self) -> Layer<'_> {
+        Layer {
+            visit: &mut self.visit,
+            parent: None,
+            limits: self.limits,
+        }
+    }
+}
+
+impl<V: Visit> Visit for Guard<V> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        self.layer().visit_value(value);
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        self.layer().visit_named_fields(named_values);
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        self.layer().visit_unnamed_fields(values);
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        self.visit.visit_primitive_slice(slice);
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        self.layer().visit_entry(key, value);
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        self.visit.control_flow()
+    }
+}
+
+impl<'a> Guarded<'a> {
+    /// Wraps `value`.
+    ///
+    /// By default, the maximum depth is 128 and cycle detection is enabled.
+    pub fn new(value: &'a dyn Valuable) -> Guarded<'a> {
+        Guarded {
+            inner: Wrapped {
+                value: value.as_value(),
+                parent: None,
+                limits: Limits::default(),
+            },
+        }
+    }
+
+    /// Sets the maximum nesting depth.
+    ///
+    /// Structs, enums, tuples, lists, and maps nested deeper than
+    /// `max_depth` are replaced with [`Marker::MaxDepth`]. With a maximum
+    /// depth of 0, all of them are replaced, including the wrapped value.
+    pub fn max_depth(mut self, max_depth: usize) -> Guarded<'a> {
+        self.inner.limits.max_depth = max_depth;
+        self
+    }
+
+    /// Enables or disables cycle detection.
+    ///
+    /// When enabled, values that are contained in themselves are replaced
+    /// with [`Marker::Cycle`].
+    pub fn detect_cycles(mut self, enabled: bool) -> Guarded<'a> {
+        self.inner.limits.detect_cycles = enabled;
+        self
+    }
+}
+
+impl Valuable for Guarded<'_> {
+    fn as_value(&self) -> Value<'_> {
+        self.inner.as_value()
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.inner.visit(visit);
+    }
+}
+
+static MARKER_VARIANTS: &[VariantDef<'static>] = &[
+    VariantDef::new("MaxDepth", Fields::Unnamed(0)),
+    VariantDef::new("Cycle", Fields::Unnamed(0)),
+];
+
+impl Valuable for Marker {
+    fn as_value(&self) -> Value<'_> {
+        Value::Enumerable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_unnamed_fields(&[]);
+    }
+}
+
+impl Enumerable for Marker {
+    fn definition(&self) -> EnumDef<'_> {
+        EnumDef::new_static("Marker", MARKER_VARIANTS)
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        match self {
+            Marker::MaxDepth => Variant::Static(&MARKER_VARIANTS[0]),
+            Marker::Cycle => Variant::Static(&MARKER_VARIANTS[1]),
+        }
+    }
+}
+
+// A value whose address is compared to detect cycles.
+#[derive(Clone, Copy, Debug)]
+enum Id<'a> {
+    Structable(&'a dyn Structable),
+    Enumerable(&'a dyn Enumerable),
+    Tuplable(&'a dyn Tuplable),
+    Listable(&'a dyn Listable),
+    Mappable(&'a dyn Mappable),
+}
+
+impl PartialEq for Id<'_> {
+    // Compares both the address and the type of the values, as a value may
+    // have the same address as its first field.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Id::Structable(a), Id::Structable(b)) => ptr::eq(*a, *b),
+            (Id::Enumerable(a), Id::Enumerable(b)) => ptr::eq(*a, *b),
+            (Id::Tuplable(a), Id::Tuplable(b)) => ptr::eq(*a, *b),
+            (Id::Listable(a), Id::Listable(b)) => ptr::eq(*a, *b),
+            (Id::Mappable(a), Id::Mappable(b)) => ptr::eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+// A value being visited, linked to the values enclosing it.
+#[derive(Debug)]
+struct Node<'a> {
+    id: Id<'a>,
+    parent: Option<&'a Node<'a>>,
+    depth: usize,
+}
+
+// A nested value, which applies the limits when visited.
+#[derive(Clone, Copy, Debug)]
+struct Wrapped<'a> {
+    value: Value<'a>,
+    parent: Option<&'a Node<'a>>,
+    limits: Limits,
+}
+
+enum Check<'a> {
+    // The value has no nested values.
+    Primitive,
+    // The value must be replaced with a marker.
+    Marker(Marker),
+    // The value may be visited.
+    Container(Node<'a>),
+}
+
+impl<'a> Wrapped<'a> {
+    fn check(&self) -> Check<'a> {
+        let id = match self.value {
+            Value::Structable(v) => Id::Structable(v),
+            Value::Enumerable(v) => Id::Enumerable(v),
+            Value::Tuplable(v) => Id::Tuplable(v),
+            Value::Listable(v) => Id::Listable(v),
+            Value::Mappable(v) => Id::Mappable(v),
+            _ => return Check::Primitive,
+        };
+
+        let depth = self.parent.map_or(0, |parent| parent.depth) + 1;
+        if depth > self.limits.max_depth {
+            return Check::Marker(Marker::MaxDepth);
+        }
+
+        if self.limits.detect_cycles {
+            let mut ancestor = self.parent;
+            while let Some(node) = ancestor {
+                if node.id == id {
+                    return Check::Marker(Marker::Cycle);
+                }
+                ancestor = node.parent;
+            }
+        }
+
+        Check::Container(Node {
+            id,
+            parent: self.parent,
+            depth,
+        })
+    }
+}
+
+impl Valuable for Wrapped<'_> {
+    fn as_value(&self) -> Value<'_> {
+        match self.check() {
+            Check::Primitive => self.value,
+            Check::Marker(Marker::MaxDepth) => Value::Enumerable(&Marker::MaxDepth),
+            Check::Marker(Marker::Cycle) => Value::Enumerable(&Marker::Cycle),
+            Check::Container(node) => match node.id {
+                Id::Structable(_) => Value::Structable(self),
+                Id::Enumerable(_) => Value::Enumerable(self),
+                Id::Tuplable(_) => Value::Tuplable(self),
+                Id::Listable(_) => Value::Listable(self),
+                Id::Mappable(_) => Value::Mappable(self),
+            },
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let node = match self.check() {
+            Check::Primitive => return visit.visit_value(self.value),
+            Check::Marker(marker) => return marker.visit(visit),
+            Check::Container(node) => node,
+        };
+
+        let mut layer = Layer {
+            visit,
+            parent: Some(&node),
+            limits: self.limits,
+        };
+        match node.id {
+            Id::Structable(v) => v.visit(&mut layer),
+            Id::Enumerable(v) => v.visit(&mut layer),
+            Id::Tuplable(v) => v.visit(&mut layer),
+            Id::Listable(v) => v.visit(&mut layer),
+            Id::Mappable(v) => v.visit(&mut layer),
+        }
+    }
+}
+
+// The trait implementations below are only used for the values returned by
+// `Wrapped::as_value`, which match the kind of the wrapped value.
+
+impl Structable for Wrapped<'_> {
+    fn definition(&self) -> StructDef<'_> {
+        match self.value {
+            Value::Structable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Enumerable for Wrapped<'_> {
+    fn definition(&self) -> EnumDef<'_> {
+        match self.value {
+            Value::Enumerable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        match self.value {
+            Value::Enumerable(v) => v.variant(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Tuplable for Wrapped<'_> {
+    fn definition(&self) -> TupleDef {
+        match self.value {
+            Value::Tuplable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Listable for Wrapped<'_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.value {
+            Value::Listable(v) => v.size_hint(),
+            _ => unreachable!(),
+        }
+    }
+
+    // Primitive elements cannot form cycles.
+    fn as_slice(&self) -> Option<Slice<'_>> {
+        match self.value {
+            Value::Listable(v) => v.as_slice(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Mappable for Wrapped<'_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.value {
+            Value::Mappable(v) => v.size_hint(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Visits the nested values of a value, forwarding them to the visitor wrapped
+// in `Wrapped`.
+struct Layer<'a> {
+    visit: &'a mut dyn Visit,
+    parent: Option<&'a Node<'a>>,
+    limits: Limits,
+}
+
+impl<'a> Layer<'a> {
+    fn wrap<'b>(&self, value: Value<'b>) -> Wrapped<'b>
+    where
+        'a: 'b,
+    {
+        Wrapped {
+            value,
+            parent: self.parent,
+            limits: self.limits,
+        }
+    }
+
+    // Calls `f` with `values`, each of them wrapped.
+    fn wrap_all(&mut self, values: &[Value<'_>], f: impl FnOnce(&mut dyn Visit, &[Value<'_>])) {
+        if values.len() <= STACK_VALUES {
+            let mut wrapped = [None; STACK_VALUES];
+            for (slot, value) in wrapped.iter_mut().zip(values) {
+                *slot = Some(self.wrap(*value));
+            }
+
+            let mut forward = [Value::Unit; STACK_VALUES];
+            for (value, wrapped) in forward.iter_mut().zip(&wrapped[..values.len()]) {
+                *value = wrapped.as_ref().unwrap().as_value();
+            }
+
+            f(self.visit, &forward[..values.len()]);
+        } else {
+            let wrapped: Vec<_> = values.iter().map(|value| self.wrap(*value)).collect();
+            let forward: Vec<_> = wrapped.iter().map(Wrapped::as_value).collect();
+            f(self.visit, &forward);
+        }
+    }
+}
+
+impl Visit for Layer<'_> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        let value = self.wrap(value);
+        self.visit.visit_value(value.as_value());
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        let fields = named_values.fields();
+        self.wrap_all(named_values.values(), |visit, values| {
+            visit.visit_named_fields(&NamedValues::new(fields, values));
+        });
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        self.wrap_all(values, |visit, values| visit.visit_unnamed_fields(values));
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        self.visit.visit_primitive_slice(slice);
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        let key = self.wrap(key);
+        let value = self.wrap(value);
+        self.visit.visit_entry(key.as_value(), value.as_value());
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        self.visit.control_flow()
+    }
+}
@@ -107,25 +107,44 @@
 //! [`valuable_serde::Serializable`]: https://docs.rs/valuable-serde/latest/valuable_serde/struct.Serializable.html
 //! [`serde::ser::Serializer`]:  https://docs.rs/serde/latest/serde/ser/trait.Serializer.html
 #![cfg_attr(not(feature = "std"), no_std)]
+#![deny(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg, doc_cfg_hide))]
 #![cfg_attr(
     docsrs,
     doc(cfg_hide(
         not(valuable_no_atomic_cas),
         not(valuable_no_atomic),
-        not(valuable_no_atomic_64)
+        not(valuable_no_atomic_64),
+        not(valuable_no_core_error)
     ))
 )]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod bytes;
+pub use bytes::Bytes;
+
+mod cmp;
+
+#[cfg(feature = "alloc")]
+pub mod diff;
+
 mod enumerable;
 pub use enumerable::{EnumDef, Enumerable, Variant, VariantDef};
 
+pub mod error;
+use error::Error;
+
 mod field;
 pub use field::{Fields, NamedField};
 
+#[cfg(feature = "alloc")]
+pub mod guard;
+
+#[cfg(feature = "alloc")]
+pub mod limit;
+
 mod listable;
 pub use listable::Listable;
 
@@ -135,15 +154,38 @@ pub use mappable::Mappable;
 mod named_values;
 pub use named_values::NamedValues;
 
+mod number;
+pub use number::{Number, TryFromValueError};
+
+mod optional;
+pub use optional::{Optional, OPTION_VARIANTS};
+
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(all(feature = "alloc", any(feature = "std", not(valuable_no_core_error))))]
+pub use owned::OwnedError;
+#[cfg(feature = "alloc")]
+pub use owned::{OwnedEnum, OwnedList, OwnedMap, OwnedStruct, OwnedTuple, OwnedValue};
+
+pub mod path;
+
 mod slice;
 pub use slice::Slice;
 
+mod stringable;
+pub use stringable::Stringable;
+
 mod structable;
 pub use structable::{StructDef, Structable};
 
+mod struct_rows;
+pub use struct_rows::StructRows;
+
 mod tuplable;
 pub use tuplable::{Tuplable, TupleDef};
 
+pub mod typed;
+
 mod valuable;
 pub use crate::valuable::Valuable;
 
@@ -153,5 +195,7 @@ pub use value::Value;
 mod visit;
 pub use visit::{visit, Visit};
 
+pub mod walk;
+
 #[cfg(feature = "derive")]
 pub use valuable_derive::Valuable;
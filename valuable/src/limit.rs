@@ -0,0 +1,676 @@
+//! Bounding the size of formatted and serialized values.
+//!
+//! Formatting a value with `Debug`, or serializing it with `valuable-serde`,
+//! visits all of its nested values. A list with a million elements produces
+//! a million entries of output, which is rarely wanted in a log line.
+//!
+//! [`Limited`] wraps a value and cuts it down while it is visited:
+//!
+//! * lists and maps are cut after a maximum number of items,
+//! * strings, including the output of [`Value::Display`] and
+//!   [`Value::Debug`] and the chunks of [`Value::Stringable`], are cut after
+//!   a maximum number of bytes,
+//! * structs, enums, tuples, lists, and maps nested deeper than a maximum
+//!   depth are omitted,
+//! * once the output reaches a maximum size, all further values are omitted.
+//!
+//! Where data was cut, a string marker is inserted instead: `"… 999,000
+//! more"` as the last element of a list, as the last key of a map, or
+//! appended to a cut string, and `"…"` in place of an omitted value.
+//!
+//! The size of the output is estimated from the `Debug` representation of
+//! primitive values and the names of fields. It does not depend on the
+//! format of the output, so it should be chosen with some headroom.
+//!
+//! # Examples
+//!
+//! ```
+//! use valuable::limit::Limited;
+//! use valuable::Valuable;
+//!
+//! let values: Vec<u32> = (0..1_000_000).collect();
+//!
+//! let limited = Limited::new(&values).max_items(3);
+//! assert_eq!(
+//!     format!("{:?}", limited.as_value()),
+//!     r#"[0, 1, 2, "… 999,997 more"]"#,
+//! );
+//! ```
+
+use crate::*;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::fmt::{self, Write};
+use core::ops::ControlFlow;
+
+const DEFAULT_MAX_ITEMS: usize = 100;
+const DEFAULT_MAX_STR_LEN: usize = 1024;
+const DEFAULT_MAX_DEPTH: usize = 32;
+const DEFAULT_MAX_BYTES: usize = 64 * 1024;
+
+// Number of field values that are cut on the stack. Visiting a struct with
+// more fields allocates.
+const STACK_VALUES: usize = 16;
+
+// Replaces an omitted value.
+const ELLIPSIS: &str = "…";
+
+/// A [`Valuable`] adapter that bounds the size of the visited value.
+///
+/// The value returned by [`as_value()`] is the wrapped value, cut down to
+/// the configured limits. The defaults are suitable for logging:
+///
+/// | Limit | Default |
+/// |-------|---------|
+/// | [`max_items`](Limited::max_items) | 100 |
+/// | [`max_str_len`](Limited::max_str_len) | 1024 |
+/// | [`max_depth`](Limited::max_depth) | 32 |
+/// | [`max_bytes`](Limited::max_bytes) | 65536 |
+///
+/// See the [module documentation](self) for details.
+///
+/// [`as_value()`]: Valuable::as_value
+///
+/// # Examples
+///
+/// ```
+/// use valuable::limit::Limited;
+/// use valuable::Valuable;
+/// use std::collections::BTreeMap;
+///
+/// let map = BTreeMap::from([
+///     ("a", "x".repeat(10)),
+///     ("b", "y".repeat(10)),
+///     ("c", "z".repeat(10)),
+/// ]);
+///
+/// let limited = Limited::new(&map).max_items(2).max_str_len(4);
+/// assert_eq!(
+///     format!("{:?}", limited.as_value()),
+///     r#"{"a": "xxxx… 6 more bytes", "b": "yyyy… 6 more bytes", "… 1 more": ()}"#,
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Limited<'a> {
+    inner: Wrapped<'a>,
+    // The wrapped value, if it is a string that was cut.
+    string: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    max_items: usize,
+    max_str_len: usize,
+    max_depth: usize,
+    max_bytes: usize,
+}
+
+impl<'a> Limited<'a> {
+    /// Wraps `value` with the default limits.
+    pub fn new(value: &'a dyn Valuable) -> Limited<'a> {
+        Limited {
+            inner: Wrapped {
+                value: value.as_value(),
+                ctx: None,
+                limits: Limits {
+                    max_items: DEFAULT_MAX_ITEMS,
+                    max_str_len: DEFAULT_MAX_STR_LEN,
+                    max_depth: DEFAULT_MAX_DEPTH,
+                    max_bytes: DEFAULT_MAX_BYTES,
+                },
+            },
+            string: None,
+        }
+        .update()
+    }
+
+    /// Sets the maximum number of items of each list or map.
+    ///
+    /// Further items are replaced with a single `"… N more"` marker.
+    pub fn max_items(mut self, max_items: usize) -> Limited<'a> {
+        self.inner.limits.max_items = max_items;
+        self
+    }
+
+    /// Sets the maximum length of each string, in bytes.
+    ///
+    /// Longer strings are cut at the last character boundary within the
+    /// limit, and `"… N more bytes"` is appended.
+    pub fn max_str_len(mut self, max_str_len: usize) -> Limited<'a> {
+        self.inner.limits.max_str_len = max_str_len;
+        self.update()
+    }
+
+    /// Sets the maximum nesting depth.
+    ///
+    /// Structs, enums, tuples, lists, and maps nested deeper than
+    /// `max_depth` are replaced with `"…"`. The wrapped value is at depth 1.
+    pub fn max_depth(mut self, max_depth: usize) -> Limited<'a> {
+        self.inner.limits.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the approximate maximum size of the output, in bytes.
+    ///
+    /// Once the limit is reached, lists and maps are cut, and the remaining
+    /// fields of structs, enums, and tuples are replaced with `"…"`.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Limited<'a> {
+        self.inner.limits.max_bytes = max_bytes;
+        self
+    }
+
+    fn update(mut self) -> Limited<'a> {
+        self.string = cut_value(&self.inner.value, self.inner.limits.max_str_len);
+        self
+    }
+}
+
+impl Valuable for Limited<'_> {
+    fn as_value(&self) -> Value<'_> {
+        if let Some(string) = &self.string {
+            return Value::String(string);
+        }
+
+        match self.inner.value {
+            Value::Structable(_)
+            | Value::Enumerable(_)
+            | Value::Tuplable(_)
+            | Value::Listable(_)
+            | Value::Mappable(_) => {
+                if self.inner.limits.max_depth == 0 {
+                    Value::String(ELLIPSIS)
+                } else {
+                    self.inner.as_value()
+                }
+            }
+            value => value,
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        match self.as_value() {
+            Value::Structable(v) => v.visit(visit),
+            Value::Enumerable(v) => v.visit(visit),
+            Value::Tuplable(v) => v.visit(visit),
+            Value::Listable(v) => v.visit(visit),
+            Value::Mappable(v) => v.visit(visit),
+            value => visit.visit_value(value),
+        }
+    }
+}
+
+// The state shared by the values nested in a `Limited`.
+#[derive(Clone, Copy, Debug)]
+struct Ctx<'a> {
+    depth: usize,
+    // Remaining size of the output, in bytes.
+    budget: &'a Cell<usize>,
+}
+
+// A struct, enum, tuple, list, or map, which applies the limits to its
+// nested values when visited.
+#[derive(Clone, Copy, Debug)]
+struct Wrapped<'a> {
+    value: Value<'a>,
+    // `None` for the value wrapped by `Limited`.
+    ctx: Option<Ctx<'a>>,
+    limits: Limits,
+}
+
+impl Valuable for Wrapped<'_> {
+    fn as_value(&self) -> Value<'_> {
+        match self.value {
+            Value::Structable(_) => Value::Structable(self),
+            Value::Enumerable(_) => Value::Enumerable(self),
+            Value::Tuplable(_) => Value::Tuplable(self),
+            Value::Listable(_) => Value::Listable(self),
+            Value::Mappable(_) => Value::Mappable(self),
+            value => value,
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let budget;
+        let ctx = match self.ctx {
+            Some(ctx) => ctx,
+            None => {
+                budget = Cell::new(self.limits.max_bytes);
+                Ctx {
+                    depth: 1,
+                    budget: &budget,
+                }
+            }
+        };
+
+        let mut layer = Layer {
+            visit,
+            ctx: Ctx {
+                depth: ctx.depth + 1,
+                budget: ctx.budget,
+            },
+            limits: self.limits,
+            items: 0,
+            cut: false,
+        };
+
+        let size_hint = match self.value {
+            Value::Structable(v) => return v.visit(&mut layer),
+            Value::Enumerable(v) => return v.visit(&mut layer),
+            Value::Tuplable(v) => return v.visit(&mut layer),
+            Value::Listable(v) => {
+                v.visit(&mut layer);
+                v.size_hint()
+            }
+            Value::Mappable(v) => {
+                v.visit(&mut layer);
+                v.size_hint()
+            }
+            _ => unreachable!(),
+        };
+
+        if layer.cut {
+            let more = match size_hint {
+                (lower, Some(upper)) if lower == upper && lower > layer.items => {
+                    Some(lower - layer.items)
+                }
+                _ => None,
+            };
+            let marker = more_marker(more);
+            if let Value::Mappable(_) = self.value {
+                layer.visit.visit_entry(Value::String(&marker), Value::Unit);
+            } else {
+                layer.visit.visit_value(Value::String(&marker));
+            }
+        }
+    }
+}
+
+// The trait implementations below are only used for the values returned by
+// `Wrapped::as_value`, which match the kind of the wrapped value.
+
+impl Structable for Wrapped<'_> {
+    fn definition(&self) -> StructDef<'_> {
+        match self.value {
+            Value::Structable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Enumerable for Wrapped<'_> {
+    fn definition(&self) -> EnumDef<'_> {
+        match self.value {
+            Value::Enumerable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        match self.value {
+            Value::Enumerable(v) => v.variant(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Tuplable for Wrapped<'_> {
+    fn definition(&self) -> TupleDef {
+        match self.value {
+            Value::Tuplable(v) => v.definition(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Listable for Wrapped<'_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.value {
+            Value::Listable(v) => cut_size_hint(v.size_hint(), self.limits),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Mappable for Wrapped<'_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.value {
+            Value::Mappable(v) => cut_size_hint(v.size_hint(), self.limits),
+            _ => unreachable!(),
+        }
+    }
+}
+
+// The number of items is at most `max_items`, plus the marker. It may be
+// anything below that, as the output size is only known while visiting.
+fn cut_size_hint((_, upper): (usize, Option<usize>), limits: Limits) -> (usize, Option<usize>) {
+    let max = limits.max_items.saturating_add(1);
+    (0, Some(upper.map_or(max, |upper| upper.min(max))))
+}
+
+// A nested value, after applying the limits.
+enum Slot<'a> {
+    Empty,
+    Value(Value<'a>),
+    Wrapped(Wrapped<'a>),
+    String(String),
+}
+
+impl Default for Slot<'_> {
+    fn default() -> Self {
+        Slot::Empty
+    }
+}
+
+impl Slot<'_> {
+    fn as_value(&self) -> Value<'_> {
+        match self {
+            Slot::Empty => Value::Unit,
+            Slot::Value(value) => *value,
+            Slot::Wrapped(wrapped) => wrapped.as_value(),
+            Slot::String(string) => Value::String(string),
+        }
+    }
+}
+
+// Visits the nested values of a value, forwarding them to the visitor after
+// applying the limits.
+struct Layer<'a> {
+    visit: &'a mut dyn Visit,
+    // The context of the nested values.
+    ctx: Ctx<'a>,
+    limits: Limits,
+    // Number of list elements or map entries forwarded.
+    items: usize,
+    // Whether list elements or map entries were dropped.
+    cut: bool,
+}
+
+impl<'a> Layer<'a> {
+    fn slot<'b>(&self, value: Value<'b>) -> Slot<'b>
+    where
+        'a: 'b,
+    {
+        match value {
+            Value::Structable(_)
+            | Value::Enumerable(_)
+            | Value::Tuplable(_)
+            | Value::Listable(_)
+            | Value::Mappable(_) => {
+                if self.ctx.depth > self.limits.max_depth {
+                    Slot::Value(Value::String(ELLIPSIS))
+                } else {
+                    Slot::Wrapped(Wrapped {
+                        value,
+                        ctx: Some(self.ctx),
+                        limits: self.limits,
+                    })
+                }
+            }
+            value => match cut_value(&value, self.limits.max_str_len) {
+                Some(cut) => Slot::String(cut),
+                None => Slot::Value(value),
+            },
+        }
+    }
+
+    // Takes `cost` bytes from the budget. If they do not fit, the budget is
+    // exhausted so that no smaller value is visited after a larger one was
+    // omitted.
+    fn charge(&self, cost: usize) -> bool {
+        let budget = self.ctx.budget.get();
+        if cost <= budget {
+            self.ctx.budget.set(budget - cost);
+            true
+        } else {
+            self.ctx.budget.set(0);
+            false
+        }
+    }
+
+    // Returns whether a list element or map entry of `cost` bytes is
+    // forwarded.
+    fn next_item(&mut self, cost: usize) -> bool {
+        if self.cut {
+            return false;
+        }
+        if self.items >= self.limits.max_items || !self.charge(cost) {
+            self.cut = true;
+            return false;
+        }
+        self.items += 1;
+        true
+    }
+
+    // Replaces `values` with their slots. Values which do not fit into the
+    // budget are replaced with `"…"`.
+    fn fill<'b>(
+        &self,
+        names: Option<&[NamedField<'_>]>,
+        values: &[Value<'b>],
+        slots: &mut [Slot<'b>],
+    ) where
+        'a: 'b,
+    {
+        for (i, (slot, value)) in slots.iter_mut().zip(values).enumerate() {
+            let name_cost = names.map_or(0, |names| names[i].name().len() + 2);
+            *slot = self.slot(*value);
+            if !self.charge(name_cost + cost(&slot.as_value()) + 2) {
+                *slot = Slot::Value(Value::String(ELLIPSIS));
+            }
+        }
+    }
+
+    // Calls `f` with `values` after applying the limits.
+    fn fields<'b>(
+        &mut self,
+        names: Option<&[NamedField<'_>]>,
+        values: &[Value<'b>],
+        f: impl FnOnce(&mut dyn Visit, &[Value<'_>]),
+    ) where
+        'a: 'b,
+    {
+        if values.len() <= STACK_VALUES {
+            let mut slots: [Slot<'_>; STACK_VALUES] = Default::default();
+            self.fill(names, values, &mut slots[..values.len()]);
+
+            let mut forward = [Value::Unit; STACK_VALUES];
+            for (value, slot) in forward.iter_mut().zip(&slots[..values.len()]) {
+                *value = slot.as_value();
+            }
+
+            f(self.visit, &forward[..values.len()]);
+        } else {
+            let mut slots: Vec<_> = values.iter().map(|_| Slot::Empty).collect();
+            self.fill(names, values, &mut slots);
+            let forward: Vec<_> = slots.iter().map(Slot::as_value).collect();
+            f(self.visit, &forward);
+        }
+    }
+}
+
+impl Visit for Layer<'_> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        if self.cut {
+            return;
+        }
+        let slot = self.slot(value);
+        let value = slot.as_value();
+        if self.next_item(cost(&value) + 2) {
+            self.visit.visit_value(value);
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        let fields = named_values.fields();
+        self.fields(Some(fields), named_values.values(), |visit, values| {
+            visit.visit_named_fields(&NamedValues::new(fields, values));
+        });
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        self.fields(None, values, |visit, values| {
+            visit.visit_unnamed_fields(values)
+        });
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        if let Slice::Str(_) | Slice::String(_) = slice {
+            // Strings may need to be cut.
+            for value in slice {
+                self.visit_value(value);
+                if self.cut {
+                    break;
+                }
+            }
+            return;
+        }
+
+        let mut len = 0;
+        for value in slice.iter() {
+            if !self.next_item(cost(&value) + 2) {
+                break;
+            }
+            len += 1;
+        }
+        if len > 0 {
+            self.visit.visit_primitive_slice(slice.slice(0..len));
+        }
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        if self.cut {
+            return;
+        }
+        let key = self.slot(key);
+        let value = self.slot(value);
+        if self.next_item(cost(&key.as_value()) + cost(&value.as_value()) + 4) {
+            self.visit.visit_entry(key.as_value(), value.as_value());
+        }
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        if self.cut {
+            ControlFlow::Break(())
+        } else {
+            self.visit.control_flow()
+        }
+    }
+}
+
+// Estimates the size of `value` in the output, not counting nested values.
+fn cost(value: &Value<'_>) -> usize {
+    struct Count(usize);
+
+    impl Write for Count {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+
+    match value {
+        Value::Structable(_)
+        | Value::Enumerable(_)
+        | Value::Tuplable(_)
+        | Value::Listable(_)
+        | Value::Mappable(_) => 2,
+        value => {
+            let mut count = Count(0);
+            let _ = write!(count, "{:?}", value);
+            count.0
+        }
+    }
+}
+
+// Returns the cut string if `value` is a string, or is formatted into one,
+// longer than `max_len`.
+fn cut_value(value: &Value<'_>, max_len: usize) -> Option<String> {
+    match value {
+        Value::String(s) if s.len() > max_len => Some(cut_str(s, max_len)),
+        Value::Display(_) | Value::Debug(_) if cost(value) > max_len => {
+            Some(cut_str(&format!("{:?}", value), max_len))
+        }
+        Value::Stringable(v) => cut_stringable(*v, max_len),
+        _ => None,
+    }
+}
+
+// Like `cut_str`, without collecting more of the string than is kept.
+fn cut_stringable(stringable: &dyn Stringable, max_len: usize) -> Option<String> {
+    struct Prefix {
+        prefix: String,
+        len: usize,
+        max_len: usize,
+    }
+
+    impl Visit for Prefix {
+        fn visit_value(&mut self, _: Value<'_>) {}
+
+        fn visit_str_chunk(&mut self, chunk: &str) {
+            self.len += chunk.len();
+            for c in chunk.chars() {
+                if self.prefix.len() >= self.max_len {
+                    break;
+                }
+                self.prefix.push(c);
+            }
+        }
+    }
+
+    let mut visit = Prefix {
+        prefix: String::new(),
+        len: 0,
+        max_len,
+    };
+    stringable.visit(&mut visit);
+
+    if visit.len > max_len {
+        Some(cut_prefix(&visit.prefix, visit.len, max_len))
+    } else {
+        None
+    }
+}
+
+fn cut_str(s: &str, max_len: usize) -> String {
+    cut_prefix(s, s.len(), max_len)
+}
+
+// Cuts `prefix`, the start of a string of `len` bytes, after at most
+// `max_len` bytes.
+fn cut_prefix(prefix: &str, len: usize, max_len: usize) -> String {
+    let mut end = max_len;
+    while !prefix.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let mut cut = String::from(&prefix[..end]);
+    cut.push_str(ELLIPSIS);
+    cut.push(' ');
+    push_count(&mut cut, len - end);
+    cut.push_str(" more bytes");
+    cut
+}
+
+fn more_marker(more: Option<usize>) -> String {
+    let mut marker = String::from(ELLIPSIS);
+    if let Some(more) = more {
+        marker.push(' ');
+        push_count(&mut marker, more);
+    }
+    marker.push_str(" more");
+    marker
+}
+
+// Appends `n` with thousands separators.
+fn push_count(s: &mut String, n: usize) {
+    let digits = n.to_string();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            s.push(',');
+        }
+        s.push(digit);
+    }
+}
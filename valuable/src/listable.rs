@@ -1,6 +1,10 @@
 use crate::*;
 
+use crate::value::detach;
+
+use core::any::Any;
 use core::fmt;
+use core::ops::{ControlFlow, Range};
 
 /// A list-like [`Valuable`] sub-type.
 ///
@@ -98,6 +102,194 @@ pub trait Listable: Valuable {
     /// assert_eq!((3, Some(3)), a.size_hint());
     /// ```
     fn size_hint(&self) -> (usize, Option<usize>);
+
+    /// Returns the element at position `index`, or `None` if `index` is out
+    /// of bounds.
+    ///
+    /// The default implementation visits the list up to the element. Values
+    /// passed to a visitor cannot outlive the call to [`visit()`], so the
+    /// default implementation is only able to return elements that do not
+    /// borrow from the list: booleans, characters, numbers up to 64 bits, and
+    /// [`Value::Unit`]. For any other element, including 128-bit integers,
+    /// which are passed by reference, it returns `None`.
+    /// Implementations that can borrow their elements directly should
+    /// override this method. The implementations for slices, arrays, [`Vec`],
+    /// and [`VecDeque`] do so, and run in constant time.
+    ///
+    /// [`visit()`]: Valuable::visit
+    /// [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Listable, Value};
+    ///
+    /// let list = vec!["a", "b", "c"];
+    /// let listable: &dyn Listable = &list;
+    ///
+    /// assert!(matches!(listable.get(1), Some(Value::String("b"))));
+    /// assert!(listable.get(3).is_none());
+    /// ```
+    fn get(&self, index: usize) -> Option<Value<'_>> {
+        struct Get {
+            remaining: usize,
+            found: Option<Option<Value<'static>>>,
+        }
+
+        impl Visit for Get {
+            fn visit_value(&mut self, value: Value<'_>) {
+                if self.found.is_some() {
+                    return;
+                }
+                match self.remaining {
+                    0 => self.found = Some(detach(&value)),
+                    _ => self.remaining -= 1,
+                }
+            }
+
+            fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+                if self.found.is_some() {
+                    return;
+                }
+                match slice.iter().nth(self.remaining) {
+                    Some(value) => self.found = Some(detach(&value)),
+                    None => self.remaining -= slice.len(),
+                }
+            }
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                match self.found {
+                    Some(_) => ControlFlow::Break(()),
+                    None => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        let mut visit = Get {
+            remaining: index,
+            found: None,
+        };
+        self.visit(&mut visit);
+        visit.found.flatten()
+    }
+
+    /// Visits the elements at positions in `range`.
+    ///
+    /// The elements are passed to `visit` the same way [`visit()`] passes
+    /// them, either one by one or as primitive slices. Positions past the end
+    /// of the list are ignored.
+    ///
+    /// The default implementation visits the list and skips the elements
+    /// outside of `range`, stopping after the end of the range. The
+    /// implementations for slices, arrays, [`Vec`], and [`VecDeque`] only
+    /// visit the elements in the range.
+    ///
+    /// [`visit()`]: Valuable::visit
+    /// [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Listable, Value, Visit};
+    ///
+    /// struct Print;
+    ///
+    /// impl Visit for Print {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         println!("{:?}", value);
+    ///     }
+    /// }
+    ///
+    /// let list: Vec<u32> = (0..10_000).collect();
+    ///
+    /// // Prints 100 through 109
+    /// list.visit_range(100..110, &mut Print);
+    /// ```
+    fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+        struct VisitRange<'a> {
+            range: Range<usize>,
+            index: usize,
+            visit: &'a mut dyn Visit,
+        }
+
+        impl Visit for VisitRange<'_> {
+            fn visit_value(&mut self, value: Value<'_>) {
+                if self.range.contains(&self.index) {
+                    self.visit.visit_value(value);
+                }
+                self.index += 1;
+            }
+
+            fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+                let start = self.range.start.saturating_sub(self.index).min(slice.len());
+                let end = self.range.end.saturating_sub(self.index).min(slice.len());
+                if start < end {
+                    self.visit.visit_primitive_slice(slice.slice(start..end));
+                }
+                self.index += slice.len();
+            }
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                if self.index >= self.range.end {
+                    ControlFlow::Break(())
+                } else {
+                    self.visit.control_flow()
+                }
+            }
+        }
+
+        if range.is_empty() {
+            return;
+        }
+
+        self.visit(&mut VisitRange {
+            range,
+            index: 0,
+            visit,
+        });
+    }
+
+    /// Returns the elements of the list as a primitive [`Slice`], if they are
+    /// stored contiguously.
+    ///
+    /// This gives direct access to the elements of lists of primitive values,
+    /// without visiting them, so that they can be copied in bulk. The
+    /// implementations for slices, arrays, [`Vec`], and [`VecDeque`] return
+    /// `Some` if the elements are primitives: booleans, characters, numbers,
+    /// and strings. The default implementation returns `None`, which is also
+    /// what lists of other values and lists that are not stored contiguously
+    /// return.
+    ///
+    /// [`VecDeque`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Listable, Slice};
+    ///
+    /// let list = vec![1_u32, 2, 3];
+    /// let listable: &dyn Listable = &list;
+    ///
+    /// match listable.as_slice() {
+    ///     Some(Slice::U32(s)) => assert_eq!(s, [1, 2, 3]),
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// let list = vec![Some(1_u32)];
+    /// assert!(Listable::as_slice(&list).is_none());
+    /// ```
+    fn as_slice(&self) -> Option<Slice<'_>> {
+        None
+    }
+
+    /// Returns `self` as [`Any`], if the type opts in to downcasting.
+    ///
+    /// The default implementation returns `None`. Implementations for
+    /// `'static` list types may override it to return `Some(self)`. See
+    /// [`Structable::as_any()`] for details.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 macro_rules! deref {
@@ -113,6 +305,22 @@ macro_rules! deref {
                 fn size_hint(&self) -> (usize, Option<usize>) {
                     T::size_hint(&**self)
                 }
+
+                fn get(&self, index: usize) -> Option<Value<'_>> {
+                    T::get(&**self, index)
+                }
+
+                fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+                    T::visit_range(&**self, range, visit)
+                }
+
+                fn as_slice(&self) -> Option<Slice<'_>> {
+                    T::as_slice(&**self)
+                }
+
+                fn as_any(&self) -> Option<&dyn Any> {
+                    T::as_any(&**self)
+                }
             }
         )*
     };
@@ -154,11 +362,29 @@ macro_rules! slice {
                 fn size_hint(&self) -> (usize, Option<usize>) {
                     (self.len(), Some(self.len()))
                 }
+
+                fn get(&self, index: usize) -> Option<Value<'_>> {
+                    <[T]>::get(self, index).map(Valuable::as_value)
+                }
+
+                fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+                    T::visit_slice(&self[clamp(range, self.len())], visit);
+                }
+
+                fn as_slice(&self) -> Option<Slice<'_>> {
+                    T::as_primitive_slice(self)
+                }
             }
         )*
     };
 }
 
+// Limits `range` to the bounds of a list of length `len`.
+fn clamp(range: Range<usize>, len: usize) -> Range<usize> {
+    let end = range.end.min(len);
+    range.start.min(end)..end
+}
+
 slice! {
     (T: Valuable) &'_ [T],
     #[cfg(feature = "alloc")]
@@ -189,6 +415,10 @@ macro_rules! collection {
 
                 fn visit(&self, visit: &mut dyn Visit) {
                     for value in self.iter() {
+                        if let ControlFlow::Break(()) = visit.control_flow() {
+                            break;
+                        }
+
                         visit.visit_value(value.as_value());
                     }
                 }
@@ -224,7 +454,10 @@ impl<T: Valuable> Valuable for alloc::collections::VecDeque<T> {
     fn visit(&self, visit: &mut dyn Visit) {
         let (first, second) = self.as_slices();
         T::visit_slice(first, visit);
-        T::visit_slice(second, visit);
+
+        if let ControlFlow::Continue(()) = visit.control_flow() {
+            T::visit_slice(second, visit);
+        }
     }
 }
 
@@ -233,6 +466,31 @@ impl<T: Valuable> Listable for alloc::collections::VecDeque<T> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.len(), Some(self.len()))
     }
+
+    fn get(&self, index: usize) -> Option<Value<'_>> {
+        alloc::collections::VecDeque::get(self, index).map(Valuable::as_value)
+    }
+
+    fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+        let (first, second) = self.as_slices();
+        let range = clamp(range, self.len());
+
+        let first_range = clamp(range.clone(), first.len());
+        T::visit_slice(&first[first_range], visit);
+
+        if let ControlFlow::Continue(()) = visit.control_flow() {
+            let start = range.start.saturating_sub(first.len());
+            let end = range.end.saturating_sub(first.len());
+            T::visit_slice(&second[start..end], visit);
+        }
+    }
+
+    fn as_slice(&self) -> Option<Slice<'_>> {
+        match self.as_slices() {
+            (first, []) => T::as_primitive_slice(first),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for dyn Listable + '_ {
@@ -1,6 +1,10 @@
+use crate::cmp;
+use crate::value::detach;
 use crate::*;
 
+use core::any::Any;
 use core::fmt;
+use core::ops::ControlFlow;
 
 /// A map-like [`Valuable`] sub-type.
 ///
@@ -96,6 +100,83 @@ pub trait Mappable: Valuable {
     /// assert_eq!((3, Some(3)), map.size_hint());
     /// ```
     fn size_hint(&self) -> (usize, Option<usize>);
+
+    /// Returns the value associated with `key`, or `None` if the map has no
+    /// entry for `key`.
+    ///
+    /// Keys are compared using the structural equality of [`Value`]. Note
+    /// that this means the key must be of the same type as the map's keys:
+    /// for example, looking up `Value::I64(1)` in a map with `u8` keys finds
+    /// nothing.
+    ///
+    /// The default implementation visits the map's entries until it finds
+    /// the key. Values passed to a visitor cannot outlive the call to
+    /// [`visit()`], so the default implementation is only able to return
+    /// values that do not borrow from the map: booleans, characters, numbers
+    /// up to 64 bits, and [`Value::Unit`]. For any other value, including
+    /// 128-bit integers, which are passed by reference, it returns `None`.
+    /// Implementations that can borrow their values directly should override
+    /// this method. The implementations for [`HashMap`] and [`BTreeMap`] do
+    /// so, and compare keys without going through the visitor. `BTreeMap`
+    /// also looks up primitive and string keys directly.
+    ///
+    /// [`visit()`]: Valuable::visit
+    /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+    /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Mappable, Value};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut headers = HashMap::new();
+    /// headers.insert("content-type", "text/plain");
+    ///
+    /// let mappable: &dyn Mappable = &headers;
+    ///
+    /// assert!(matches!(
+    ///     mappable.get(&Value::String("content-type")),
+    ///     Some(Value::String("text/plain")),
+    /// ));
+    /// assert!(mappable.get(&Value::String("accept")).is_none());
+    /// ```
+    fn get(&self, key: &Value<'_>) -> Option<Value<'_>> {
+        struct Get<'a, 'b> {
+            key: &'a Value<'b>,
+            found: Option<Option<Value<'static>>>,
+        }
+
+        impl Visit for Get<'_, '_> {
+            fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+                if self.found.is_none() && cmp::eq(self.key, &key) {
+                    self.found = Some(detach(&value));
+                }
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                match self.found {
+                    Some(_) => ControlFlow::Break(()),
+                    None => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        let mut visit = Get { key, found: None };
+        self.visit(&mut visit);
+        visit.found.flatten()
+    }
+
+    /// Returns `self` as [`Any`], if the type opts in to downcasting.
+    ///
+    /// The default implementation returns `None`. Implementations for
+    /// `'static` map types may override it to return `Some(self)`. See
+    /// [`Structable::as_any()`] for details.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 macro_rules! deref {
@@ -111,6 +192,14 @@ macro_rules! deref {
                 fn size_hint(&self) -> (usize, Option<usize>) {
                     T::size_hint(&**self)
                 }
+
+                fn get(&self, key: &Value<'_>) -> Option<Value<'_>> {
+                    T::get(&**self, key)
+                }
+
+                fn as_any(&self) -> Option<&dyn Any> {
+                    T::as_any(&**self)
+                }
             }
         )*
     };
@@ -136,6 +225,10 @@ impl<K: Valuable, V: Valuable, S> Valuable for std::collections::HashMap<K, V, S
 
     fn visit(&self, visit: &mut dyn Visit) {
         for (key, value) in self.iter() {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
             visit.visit_entry(key.as_value(), value.as_value());
         }
     }
@@ -146,6 +239,12 @@ impl<K: Valuable, V: Valuable, S> Mappable for std::collections::HashMap<K, V, S
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter().size_hint()
     }
+
+    // Looking up the key directly would require `K: Hash + Eq` and
+    // `S: BuildHasher`, which this impl does not.
+    fn get(&self, key: &Value<'_>) -> Option<Value<'_>> {
+        get(self.iter(), key)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -156,6 +255,10 @@ impl<K: Valuable, V: Valuable> Valuable for alloc::collections::BTreeMap<K, V> {
 
     fn visit(&self, visit: &mut dyn Visit) {
         for (key, value) in self.iter() {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
             visit.visit_entry(key.as_value(), value.as_value());
         }
     }
@@ -166,6 +269,28 @@ impl<K: Valuable, V: Valuable> Mappable for alloc::collections::BTreeMap<K, V> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter().size_hint()
     }
+
+    fn get(&self, key: &Value<'_>) -> Option<Value<'_>> {
+        match K::lookup(self, key) {
+            Some(value) => value.map(Valuable::as_value),
+            None => get(self.iter(), key),
+        }
+    }
+}
+
+// Finds the value for `key` among the entries of a map.
+//
+// This is used for `HashMap`, for `BTreeMap` when `key` cannot be converted
+// to the map's key type, for example because the keys are structs, and for
+// captured maps. It only compares the keys, without visiting the values.
+#[cfg(feature = "alloc")]
+pub(crate) fn get<'a, K: Valuable + 'a, V: Valuable + 'a>(
+    mut entries: impl Iterator<Item = (&'a K, &'a V)>,
+    key: &Value<'_>,
+) -> Option<Value<'a>> {
+    entries
+        .find(|(k, _)| cmp::eq(&k.as_value(), key))
+        .map(|(_, v)| v.as_value())
 }
 
 impl fmt::Debug for dyn Mappable + '_ {
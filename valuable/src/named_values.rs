@@ -49,6 +49,14 @@ impl<'a> NamedValues<'a> {
 
     /// Get a value using a `NamedField` reference.
     ///
+    /// The field is found by its address, so `field` must be borrowed from the
+    /// fields passed to [`NamedValues::new()`], such as the static fields of a
+    /// struct definition. Returns `None` for any other field, including a
+    /// field with the same name. Use [`get_by_name()`] to find a field by
+    /// name instead.
+    ///
+    /// [`get_by_name()`]: NamedValues::get_by_name
+    ///
     /// # Examples
     ///
     /// ```
@@ -72,9 +80,15 @@ impl<'a> NamedValues<'a> {
     pub fn get(&self, field: &NamedField<'_>) -> Option<&Value<'_>> {
         use core::mem;
 
-        let idx = (field as *const _ as usize - &self.fields[0] as *const _ as usize)
-            / mem::size_of::<NamedField<'_>>();
-        self.values.get(idx)
+        // `field` may belong to another set of fields, for example when a
+        // struct passes its fields in several chunks.
+        let offset =
+            (field as *const NamedField<'_> as usize).checked_sub(self.fields.as_ptr() as usize)?;
+        let size = mem::size_of::<NamedField<'_>>();
+        if offset % size != 0 {
+            return None;
+        }
+        self.values.get(offset / size)
     }
 
     /// Get a value using string.
@@ -149,6 +163,16 @@ impl<'a> NamedValues<'a> {
     pub fn is_empty(&self) -> bool {
         self.fields.is_empty()
     }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn fields(&self) -> &'a [NamedField<'a>] {
+        self.fields
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn values(&self) -> &'a [Value<'a>] {
+        self.values
+    }
 }
 
 impl<'a, 'b> IntoIterator for &'b NamedValues<'a> {
@@ -0,0 +1,350 @@
+use crate::*;
+
+use core::cmp::Ordering;
+use core::fmt;
+
+// 2^127, the smallest float greater than every `i128`.
+const I128_END: f64 = -(i128::MIN as f64);
+
+// 2^128, the smallest float greater than every `u128`.
+const U128_END: f64 = I128_END * 2.0;
+
+/// A number of any primitive numeric type.
+///
+/// A `Number` is extracted from any integer or float [`Value`] with
+/// [`Value::as_number()`], or converted from a primitive with [`From`]. It
+/// forgets the width of the original type, so code handling numbers does not
+/// need to match on each numeric variant.
+///
+/// Numbers compare exactly, whatever their original types: `1_u8` is equal to
+/// `1_i64` and `1.0_f32`, and `u64::MAX` is less than `u64::MAX as f64`, which
+/// rounds up to 2<sup>64</sup>. As with floats, NaN is not equal to any
+/// number, including itself.
+///
+/// Numbers are converted back to primitives with [`TryFrom`], which fails if
+/// the number is not exactly representable in the target type. Integers
+/// convert to floats if no precision is lost, and floats convert to integers
+/// if they have no fractional part and are in range. Use
+/// [`as_f64()`](Number::as_f64) for a lossy conversion.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use valuable::{Number, Value};
+///
+/// let a = Value::U8(200).as_number().unwrap();
+/// let b = Value::F64(200.0).as_number().unwrap();
+/// assert_eq!(a, b);
+/// assert!(a < Number::from(200.5));
+///
+/// assert_eq!(i64::try_from(b), Ok(200));
+/// assert!(i8::try_from(a).is_err());
+///
+/// assert!(Value::String("200").as_number().is_none());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Number(Repr);
+
+// Integers that fit in an `i128` are always stored as `Int`, so that each
+// integer has a single representation.
+#[derive(Clone, Copy)]
+enum Repr {
+    Int(i128),
+    // Greater than `i128::MAX`.
+    BigUint(u128),
+    Float(f64),
+}
+
+impl Number {
+    /// Returns `true` if the number was created from an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::Number;
+    ///
+    /// assert!(Number::from(1_u64).is_integer());
+    /// assert!(!Number::from(1.0).is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        !self.is_float()
+    }
+
+    /// Returns `true` if the number was created from a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::Number;
+    ///
+    /// assert!(Number::from(1.0_f32).is_float());
+    /// assert!(!Number::from(1).is_float());
+    /// ```
+    pub fn is_float(&self) -> bool {
+        matches!(self.0, Repr::Float(_))
+    }
+
+    /// Converts the number to the nearest `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::Number;
+    ///
+    /// assert_eq!(Number::from(3_u8).as_f64(), 3.0);
+    /// assert_eq!(Number::from(u128::MAX).as_f64(), 2_f64.powi(128));
+    /// ```
+    pub fn as_f64(&self) -> f64 {
+        match self.0 {
+            Repr::Int(v) => v as f64,
+            Repr::BigUint(v) => v as f64,
+            Repr::Float(v) => v,
+        }
+    }
+
+    fn is_nan(&self) -> bool {
+        matches!(self.0, Repr::Float(v) if v.is_nan())
+    }
+}
+
+macro_rules! int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(v: $ty) -> Number {
+                    Number(Repr::Int(v as i128))
+                }
+            }
+
+            impl TryFrom<Number> for $ty {
+                type Error = TryFromValueError;
+
+                fn try_from(number: Number) -> Result<$ty, TryFromValueError> {
+                    let v = match number.0 {
+                        Repr::Int(v) => v.try_into().ok(),
+                        Repr::BigUint(v) => v.try_into().ok(),
+                        Repr::Float(v) => float_to_int(v).and_then(|v| v.try_into().ok()),
+                    };
+                    v.ok_or_else(|| TryFromValueError::out_of_range(stringify!($ty)))
+                }
+            }
+        )*
+    };
+}
+
+int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Number {
+    fn from(v: u128) -> Number {
+        match i128::try_from(v) {
+            Ok(v) => Number(Repr::Int(v)),
+            Err(_) => Number(Repr::BigUint(v)),
+        }
+    }
+}
+
+impl TryFrom<Number> for u128 {
+    type Error = TryFromValueError;
+
+    fn try_from(number: Number) -> Result<u128, TryFromValueError> {
+        let v = match number.0 {
+            Repr::Int(v) => v.try_into().ok(),
+            Repr::BigUint(v) => Some(v),
+            // Floats of this magnitude have no fractional part.
+            Repr::Float(v) if (I128_END..U128_END).contains(&v) => Some(v as u128),
+            Repr::Float(v) => float_to_int(v).and_then(|v| v.try_into().ok()),
+        };
+        v.ok_or_else(|| TryFromValueError::out_of_range("u128"))
+    }
+}
+
+impl From<f32> for Number {
+    fn from(v: f32) -> Number {
+        Number(Repr::Float(v.into()))
+    }
+}
+
+impl From<f64> for Number {
+    fn from(v: f64) -> Number {
+        Number(Repr::Float(v))
+    }
+}
+
+impl TryFrom<Number> for f32 {
+    type Error = TryFromValueError;
+
+    fn try_from(number: Number) -> Result<f32, TryFromValueError> {
+        let v = number.as_f64() as f32;
+        if number.is_nan() || number == Number::from(v) {
+            Ok(v)
+        } else {
+            Err(TryFromValueError::out_of_range("f32"))
+        }
+    }
+}
+
+impl TryFrom<Number> for f64 {
+    type Error = TryFromValueError;
+
+    fn try_from(number: Number) -> Result<f64, TryFromValueError> {
+        let v = number.as_f64();
+        if number.is_nan() || number == Number::from(v) {
+            Ok(v)
+        } else {
+            Err(TryFromValueError::out_of_range("f64"))
+        }
+    }
+}
+
+// Converts a float without fractional part to an `i128`, if in range.
+fn float_to_int(v: f64) -> Option<i128> {
+    if (-I128_END..I128_END).contains(&v) && (v as i128) as f64 == v {
+        Some(v as i128)
+    } else {
+        None
+    }
+}
+
+// Compares an integer with a float exactly.
+fn cmp_int_float(int: i128, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        None
+    } else if float >= I128_END {
+        Some(Ordering::Less)
+    } else if float < -I128_END {
+        Some(Ordering::Greater)
+    } else {
+        // Casts truncate towards zero, and the truncated float is exactly
+        // representable as both an `i128` and an `f64`.
+        let trunc = float as i128;
+        let fract = float - trunc as f64;
+        Some(int.cmp(&trunc).then(0.0_f64.partial_cmp(&fract)?))
+    }
+}
+
+// Compares an integer greater than `i128::MAX` with a float exactly.
+fn cmp_big_uint_float(int: u128, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        None
+    } else if float >= U128_END {
+        Some(Ordering::Less)
+    } else if float < I128_END {
+        Some(Ordering::Greater)
+    } else {
+        // Floats of this magnitude have no fractional part.
+        Some(int.cmp(&(float as u128)))
+    }
+}
+
+fn cmp(a: &Repr, b: &Repr) -> Option<Ordering> {
+    match (*a, *b) {
+        (Repr::Int(a), Repr::Int(b)) => Some(a.cmp(&b)),
+        (Repr::BigUint(a), Repr::BigUint(b)) => Some(a.cmp(&b)),
+        (Repr::Int(_), Repr::BigUint(_)) => Some(Ordering::Less),
+        (Repr::BigUint(_), Repr::Int(_)) => Some(Ordering::Greater),
+        (Repr::Float(a), Repr::Float(b)) => a.partial_cmp(&b),
+        (Repr::Int(a), Repr::Float(b)) => cmp_int_float(a, b),
+        (Repr::Float(a), Repr::Int(b)) => cmp_int_float(b, a).map(Ordering::reverse),
+        (Repr::BigUint(a), Repr::Float(b)) => cmp_big_uint_float(a, b),
+        (Repr::Float(a), Repr::BigUint(b)) => cmp_big_uint_float(b, a).map(Ordering::reverse),
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Number) -> bool {
+        cmp(&self.0, &other.0) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        cmp(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Int(v) => fmt::Debug::fmt(&v, f),
+            Repr::BigUint(v) => fmt::Debug::fmt(&v, f),
+            Repr::Float(v) => fmt::Debug::fmt(&v, f),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Int(v) => fmt::Display::fmt(&v, f),
+            Repr::BigUint(v) => fmt::Display::fmt(&v, f),
+            Repr::Float(v) => fmt::Display::fmt(&v, f),
+        }
+    }
+}
+
+impl Valuable for Number {
+    fn as_value(&self) -> Value<'_> {
+        match &self.0 {
+            Repr::Int(v) => Value::I128(v),
+            Repr::BigUint(v) => Value::U128(v),
+            Repr::Float(v) => Value::F64(*v),
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+/// The error returned when converting a [`Value`] or a [`Number`] to a
+/// primitive fails.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use valuable::Value;
+///
+/// assert!(u8::try_from(Value::U8(1)).is_ok());
+///
+/// let err = u8::try_from(Value::I32(-1)).unwrap_err();
+/// assert_eq!(err.to_string(), "number out of range for `u8`");
+///
+/// let err = u8::try_from(Value::String("1")).unwrap_err();
+/// assert_eq!(err.to_string(), "invalid type, expected `u8`");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryFromValueError {
+    ty: &'static str,
+    out_of_range: bool,
+}
+
+impl TryFromValueError {
+    pub(crate) fn out_of_range(ty: &'static str) -> TryFromValueError {
+        TryFromValueError {
+            ty,
+            out_of_range: true,
+        }
+    }
+
+    pub(crate) fn invalid_type(ty: &'static str) -> TryFromValueError {
+        TryFromValueError {
+            ty,
+            out_of_range: false,
+        }
+    }
+}
+
+impl fmt::Display for TryFromValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.out_of_range {
+            write!(f, "number out of range for `{}`", self.ty)
+        } else {
+            write!(f, "invalid type, expected `{}`", self.ty)
+        }
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl crate::Error for TryFromValueError {}
@@ -0,0 +1,83 @@
+use crate::*;
+
+/// A wrapper that represents an [`Option`] as an [`Enumerable`].
+///
+/// By default, `Option<T>` is transparent: `Some(value)` is represented by
+/// the value itself, and `None` by [`Value::Unit`]. Wrapping the option in
+/// `Optional` exposes it as an enum named `Option` with the variants `None`
+/// and `Some`, the latter with a single unnamed field. Visitors can then tell
+/// an absent value from a present one, whatever the value is, and
+/// `valuable-serde` serializes it as a serde option.
+///
+/// When deriving [`Valuable`], the `#[valuable(option)]` field attribute has
+/// the same effect without changing the field's type. The field is wrapped
+/// in an `Optional<&T>` while it is visited.
+///
+/// # Examples
+///
+/// ```
+/// use valuable::{Enumerable, Optional, Valuable, Value};
+///
+/// let present = Optional(Some(()));
+/// let absent = Optional::<()>(None);
+///
+/// assert_eq!(present.variant().name(), "Some");
+/// assert_eq!(absent.variant().name(), "None");
+/// assert_eq!(format!("{:?}", present.as_value()), "Option::Some(())");
+///
+/// // Without the wrapper, `None` is a unit value.
+/// assert!(matches!(None::<()>.as_value(), Value::Unit));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for Optional<T> {
+    fn from(option: Option<T>) -> Optional<T> {
+        Optional(option)
+    }
+}
+
+/// The variants of the enum that [`Optional`] represents an [`Option`] as.
+///
+/// Every `Optional` has these variants, so a visitor can recognize an
+/// `Optional` by comparing the address of the variants of an [`EnumDef`] to
+/// the address of this table, rather than by comparing names.
+///
+/// # Examples
+///
+/// ```
+/// use valuable::{Enumerable, Optional, OPTION_VARIANTS};
+///
+/// let def = Optional(Some(1)).definition();
+/// assert!(core::ptr::eq(def.variants(), OPTION_VARIANTS));
+/// ```
+pub static OPTION_VARIANTS: &[VariantDef<'static>] = &[
+    VariantDef::new("None", Fields::Unnamed(0)),
+    VariantDef::new("Some", Fields::Unnamed(1)),
+];
+
+impl<T: Valuable> Enumerable for Optional<T> {
+    fn definition(&self) -> EnumDef<'_> {
+        EnumDef::new_static("Option", OPTION_VARIANTS)
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        match self.0 {
+            None => Variant::Static(&OPTION_VARIANTS[0]),
+            Some(_) => Variant::Static(&OPTION_VARIANTS[1]),
+        }
+    }
+}
+
+impl<T: Valuable> Valuable for Optional<T> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Enumerable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        match &self.0 {
+            None => visit.visit_unnamed_fields(&[]),
+            Some(value) => visit.visit_unnamed_fields(&[value.as_value()]),
+        }
+    }
+}
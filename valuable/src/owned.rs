@@ -0,0 +1,1282 @@
+use crate::*;
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+use core::{fmt, mem};
+
+mod cmp;
+
+/// An owned, `'static` counterpart to [`Value`].
+///
+/// A [`Value`] only borrows the data it represents, so it cannot outlive the
+/// value it was created from. `OwnedValue` stores a deep copy of the data
+/// instead, which makes it possible to keep a value around after the source
+/// is dropped, send it to another thread, or queue it for later processing.
+///
+/// An `OwnedValue` is created by calling [`OwnedValue::capture()`], which
+/// visits the source value and copies every field, element, and entry it
+/// encounters. The captured tree implements [`Valuable`] (and [`Structable`],
+/// [`Enumerable`], [`Mappable`], [`Tuplable`] for the nested values), so it can
+/// be inspected exactly like the original. Owned values are compared and
+/// hashed the same way as [`Value`].
+///
+/// # Examples
+///
+/// ```
+/// use valuable::{OwnedValue, Valuable};
+///
+/// #[derive(Valuable)]
+/// struct Request {
+///     method: String,
+///     path: String,
+/// }
+///
+/// let request = Request {
+///     method: "GET".to_string(),
+///     path: "/".to_string(),
+/// };
+///
+/// let captured = OwnedValue::capture(&request);
+/// drop(request);
+///
+/// assert_eq!(
+///     format!("{:?}", captured),
+///     r#"Request { method: "GET", path: "/" }"#,
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Clone)]
+pub enum OwnedValue {
+    /// A Rust `bool` value
+    Bool(bool),
+
+    /// A Rust `char` value
+    Char(char),
+
+    /// A Rust `f32` value
+    F32(f32),
+
+    /// A Rust `f64` value
+    F64(f64),
+
+    /// A Rust `i8` value
+    I8(i8),
+
+    /// A Rust `i16` value
+    I16(i16),
+
+    /// A Rust `i32` value
+    I32(i32),
+
+    /// A Rust `i64` value
+    I64(i64),
+
+    /// A Rust `i128` value
+    I128(i128),
+
+    /// A Rust `isize` value
+    Isize(isize),
+
+    /// An owned string
+    ///
+    /// Values of [`Value::Display`] and [`Value::Debug`] are captured as
+    /// their formatted output, and [`Value::Stringable`] values as the
+    /// concatenation of their chunks.
+    String(String),
+
+    /// A Rust `u8` value
+    U8(u8),
+
+    /// A Rust `u16` value
+    U16(u16),
+
+    /// A Rust `u32` value
+    U32(u32),
+
+    /// A Rust `u64` value
+    U64(u64),
+
+    /// A Rust `u128` value
+    U128(u128),
+
+    /// A Rust `usize` value
+    Usize(usize),
+
+    /// Owned binary data
+    Bytes(Vec<u8>),
+
+    /// An owned file system path
+    #[cfg(feature = "std")]
+    Path(std::path::PathBuf),
+
+    /// A captured error
+    #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+    Error(OwnedError),
+
+    /// A captured list
+    Listable(OwnedList),
+
+    /// A captured map
+    Mappable(OwnedMap),
+
+    /// A captured struct
+    Structable(OwnedStruct),
+
+    /// A captured enum
+    Enumerable(OwnedEnum),
+
+    /// A captured tuple
+    Tuplable(OwnedTuple),
+
+    /// A Rust `()` or `None` value.
+    Unit,
+}
+
+/// A captured list.
+///
+/// Elements that were visited as a primitive [`Slice`] are stored in a typed
+/// vector, and passed to [`Visit::visit_primitive_slice()`] again when the
+/// captured list is visited.
+#[derive(Clone, Default)]
+pub struct OwnedList {
+    chunks: Vec<ListChunk>,
+}
+
+/// A captured map.
+///
+/// Entries are stored in the order they were visited.
+#[derive(Clone, Default)]
+pub struct OwnedMap {
+    entries: Vec<(OwnedValue, OwnedValue)>,
+}
+
+/// A captured struct.
+///
+/// Statically defined structs keep their [`StructDef::Static`] definition, so
+/// [`NamedField`] instances obtained from the original definition can be used
+/// to look up values. Dynamically defined structs keep their name, and replay
+/// their fields in the same batches as the original.
+#[derive(Clone)]
+pub struct OwnedStruct {
+    def: Def,
+    fields: OwnedFields,
+}
+
+/// A captured enum.
+#[derive(Clone)]
+pub struct OwnedEnum {
+    def: EnumShape,
+    variant: OwnedVariant,
+    fields: OwnedFields,
+}
+
+/// A captured tuple.
+#[derive(Clone)]
+pub struct OwnedTuple {
+    // `None` for statically defined tuples.
+    dynamic: Option<(usize, Option<usize>)>,
+    fields: Vec<Vec<OwnedValue>>,
+}
+
+/// A captured error.
+///
+/// The error's `Display` and `Debug` output are captured, along with its chain
+/// of sources.
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedError {
+    message: String,
+    debug: String,
+    source: Option<alloc::boxed::Box<OwnedError>>,
+}
+
+// Elements of a list, grouped by how they were visited. Consecutive values
+// visited one by one are kept in the same chunk.
+#[derive(Clone)]
+enum ListChunk {
+    Values(Vec<OwnedValue>),
+    Slice(OwnedSlice),
+}
+
+// Name and shape of a struct or enum.
+#[derive(Clone)]
+enum Def {
+    Static {
+        name: &'static str,
+        fields: Fields<'static>,
+    },
+    Dynamic {
+        name: String,
+        fields: DynamicFields,
+    },
+}
+
+// Name and variants of an enum.
+#[derive(Clone)]
+enum EnumShape {
+    Static {
+        name: &'static str,
+        variants: &'static [VariantDef<'static>],
+    },
+    Dynamic {
+        name: String,
+    },
+}
+
+// Variant of an enum.
+#[derive(Clone)]
+enum OwnedVariant {
+    Static(&'static VariantDef<'static>),
+    Dynamic { name: String, fields: DynamicFields },
+}
+
+// The field shape of a dynamic definition. Field names of a dynamic
+// definition cannot be borrowed by the captured value, so only the kind of
+// fields is retained.
+#[derive(Clone, Copy)]
+enum DynamicFields {
+    Named,
+    Unnamed(usize),
+}
+
+// Flattened field values of a struct or enum.
+pub(crate) enum FieldsView<'a> {
+    Named(Vec<(&'a str, &'a OwnedValue)>),
+    Unnamed(Vec<&'a OwnedValue>),
+}
+
+// Field values, grouped by the `visit_*_fields` call that produced them.
+#[derive(Clone)]
+enum OwnedFields {
+    Named(Vec<Vec<(FieldName, OwnedValue)>>),
+    Unnamed(Vec<Vec<OwnedValue>>),
+}
+
+// The field of a captured named value. Fields taken from a statically defined
+// field list are kept by reference, so they can be passed to visitors again.
+#[derive(Clone)]
+enum FieldName {
+    Static(&'static NamedField<'static>),
+    Dynamic(String),
+}
+
+impl OwnedValue {
+    /// Captures a deep, owned copy of `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::OwnedValue;
+    ///
+    /// let captured = {
+    ///     let list = vec![1, 2, 3];
+    ///     OwnedValue::capture(&list)
+    /// };
+    ///
+    /// assert_eq!(format!("{:?}", captured), "[1, 2, 3]");
+    /// ```
+    pub fn capture(value: &dyn Valuable) -> OwnedValue {
+        OwnedValue::from(value.as_value())
+    }
+}
+
+impl From<Value<'_>> for OwnedValue {
+    fn from(value: Value<'_>) -> OwnedValue {
+        match value {
+            Value::Bool(v) => OwnedValue::Bool(v),
+            Value::Char(v) => OwnedValue::Char(v),
+            Value::F32(v) => OwnedValue::F32(v),
+            Value::F64(v) => OwnedValue::F64(v),
+            Value::I8(v) => OwnedValue::I8(v),
+            Value::I16(v) => OwnedValue::I16(v),
+            Value::I32(v) => OwnedValue::I32(v),
+            Value::I64(v) => OwnedValue::I64(v),
+            Value::I128(&v) => OwnedValue::I128(v),
+            Value::Isize(v) => OwnedValue::Isize(v),
+            Value::String(v) => OwnedValue::String(v.to_string()),
+            Value::U8(v) => OwnedValue::U8(v),
+            Value::U16(v) => OwnedValue::U16(v),
+            Value::U32(v) => OwnedValue::U32(v),
+            Value::U64(v) => OwnedValue::U64(v),
+            Value::U128(&v) => OwnedValue::U128(v),
+            Value::Usize(v) => OwnedValue::Usize(v),
+            Value::Bytes(v) => OwnedValue::Bytes(v.to_vec()),
+            Value::Display(v) => OwnedValue::String(v.to_string()),
+            Value::Debug(v) => OwnedValue::String(format!("{:?}", v)),
+            Value::Stringable(v) => OwnedValue::String(v.to_string()),
+            #[cfg(feature = "std")]
+            Value::Path(v) => OwnedValue::Path(v.to_path_buf()),
+            #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+            Value::Error(v) => OwnedValue::Error(OwnedError::capture(v)),
+            Value::Listable(v) => OwnedValue::Listable(OwnedList::capture(v)),
+            Value::Mappable(v) => OwnedValue::Mappable(OwnedMap::capture(v)),
+            Value::Structable(v) => OwnedValue::Structable(OwnedStruct::capture(v)),
+            Value::Enumerable(v) => OwnedValue::Enumerable(OwnedEnum::capture(v)),
+            Value::Tuplable(v) => OwnedValue::Tuplable(OwnedTuple::capture(v)),
+            Value::Unit => OwnedValue::Unit,
+        }
+    }
+}
+
+impl Valuable for OwnedValue {
+    fn as_value(&self) -> Value<'_> {
+        match self {
+            OwnedValue::Bool(v) => Value::Bool(*v),
+            OwnedValue::Char(v) => Value::Char(*v),
+            OwnedValue::F32(v) => Value::F32(*v),
+            OwnedValue::F64(v) => Value::F64(*v),
+            OwnedValue::I8(v) => Value::I8(*v),
+            OwnedValue::I16(v) => Value::I16(*v),
+            OwnedValue::I32(v) => Value::I32(*v),
+            OwnedValue::I64(v) => Value::I64(*v),
+            OwnedValue::I128(v) => Value::I128(v),
+            OwnedValue::Isize(v) => Value::Isize(*v),
+            OwnedValue::String(v) => Value::String(v),
+            OwnedValue::U8(v) => Value::U8(*v),
+            OwnedValue::U16(v) => Value::U16(*v),
+            OwnedValue::U32(v) => Value::U32(*v),
+            OwnedValue::U64(v) => Value::U64(*v),
+            OwnedValue::U128(v) => Value::U128(v),
+            OwnedValue::Usize(v) => Value::Usize(*v),
+            OwnedValue::Bytes(v) => Value::Bytes(v),
+            #[cfg(feature = "std")]
+            OwnedValue::Path(v) => Value::Path(v),
+            #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+            OwnedValue::Error(v) => Value::Error(v),
+            OwnedValue::Listable(v) => Value::Listable(v),
+            OwnedValue::Mappable(v) => Value::Mappable(v),
+            OwnedValue::Structable(v) => Value::Structable(v),
+            OwnedValue::Enumerable(v) => Value::Enumerable(v),
+            OwnedValue::Tuplable(v) => Value::Tuplable(v),
+            OwnedValue::Unit => Value::Unit,
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+impl fmt::Debug for OwnedValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+impl Default for OwnedValue {
+    fn default() -> Self {
+        OwnedValue::Unit
+    }
+}
+
+impl OwnedList {
+    fn capture(list: &dyn Listable) -> OwnedList {
+        struct CaptureList {
+            chunks: Vec<ListChunk>,
+            capacity: usize,
+        }
+
+        impl Visit for CaptureList {
+            fn visit_value(&mut self, value: Value<'_>) {
+                let value = OwnedValue::from(value);
+                match self.chunks.last_mut() {
+                    Some(ListChunk::Values(values)) => values.push(value),
+                    _ => {
+                        let mut values = Vec::with_capacity(self.capacity);
+                        values.push(value);
+                        self.chunks.push(ListChunk::Values(values));
+                    }
+                }
+            }
+
+            fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+                self.chunks
+                    .push(ListChunk::Slice(OwnedSlice::capture(slice)));
+            }
+        }
+
+        let mut capture = CaptureList {
+            chunks: Vec::new(),
+            capacity: list.size_hint().0,
+        };
+        list.visit(&mut capture);
+        OwnedList {
+            chunks: capture.chunks,
+        }
+    }
+
+    /// Returns the number of elements in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::OwnedValue;
+    ///
+    /// match OwnedValue::capture(&vec![1, 2, 3]) {
+    ///     OwnedValue::Listable(list) => assert_eq!(list.len(), 3),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(ListChunk::len).sum()
+    }
+
+    /// Returns `true` if the list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the elements of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{OwnedValue, Value};
+    ///
+    /// match OwnedValue::capture(&vec!["a", "b"]) {
+    ///     OwnedValue::Listable(list) => {
+    ///         assert!(list.iter().all(|v| matches!(v, Value::String(_))));
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Value<'_>> + '_ {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| (0..chunk.len()).filter_map(move |i| chunk.get(i)))
+    }
+
+    // Returns the elements as `OwnedValue`s. Elements stored in typed
+    // vectors are converted.
+    pub(crate) fn values(&self) -> impl Iterator<Item = Cow<'_, OwnedValue>> + '_ {
+        self.chunks.iter().flat_map(|chunk| {
+            (0..chunk.len()).filter_map(move |i| match chunk {
+                ListChunk::Values(values) => <[OwnedValue]>::get(values, i).map(Cow::Borrowed),
+                ListChunk::Slice(slice) => slice.get(i).map(|v| Cow::Owned(OwnedValue::from(v))),
+            })
+        })
+    }
+}
+
+impl From<Vec<OwnedValue>> for OwnedList {
+    fn from(values: Vec<OwnedValue>) -> OwnedList {
+        OwnedList {
+            chunks: vec![ListChunk::Values(values)],
+        }
+    }
+}
+
+impl Valuable for OwnedList {
+    fn as_value(&self) -> Value<'_> {
+        Value::Listable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.visit_range(0..usize::MAX, visit);
+    }
+}
+
+impl Listable for OwnedList {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn get(&self, mut index: usize) -> Option<Value<'_>> {
+        for chunk in &self.chunks {
+            if index < chunk.len() {
+                return chunk.get(index);
+            }
+            index -= chunk.len();
+        }
+        None
+    }
+
+    fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+        let mut start = 0;
+        for chunk in &self.chunks {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
+            // Empty chunks are replayed too, as long as they are in range.
+            let end = start + chunk.len();
+            let (lo, hi) = (range.start.max(start), range.end.min(end));
+            if lo < hi || range.contains(&start) {
+                chunk.visit_range(lo - start..hi - start, visit);
+            }
+            if range.end <= end {
+                break;
+            }
+            start = end;
+        }
+    }
+
+    fn as_slice(&self) -> Option<Slice<'_>> {
+        match &self.chunks[..] {
+            [ListChunk::Slice(slice)] => slice.as_slice(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for OwnedList {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+impl ListChunk {
+    fn len(&self) -> usize {
+        match self {
+            ListChunk::Values(values) => values.len(),
+            ListChunk::Slice(slice) => slice.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<Value<'_>> {
+        match self {
+            ListChunk::Values(values) => <[OwnedValue]>::get(values, index).map(Valuable::as_value),
+            ListChunk::Slice(slice) => slice.get(index),
+        }
+    }
+
+    // The range must be in bounds.
+    fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+        match self {
+            ListChunk::Values(values) => OwnedValue::visit_slice(&values[range], visit),
+            ListChunk::Slice(slice) => slice.visit_range(range, visit),
+        }
+    }
+}
+
+macro_rules! owned_slice {
+    (
+        $(
+            $variant:ident($ty:ty),
+        )*
+    ) => {
+        // An owned `Slice`. String slices are stored as `String`s, and
+        // borrowed again when visited.
+        #[derive(Clone)]
+        enum OwnedSlice {
+            $(
+                $variant(Vec<$ty>),
+            )*
+            Str(Vec<String>),
+        }
+
+        impl OwnedSlice {
+            fn capture(slice: Slice<'_>) -> OwnedSlice {
+                match slice {
+                    $(
+                        Slice::$variant(s) => OwnedSlice::$variant(s.to_vec()),
+                    )*
+                    Slice::Str(s) => OwnedSlice::Str(s.iter().map(|s| s.to_string()).collect()),
+                }
+            }
+
+            fn len(&self) -> usize {
+                match self {
+                    $(
+                        OwnedSlice::$variant(v) => v.len(),
+                    )*
+                    OwnedSlice::Str(v) => v.len(),
+                }
+            }
+
+            fn get(&self, index: usize) -> Option<Value<'_>> {
+                match self {
+                    $(
+                        OwnedSlice::$variant(v) => <[$ty]>::get(v, index).map(Valuable::as_value),
+                    )*
+                    OwnedSlice::Str(v) => <[String]>::get(v, index).map(|s| Value::String(s)),
+                }
+            }
+
+            // String slices cannot be borrowed as a `Slice` without
+            // allocating, so they are not returned.
+            fn as_slice(&self) -> Option<Slice<'_>> {
+                match self {
+                    $(
+                        OwnedSlice::$variant(v) => Some(Slice::$variant(v)),
+                    )*
+                    OwnedSlice::Str(_) => None,
+                }
+            }
+
+            // The range must be in bounds.
+            fn visit_range(&self, range: Range<usize>, visit: &mut dyn Visit) {
+                match self {
+                    $(
+                        OwnedSlice::$variant(v) => {
+                            visit.visit_primitive_slice(Slice::$variant(&v[range]));
+                        }
+                    )*
+                    OwnedSlice::Str(v) => {
+                        let v: Vec<&str> = v[range].iter().map(String::as_str).collect();
+                        visit.visit_primitive_slice(Slice::Str(&v));
+                    }
+                }
+            }
+        }
+    };
+}
+
+owned_slice! {
+    Bool(bool),
+    Char(char),
+    F32(f32),
+    F64(f64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    String(String),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    Unit(()),
+}
+
+impl OwnedMap {
+    fn capture(map: &dyn Mappable) -> OwnedMap {
+        struct CaptureMap(Vec<(OwnedValue, OwnedValue)>);
+
+        impl Visit for CaptureMap {
+            fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+                self.0
+                    .push((OwnedValue::from(key), OwnedValue::from(value)));
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+        }
+
+        let mut capture = CaptureMap(Vec::with_capacity(map.size_hint().0));
+        map.visit(&mut capture);
+        OwnedMap { entries: capture.0 }
+    }
+
+    /// Returns the map's entries, in the order they were visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::OwnedValue;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// match OwnedValue::capture(&map) {
+    ///     OwnedValue::Mappable(map) => assert_eq!(map.entries().len(), 1),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn entries(&self) -> &[(OwnedValue, OwnedValue)] {
+        &self.entries
+    }
+}
+
+impl Valuable for OwnedMap {
+    fn as_value(&self) -> Value<'_> {
+        Value::Mappable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for (key, value) in &self.entries {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
+            visit.visit_entry(key.as_value(), value.as_value());
+        }
+    }
+}
+
+impl Mappable for OwnedMap {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.entries.len(), Some(self.entries.len()))
+    }
+
+    fn get(&self, key: &Value<'_>) -> Option<Value<'_>> {
+        mappable::get(self.entries.iter().map(|(k, v)| (k, v)), key)
+    }
+}
+
+impl fmt::Debug for OwnedMap {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+impl OwnedStruct {
+    fn capture(structable: &dyn Structable) -> OwnedStruct {
+        let def = match structable.definition() {
+            StructDef::Static { name, fields } => Def::Static { name, fields },
+            StructDef::Dynamic { name, fields } => Def::Dynamic {
+                name: name.to_string(),
+                fields: DynamicFields::from(&fields),
+            },
+        };
+        let fields = OwnedFields::capture(def.is_named(), def.static_fields(), |visit| {
+            structable.visit(visit)
+        });
+        OwnedStruct { def, fields }
+    }
+
+    /// Returns the struct's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{OwnedValue, Valuable};
+    ///
+    /// #[derive(Valuable)]
+    /// struct Hello;
+    ///
+    /// match OwnedValue::capture(&Hello) {
+    ///     OwnedValue::Structable(s) => assert_eq!(s.name(), "Hello"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn name(&self) -> &str {
+        self.def.name()
+    }
+
+    pub(crate) fn fields(&self) -> FieldsView<'_> {
+        self.fields.view()
+    }
+
+    pub(crate) fn is_static(&self) -> bool {
+        matches!(self.def, Def::Static { .. })
+    }
+}
+
+impl Valuable for OwnedStruct {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.fields.visit(self.def.static_fields(), visit);
+    }
+}
+
+impl Structable for OwnedStruct {
+    fn definition(&self) -> StructDef<'_> {
+        match &self.def {
+            Def::Static { name, fields } => StructDef::new_static(name, *fields),
+            Def::Dynamic { name, fields } => StructDef::new_dynamic(name, fields.as_fields()),
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<Value<'_>> {
+        self.fields.get(name).map(Valuable::as_value)
+    }
+
+    fn field_at(&self, index: usize) -> Option<Value<'_>> {
+        self.fields.get_at(index).map(Valuable::as_value)
+    }
+}
+
+impl fmt::Debug for OwnedStruct {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+impl OwnedEnum {
+    fn capture(enumerable: &dyn Enumerable) -> OwnedEnum {
+        let def = match enumerable.definition() {
+            EnumDef::Static { name, variants } => EnumShape::Static { name, variants },
+            EnumDef::Dynamic { name, .. } => EnumShape::Dynamic {
+                name: name.to_string(),
+            },
+        };
+        let variant = match enumerable.variant() {
+            Variant::Static(variant) => OwnedVariant::Static(variant),
+            Variant::Dynamic(variant) => OwnedVariant::Dynamic {
+                name: variant.name().to_string(),
+                fields: DynamicFields::from(variant.fields()),
+            },
+        };
+        let fields = OwnedFields::capture(variant.is_named(), variant.static_fields(), |visit| {
+            enumerable.visit(visit)
+        });
+        OwnedEnum {
+            def,
+            variant,
+            fields,
+        }
+    }
+
+    /// Returns the enum's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{OwnedValue, Valuable};
+    ///
+    /// #[derive(Valuable)]
+    /// enum Greeting {
+    ///     Hello,
+    /// }
+    ///
+    /// match OwnedValue::capture(&Greeting::Hello) {
+    ///     OwnedValue::Enumerable(e) => assert_eq!(e.name(), "Greeting"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn name(&self) -> &str {
+        match &self.def {
+            EnumShape::Static { name, .. } => name,
+            EnumShape::Dynamic { name } => name,
+        }
+    }
+
+    /// Returns the name of the enum's variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{OwnedValue, Valuable};
+    ///
+    /// #[derive(Valuable)]
+    /// enum Greeting {
+    ///     Hello,
+    /// }
+    ///
+    /// match OwnedValue::capture(&Greeting::Hello) {
+    ///     OwnedValue::Enumerable(e) => assert_eq!(e.variant_name(), "Hello"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn variant_name(&self) -> &str {
+        match &self.variant {
+            OwnedVariant::Static(variant) => variant.name(),
+            OwnedVariant::Dynamic { name, .. } => name,
+        }
+    }
+
+    pub(crate) fn fields(&self) -> FieldsView<'_> {
+        self.fields.view()
+    }
+
+    pub(crate) fn is_static(&self) -> bool {
+        matches!(self.def, EnumShape::Static { .. })
+    }
+
+    // Returns `true` if the enum was captured from an `Optional`.
+    pub(crate) fn is_option(&self) -> bool {
+        match self.def {
+            EnumShape::Static { variants, .. } => core::ptr::eq(variants, OPTION_VARIANTS),
+            EnumShape::Dynamic { .. } => false,
+        }
+    }
+}
+
+impl Valuable for OwnedEnum {
+    fn as_value(&self) -> Value<'_> {
+        Value::Enumerable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.fields.visit(self.variant.static_fields(), visit);
+    }
+}
+
+impl Enumerable for OwnedEnum {
+    fn definition(&self) -> EnumDef<'_> {
+        match &self.def {
+            EnumShape::Static { name, variants } => EnumDef::new_static(name, variants),
+            // Variant definitions of a dynamic enum are not retained.
+            EnumShape::Dynamic { name } => EnumDef::new_dynamic(name, &[]),
+        }
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        match &self.variant {
+            OwnedVariant::Static(variant) => Variant::Static(variant),
+            OwnedVariant::Dynamic { name, fields } => {
+                Variant::Dynamic(VariantDef::new(name, fields.as_fields()))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for OwnedEnum {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+impl OwnedTuple {
+    fn capture(tuplable: &dyn Tuplable) -> OwnedTuple {
+        let dynamic = match tuplable.definition() {
+            TupleDef::Static { .. } => None,
+            TupleDef::Dynamic { fields } => Some(fields),
+        };
+        let fields = match OwnedFields::capture(false, None, |visit| tuplable.visit(visit)) {
+            OwnedFields::Unnamed(fields) => fields,
+            OwnedFields::Named(_) => unreachable!(),
+        };
+        OwnedTuple { dynamic, fields }
+    }
+
+    /// Returns the number of fields in the tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::OwnedValue;
+    ///
+    /// match OwnedValue::capture(&(1, "two")) {
+    ///     OwnedValue::Tuplable(t) => assert_eq!(t.len(), 2),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.fields.iter().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the tuple has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &OwnedValue> + '_ {
+        self.fields.iter().flatten()
+    }
+}
+
+impl Valuable for OwnedTuple {
+    fn as_value(&self) -> Value<'_> {
+        Value::Tuplable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        for chunk in &self.fields {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
+            let values: Vec<_> = chunk.iter().map(Valuable::as_value).collect();
+            visit.visit_unnamed_fields(&values);
+        }
+    }
+}
+
+impl Tuplable for OwnedTuple {
+    fn definition(&self) -> TupleDef {
+        match self.dynamic {
+            Some(fields) => TupleDef::new_dynamic(fields),
+            None => TupleDef::new_static(self.len()),
+        }
+    }
+}
+
+impl fmt::Debug for OwnedTuple {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_value().fmt(fmt)
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl OwnedError {
+    fn capture(error: &(dyn crate::Error + 'static)) -> OwnedError {
+        OwnedError {
+            message: error.to_string(),
+            debug: format!("{:?}", error),
+            source: error
+                .source()
+                .map(|source| alloc::boxed::Box::new(OwnedError::capture(source))),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl Valuable for OwnedError {
+    fn as_value(&self) -> Value<'_> {
+        Value::Error(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl fmt::Display for OwnedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.message)
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl fmt::Debug for OwnedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.debug)
+    }
+}
+
+#[cfg(any(feature = "std", not(valuable_no_core_error)))]
+impl crate::Error for OwnedError {
+    fn source(&self) -> Option<&(dyn crate::Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(&**source),
+            None => None,
+        }
+    }
+}
+
+impl Def {
+    fn name(&self) -> &str {
+        match self {
+            Def::Static { name, .. } => name,
+            Def::Dynamic { name, .. } => name,
+        }
+    }
+
+    fn is_named(&self) -> bool {
+        match self {
+            Def::Static { fields, .. } => fields.is_named(),
+            Def::Dynamic { fields, .. } => matches!(fields, DynamicFields::Named),
+        }
+    }
+
+    fn static_fields(&self) -> Option<&'static [NamedField<'static>]> {
+        match self {
+            Def::Static {
+                fields: Fields::Named(fields),
+                ..
+            } => Some(fields),
+            _ => None,
+        }
+    }
+}
+
+impl OwnedVariant {
+    fn is_named(&self) -> bool {
+        match self {
+            OwnedVariant::Static(variant) => variant.fields().is_named(),
+            OwnedVariant::Dynamic { fields, .. } => matches!(fields, DynamicFields::Named),
+        }
+    }
+
+    fn static_fields(&self) -> Option<&'static [NamedField<'static>]> {
+        match self {
+            OwnedVariant::Static(variant) => match variant.fields() {
+                Fields::Named(fields) => Some(*fields),
+                Fields::Unnamed(_) => None,
+            },
+            OwnedVariant::Dynamic { .. } => None,
+        }
+    }
+}
+
+impl FieldName {
+    fn name(&self) -> &str {
+        match self {
+            FieldName::Static(field) => field.name(),
+            FieldName::Dynamic(name) => name,
+        }
+    }
+}
+
+// Returns the position of `field` in `fields`, if `field` is a reference into
+// `fields`.
+fn static_index(fields: &[NamedField<'_>], field: &NamedField<'_>) -> Option<usize> {
+    let offset = (field as *const NamedField<'_> as usize).checked_sub(fields.as_ptr() as usize)?;
+    let size = mem::size_of::<NamedField<'_>>();
+    if offset % size != 0 || offset / size >= fields.len() {
+        return None;
+    }
+    Some(offset / size)
+}
+
+impl DynamicFields {
+    fn as_fields(self) -> Fields<'static> {
+        match self {
+            DynamicFields::Named => Fields::Named(&[]),
+            DynamicFields::Unnamed(len) => Fields::Unnamed(len),
+        }
+    }
+}
+
+impl From<&Fields<'_>> for DynamicFields {
+    fn from(fields: &Fields<'_>) -> DynamicFields {
+        match fields {
+            Fields::Named(_) => DynamicFields::Named,
+            Fields::Unnamed(len) => DynamicFields::Unnamed(*len),
+        }
+    }
+}
+
+impl OwnedFields {
+    fn view(&self) -> FieldsView<'_> {
+        match self {
+            OwnedFields::Named(chunks) => FieldsView::Named(
+                chunks
+                    .iter()
+                    .flatten()
+                    .map(|(field, value)| (field.name(), value))
+                    .collect(),
+            ),
+            OwnedFields::Unnamed(chunks) => FieldsView::Unnamed(chunks.iter().flatten().collect()),
+        }
+    }
+
+    // Returns the first field named `name`.
+    fn get(&self, name: &str) -> Option<&OwnedValue> {
+        match self {
+            OwnedFields::Named(chunks) => chunks
+                .iter()
+                .flatten()
+                .find(|(field, _)| field.name() == name)
+                .map(|(_, value)| value),
+            OwnedFields::Unnamed(_) => None,
+        }
+    }
+
+    // Returns the field at `index`, counting the fields of every chunk.
+    fn get_at(&self, index: usize) -> Option<&OwnedValue> {
+        match self {
+            OwnedFields::Named(chunks) => {
+                chunks.iter().flatten().nth(index).map(|(_, value)| value)
+            }
+            OwnedFields::Unnamed(chunks) => chunks.iter().flatten().nth(index),
+        }
+    }
+
+    // `static_fields` is the statically defined field list, if any. Fields
+    // taken from it are recorded by reference, so that they are replayed as
+    // the same `NamedField`s.
+    fn capture(
+        named: bool,
+        static_fields: Option<&'static [NamedField<'static>]>,
+        visit: impl FnOnce(&mut dyn Visit),
+    ) -> OwnedFields {
+        struct CaptureFields {
+            fields: OwnedFields,
+            static_fields: &'static [NamedField<'static>],
+        }
+
+        impl CaptureFields {
+            fn field(&self, field: &NamedField<'_>, value: Value<'_>) -> (FieldName, OwnedValue) {
+                let name = match static_index(self.static_fields, field) {
+                    Some(index) => FieldName::Static(&self.static_fields[index]),
+                    None => FieldName::Dynamic(field.name().to_string()),
+                };
+                (name, OwnedValue::from(value))
+            }
+        }
+
+        impl Visit for CaptureFields {
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                let chunk = named_values
+                    .iter()
+                    .map(|(field, value)| self.field(field, *value))
+                    .collect();
+                if let OwnedFields::Named(chunks) = &mut self.fields {
+                    chunks.push(chunk);
+                }
+            }
+
+            fn visit_named_field(&mut self, field: &NamedField<'_>, value: Value<'_>) {
+                let chunk = vec![self.field(field, value)];
+                if let OwnedFields::Named(chunks) = &mut self.fields {
+                    chunks.push(chunk);
+                }
+            }
+
+            fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+                if let OwnedFields::Unnamed(chunks) = &mut self.fields {
+                    chunks.push(values.iter().copied().map(OwnedValue::from).collect());
+                }
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+        }
+
+        let mut capture = CaptureFields {
+            fields: if named {
+                OwnedFields::Named(Vec::new())
+            } else {
+                OwnedFields::Unnamed(Vec::new())
+            },
+            static_fields: static_fields.unwrap_or(&[]),
+        };
+        visit(&mut capture);
+        capture.fields
+    }
+
+    // Chunks made of consecutive fields of `static_fields` are replayed with
+    // the original `NamedField`s, which keeps `NamedValues::get()` working
+    // with fields taken from the definition.
+    fn visit(&self, static_fields: Option<&'static [NamedField<'static>]>, visit: &mut dyn Visit) {
+        match self {
+            OwnedFields::Named(chunks) => {
+                for chunk in chunks {
+                    if let ControlFlow::Break(()) = visit.control_flow() {
+                        break;
+                    }
+
+                    let values: Vec<_> = chunk.iter().map(|(_, value)| value.as_value()).collect();
+                    match static_fields.and_then(|fields| static_range(fields, chunk)) {
+                        Some(fields) => {
+                            visit.visit_named_fields(&NamedValues::new(fields, &values));
+                        }
+                        None => {
+                            let fields: Vec<_> = chunk
+                                .iter()
+                                .map(|(field, _)| NamedField::new(field.name()))
+                                .collect();
+                            visit.visit_named_fields(&NamedValues::new(&fields, &values));
+                        }
+                    }
+                }
+            }
+            OwnedFields::Unnamed(chunks) => {
+                for chunk in chunks {
+                    if let ControlFlow::Break(()) = visit.control_flow() {
+                        break;
+                    }
+
+                    let values: Vec<_> = chunk.iter().map(Valuable::as_value).collect();
+                    visit.visit_unnamed_fields(&values);
+                }
+            }
+        }
+    }
+}
+
+// Returns the part of `fields` that the fields of `chunk` were taken from, if
+// they are consecutive fields of `fields`.
+fn static_range(
+    fields: &'static [NamedField<'static>],
+    chunk: &[(FieldName, OwnedValue)],
+) -> Option<&'static [NamedField<'static>]> {
+    let start = match chunk.first() {
+        Some((FieldName::Static(field), _)) => static_index(fields, field)?,
+        _ => return None,
+    };
+    let range = fields.get(start..start + chunk.len())?;
+    let consecutive = range.iter().zip(chunk).all(|(expect, (field, _))| {
+        matches!(field, FieldName::Static(field) if core::ptr::eq(*field, expect))
+    });
+    if consecutive {
+        Some(range)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,565 @@
+// Structural comparison and hashing of `Value` and the owned value types. The
+// rules are documented on `Value`.
+//
+// Values are compared by walking both sides in step: while one side passes a
+// batch of nested values to a visitor, the matching nested values of the
+// other side are visited, so that both are borrowed at the same time. Only
+// maps, which are compared by their sorted entries, formatted values and
+// errors, which are compared by their output, and the rest of containers that
+// can only be walked from the start, are captured as `OwnedValue`s.
+
+use super::*;
+use crate::cmp::{cmp_shallow, hash_shallow, rank};
+
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+
+impl PartialEq for Value<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value<'_> {}
+
+impl PartialOrd for Value<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let Some(ordering) = cmp_shallow(self, other) {
+            return ordering;
+        }
+
+        match (*self, *other) {
+            (Value::Listable(a), Value::Listable(b)) => cmp_items(Items::List(a), Items::List(b)),
+            (Value::Structable(a), Value::Structable(b)) => {
+                let (a_def, b_def) = (a.definition(), b.definition());
+                a_def.name().cmp(b_def.name()).then_with(|| {
+                    cmp_fields(
+                        Items::Struct(a, a_def.fields().is_named()),
+                        Items::Struct(b, b_def.fields().is_named()),
+                    )
+                })
+            }
+            (Value::Enumerable(a), Value::Enumerable(b)) => {
+                let (a_variant, b_variant) = (a.variant(), b.variant());
+                a.definition()
+                    .name()
+                    .cmp(b.definition().name())
+                    .then_with(|| a_variant.name().cmp(b_variant.name()))
+                    .then_with(|| {
+                        cmp_fields(
+                            Items::Enum(a, a_variant.is_named_fields()),
+                            Items::Enum(b, b_variant.is_named_fields()),
+                        )
+                    })
+            }
+            (Value::Tuplable(a), Value::Tuplable(b)) => cmp_items(Items::Tuple(a), Items::Tuple(b)),
+            _ => OwnedValue::from(*self).cmp(&OwnedValue::from(*other)),
+        }
+    }
+}
+
+impl Hash for Value<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if hash_shallow(self, state) {
+            return;
+        }
+
+        let items = match *self {
+            Value::Listable(v) => Items::List(v),
+            Value::Structable(v) => {
+                let def = v.definition();
+                def.name().hash(state);
+                def.fields().is_named().hash(state);
+                Items::Struct(v, def.fields().is_named())
+            }
+            Value::Enumerable(v) => {
+                let variant = v.variant();
+                v.definition().name().hash(state);
+                variant.name().hash(state);
+                variant.is_named_fields().hash(state);
+                Items::Enum(v, variant.is_named_fields())
+            }
+            Value::Tuplable(v) => Items::Tuple(v),
+            _ => return OwnedValue::from(*self).hash(state),
+        };
+
+        rank(self).hash(state);
+        let mut len = 0_usize;
+        items.visit_range(0..usize::MAX, &mut |name, value| {
+            name.hash(state);
+            value.hash(state);
+            len += 1;
+            true
+        });
+        len.hash(state);
+    }
+}
+
+// The nested values of a value, in the order they are compared in.
+#[derive(Clone, Copy)]
+enum Items<'a> {
+    // The elements of a list.
+    List(&'a dyn Listable),
+    // The named or unnamed fields of a struct or enum variant, as given by
+    // its definition. Fields of the other kind are ignored, as when capturing
+    // the value.
+    Struct(&'a dyn Structable, bool),
+    Enum(&'a dyn Enumerable, bool),
+    // The fields of a tuple.
+    Tuple(&'a dyn Tuplable),
+}
+
+impl Items<'_> {
+    // `None` for list elements, or whether the fields are named.
+    fn fields(&self) -> Option<bool> {
+        match *self {
+            Items::List(_) => None,
+            Items::Struct(_, named) | Items::Enum(_, named) => Some(named),
+            Items::Tuple(_) => Some(false),
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        match *self {
+            Items::List(v) => v.visit(visit),
+            Items::Struct(v, _) => v.visit(visit),
+            Items::Enum(v, _) => v.visit(visit),
+            Items::Tuple(v) => v.visit(visit),
+        }
+    }
+
+    // Passes the items in `range` to `f`, with their field name, until `f`
+    // returns `false`.
+    //
+    // Returns how much work the walk did, as the number of items and
+    // `control_flow` calls seen. Walks that start from the first item
+    // instead of the start of `range` do more than twice the length of
+    // `range`.
+    fn visit_range(
+        &self,
+        range: Range<usize>,
+        f: &mut dyn FnMut(Option<&str>, Value<'_>) -> bool,
+    ) -> usize {
+        struct VisitItems<'a> {
+            range: Range<usize>,
+            index: usize,
+            // `None` for list elements, or whether the fields are named.
+            fields: Option<bool>,
+            done: bool,
+            f: &'a mut dyn FnMut(Option<&str>, Value<'_>) -> bool,
+            // Lists that skip to the start of the range by visiting the
+            // items before it still ask for the control flow of each one.
+            walked: Cell<usize>,
+        }
+
+        impl VisitItems<'_> {
+            fn item(&mut self, name: Option<&str>, value: Value<'_>) {
+                if !self.done && self.range.contains(&self.index) {
+                    self.done = !(self.f)(name, value);
+                }
+                self.index += 1;
+                self.walked.set(self.walked.get() + 1);
+            }
+        }
+
+        impl Visit for VisitItems<'_> {
+            fn visit_value(&mut self, value: Value<'_>) {
+                if self.fields.is_none() {
+                    self.item(None, value);
+                }
+            }
+
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                if self.fields == Some(true) {
+                    for (field, value) in named_values {
+                        self.item(Some(field.name()), *value);
+                    }
+                }
+            }
+
+            fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+                if self.fields == Some(false) {
+                    for value in values {
+                        self.item(None, *value);
+                    }
+                }
+            }
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                self.walked.set(self.walked.get() + 1);
+                if self.done || self.index >= self.range.end {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }
+        }
+
+        let mut visit = VisitItems {
+            range: range.clone(),
+            index: 0,
+            fields: self.fields(),
+            done: false,
+            f,
+            walked: Cell::new(0),
+        };
+        match *self {
+            // Lists can skip to the start of the range.
+            Items::List(list) => {
+                visit.index = range.start;
+                list.visit_range(range, &mut visit);
+            }
+            items => items.visit(&mut visit),
+        }
+        visit.walked.get()
+    }
+}
+
+// Compares the fields of two structs or enum variants. Named fields sort
+// after unnamed fields.
+fn cmp_fields(a: Items<'_>, b: Items<'_>) -> Ordering {
+    a.fields().cmp(&b.fields()).then_with(|| cmp_items(a, b))
+}
+
+// Compares the items of `a` and `b` in order, then their number. Each batch
+// of items passed to a visitor by `a` is compared to the items of `b` at the
+// same positions. If `b` cannot skip to those positions without walking its
+// earlier items, its remaining items are captured once instead, so that
+// comparing stays linear.
+fn cmp_items(a: Items<'_>, b: Items<'_>) -> Ordering {
+    struct CmpItems<'a> {
+        other: Items<'a>,
+        len: usize,
+        // `None` for list elements, or whether the fields are named.
+        fields: Option<bool>,
+        ordering: Ordering,
+        // The items of `other` after the first `len`, once captured.
+        rest: Option<vec::IntoIter<(Option<String>, OwnedValue)>>,
+    }
+
+    impl CmpItems<'_> {
+        fn batch<'v>(
+            &mut self,
+            items: impl ExactSizeIterator<Item = (Option<&'v str>, Value<'v>)>,
+        ) {
+            if self.ordering != Ordering::Equal {
+                return;
+            }
+
+            let mut items = items;
+            let range = self.len..self.len + items.len();
+            self.len = range.end;
+
+            if let Some(rest) = &mut self.rest {
+                self.ordering = cmp_captured(items, rest);
+                return;
+            }
+
+            let mut ordering = Ordering::Equal;
+            let mut remaining = items.len();
+            let walked = self.other.visit_range(range.clone(), &mut |name, value| {
+                let (a_name, a_value) = items.next().unwrap();
+                remaining -= 1;
+                ordering = a_name.cmp(&name).then_with(|| a_value.cmp(&value));
+                ordering == Ordering::Equal
+            });
+
+            if ordering == Ordering::Equal && remaining != 0 {
+                // `other` has fewer items.
+                ordering = Ordering::Greater;
+            }
+            self.ordering = ordering;
+
+            if ordering == Ordering::Equal && walked > 2 * range.len() + 2 {
+                let mut rest = vec![];
+                self.other
+                    .visit_range(range.end..usize::MAX, &mut |name, value| {
+                        rest.push((name.map(String::from), OwnedValue::from(value)));
+                        true
+                    });
+                self.rest = Some(rest.into_iter());
+            }
+        }
+    }
+
+    impl Visit for CmpItems<'_> {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if self.fields.is_none() {
+                self.batch(core::iter::once((None, value)));
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            if self.fields == Some(true) {
+                self.batch(
+                    named_values
+                        .iter()
+                        .map(|(field, value)| (Some(field.name()), *value)),
+                );
+            }
+        }
+
+        fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+            if self.fields == Some(false) {
+                self.batch(values.iter().map(|value| (None, *value)));
+            }
+        }
+
+        fn control_flow(&self) -> ControlFlow<()> {
+            if self.ordering == Ordering::Equal {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    let mut cmp = CmpItems {
+        other: b,
+        len: 0,
+        fields: a.fields(),
+        ordering: Ordering::Equal,
+        rest: None,
+    };
+    a.visit(&mut cmp);
+
+    if cmp.ordering != Ordering::Equal {
+        return cmp.ordering;
+    }
+
+    // `other` has more items.
+    let more = match &mut cmp.rest {
+        Some(rest) => rest.next().is_some(),
+        None => {
+            let mut more = false;
+            b.visit_range(cmp.len..cmp.len + 1, &mut |_, _| {
+                more = true;
+                false
+            });
+            more
+        }
+    };
+    if more {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+// Compares `items` to the next captured items of the other value.
+fn cmp_captured<'v>(
+    items: impl Iterator<Item = (Option<&'v str>, Value<'v>)>,
+    rest: &mut impl Iterator<Item = (Option<String>, OwnedValue)>,
+) -> Ordering {
+    for (a_name, a_value) in items {
+        let ordering = match rest.next() {
+            Some((name, value)) => a_name
+                .cmp(&name.as_deref())
+                .then_with(|| a_value.cmp(&value.as_value())),
+            // The other value has fewer items.
+            None => Ordering::Greater,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+impl PartialEq for OwnedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OwnedValue {}
+
+impl PartialOrd for OwnedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OwnedValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let Some(ordering) = cmp_shallow(&self.as_value(), &other.as_value()) {
+            return ordering;
+        }
+
+        match (self, other) {
+            #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+            (OwnedValue::Error(a), OwnedValue::Error(b)) => a.cmp(b),
+            (OwnedValue::Listable(a), OwnedValue::Listable(b)) => a.cmp(b),
+            (OwnedValue::Mappable(a), OwnedValue::Mappable(b)) => a.cmp(b),
+            (OwnedValue::Structable(a), OwnedValue::Structable(b)) => a.cmp(b),
+            (OwnedValue::Enumerable(a), OwnedValue::Enumerable(b)) => a.cmp(b),
+            (OwnedValue::Tuplable(a), OwnedValue::Tuplable(b)) => a.cmp(b),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Hash for OwnedValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let value = self.as_value();
+        if hash_shallow(&value, state) {
+            return;
+        }
+
+        rank(&value).hash(state);
+        match self {
+            #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+            OwnedValue::Error(v) => v.hash(state),
+            OwnedValue::Listable(v) => v.hash(state),
+            OwnedValue::Mappable(v) => v.hash(state),
+            OwnedValue::Structable(v) => v.hash(state),
+            OwnedValue::Enumerable(v) => v.hash(state),
+            OwnedValue::Tuplable(v) => v.hash(state),
+            _ => unreachable!(),
+        }
+    }
+}
+
+// Implements comparison and hashing in terms of a key returned by the type's
+// `cmp_key()` method.
+macro_rules! by_key {
+    ($($ty:ty,)*) => {
+        $(
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for $ty {}
+
+            impl PartialOrd for $ty {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for $ty {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.cmp_key().cmp(&other.cmp_key())
+                }
+            }
+
+            impl Hash for $ty {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.cmp_key().hash(state);
+                }
+            }
+        )*
+    };
+}
+
+by_key! {
+    OwnedList,
+    OwnedMap,
+    OwnedStruct,
+    OwnedEnum,
+    OwnedTuple,
+    OwnedFields,
+    FieldName,
+}
+
+impl OwnedList {
+    // Lists are compared by their elements, regardless of how they were
+    // visited.
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        self.values().collect::<Vec<_>>()
+    }
+}
+
+impl OwnedMap {
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort();
+        entries
+    }
+}
+
+impl OwnedStruct {
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        (self.name(), &self.fields)
+    }
+}
+
+impl OwnedEnum {
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        (self.name(), self.variant_name(), &self.fields)
+    }
+}
+
+impl OwnedTuple {
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        Flatten(&self.fields)
+    }
+}
+
+impl OwnedFields {
+    // Named fields sort after unnamed fields. Fields are compared regardless
+    // of how they were split across `visit_*_fields` calls.
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        match self {
+            OwnedFields::Named(chunks) => (Some(Flatten(chunks)), None),
+            OwnedFields::Unnamed(chunks) => (None, Some(Flatten(chunks))),
+        }
+    }
+}
+
+impl FieldName {
+    // Fields are compared by name, whether or not they are statically defined.
+    fn cmp_key(&self) -> impl Ord + Hash + '_ {
+        self.name()
+    }
+}
+
+// Compares and hashes chunked items as if they were a single slice.
+struct Flatten<'a, T>(&'a [Vec<T>]);
+
+impl<T> Flatten<'_, T> {
+    fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.0.iter().flatten()
+    }
+}
+
+impl<T: Ord> PartialEq for Flatten<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Flatten<'_, T> {}
+
+impl<T: Ord> PartialOrd for Flatten<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Flatten<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for Flatten<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0_usize;
+        for item in self.iter() {
+            item.hash(state);
+            len += 1;
+        }
+        len.hash(state);
+    }
+}
@@ -0,0 +1,1023 @@
+//! Selecting nested values by path.
+//!
+//! A [`Location`] describes where a value was found inside another value,
+//! as a sequence of [`Segment`]s: struct fields, tuple indices, list
+//! elements, map keys, and enum variants. Locations are built on the stack
+//! while traversing, so creating them does not allocate.
+//!
+//! A [`PathVisit`] implementation receives the location of each value it
+//! visits, which is useful for redacting, flattening, or reporting errors in
+//! nested values. [`visit_nested()`] visits the values nested in a container
+//! along with their locations.
+//!
+//! With the `alloc` feature enabled, this module also provides [`Selector`],
+//! a small query language for finding nested values without writing a
+//! [`Visit`] implementation, and [`OwnedLocation`], an owned copy of a
+//! [`Location`] that can be stored.
+//!
+//! # Selector syntax
+//!
+//! A selector is a sequence of steps. Each step is applied to the values
+//! matched by the previous steps:
+//!
+//! | Step        | Matches                                                      |
+//! |-------------|--------------------------------------------------------------|
+//! | `.name`     | The named field `name` of a struct or enum variant.          |
+//! | `."name"`   | Same as `.name`, for names that are not identifiers.         |
+//! | `.0`        | The unnamed field `0` of a tuple, tuple struct, or variant.  |
+//! | `.*`        | Every field of a struct, enum variant, or tuple.             |
+//! | `[3]`       | The element `3` of a list, or the entry with key `3` of a map. |
+//! | `["key"]`   | The entry with the string key `key` of a map.                |
+//! | `[*]`       | Every element of a list, or every value of a map.            |
+//! | `::Variant` | The value itself, if it is an enum currently set to `Variant`. |
+//!
+//! The leading `.` of the first step may be omitted, so `items[3].price`
+//! and `.items[3].price` are the same selector. An empty selector matches
+//! the root value.
+//!
+//! Selectors work with both statically and dynamically defined types, as
+//! values are found by visiting them.
+//!
+//! # Examples
+//!
+//! ```
+//! use valuable::path::Selector;
+//! use valuable::Valuable;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Valuable)]
+//! struct Request {
+//!     headers: HashMap<String, String>,
+//!     items: Vec<Item>,
+//! }
+//!
+//! #[derive(Valuable)]
+//! struct Item {
+//!     price: u32,
+//! }
+//!
+//! let mut headers = HashMap::new();
+//! headers.insert("host".to_string(), "example.com".to_string());
+//!
+//! let request = Request {
+//!     headers,
+//!     items: vec![Item { price: 10 }, Item { price: 20 }],
+//! };
+//!
+//! let host = Selector::parse(r#"headers["host"]"#).unwrap();
+//! host.select(&request, |location, value| {
+//!     assert_eq!(location.to_string(), r#".headers["host"]"#);
+//!     assert_eq!(value.as_str(), Some("example.com"));
+//! });
+//!
+//! let mut prices = vec![];
+//! Selector::parse("items[*].price")
+//!     .unwrap()
+//!     .select(&request, |location, value| {
+//!         prices.push((location.to_string(), value.as_u32().unwrap()));
+//!     });
+//!
+//! assert_eq!(
+//!     prices,
+//!     [
+//!         (".items[0].price".to_string(), 10),
+//!         (".items[1].price".to_string(), 20),
+//!     ]
+//! );
+//! ```
+
+use crate::*;
+
+use core::fmt;
+use core::ops::ControlFlow;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, string::ToString, vec::Vec};
+
+/// A single step in a [`Location`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum Segment<'a> {
+    /// A named field of a struct or enum variant.
+    Field(&'a str),
+
+    /// An unnamed field of a tuple, tuple struct, or enum variant.
+    Index(usize),
+
+    /// An element of a list.
+    Element(usize),
+
+    /// The value of a map entry with the given key.
+    Key(Value<'a>),
+
+    /// An enum variant.
+    ///
+    /// This segment does not descend into the value: it records that the
+    /// enum was matched against the variant.
+    Variant(&'a str),
+}
+
+/// The location of a nested value, relative to the root value.
+///
+/// A `Location` is a linked list of [`Segment`]s, where each location borrows
+/// its parent. This allows building locations on the stack while traversing
+/// nested values, without allocating.
+///
+/// Locations are displayed using the [selector syntax](self#selector-syntax).
+///
+/// # Examples
+///
+/// ```
+/// use valuable::path::{Location, Segment};
+/// use valuable::Value;
+///
+/// let root = Location::root();
+/// let items = root.child(Segment::Field("items"));
+/// let item = items.child(Segment::Element(3));
+/// let price = item.child(Segment::Field("price"));
+///
+/// assert_eq!(price.to_string(), ".items[3].price");
+/// assert_eq!(price.depth(), 3);
+/// assert!(root.is_root());
+///
+/// let host = root.child(Segment::Key(Value::String("host")));
+/// assert_eq!(host.to_string(), r#"["host"]"#);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Location<'a> {
+    parent: Option<&'a Location<'a>>,
+    segment: Option<Segment<'a>>,
+    depth: usize,
+}
+
+impl<'a> Location<'a> {
+    /// Returns the location of the root value.
+    pub const fn root() -> Location<'a> {
+        Location {
+            parent: None,
+            segment: None,
+            depth: 0,
+        }
+    }
+
+    /// Returns the location reached by following `segment` from `self`.
+    pub fn child<'b>(&'b self, segment: Segment<'b>) -> Location<'b> {
+        Location {
+            parent: Some(self),
+            segment: Some(segment),
+            depth: self.depth + 1,
+        }
+    }
+
+    /// Returns the last segment of the location, or `None` for the root.
+    pub fn segment(&self) -> Option<&Segment<'a>> {
+        self.segment.as_ref()
+    }
+
+    /// Returns the parent location, or `None` for the root.
+    pub fn parent(&self) -> Option<&Location<'a>> {
+        self.parent
+    }
+
+    /// Returns the number of segments in the location.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns `true` if this is the location of the root value.
+    pub fn is_root(&self) -> bool {
+        self.parent.is_none()
+    }
+}
+
+impl Default for Location<'_> {
+    fn default() -> Self {
+        Location::root()
+    }
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(parent) = self.parent {
+            fmt::Display::fmt(parent, fmt)?;
+        }
+        match &self.segment {
+            Some(segment) => fmt::Display::fmt(segment, fmt),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for Location<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Location(\"{}\")", self)
+    }
+}
+
+impl fmt::Display for Segment<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Field(name) => {
+                fmt.write_str(".")?;
+                write_name(name, fmt)
+            }
+            Segment::Index(index) => write!(fmt, ".{}", index),
+            Segment::Element(index) => write!(fmt, "[{}]", index),
+            Segment::Key(key) => write!(fmt, "[{:?}]", key),
+            Segment::Variant(name) => write!(fmt, "::{}", name),
+        }
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn write_name(name: &str, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if is_ident(name) {
+        fmt.write_str(name)
+    } else {
+        write!(fmt, "{:?}", name)
+    }
+}
+
+/// A visitor that receives the location of each value it visits.
+///
+/// A [`Visit`] implementation does not know which field or element led to
+/// the value it is visiting, unless it tracks that itself. A `PathVisit`
+/// implementation receives the [`Location`] of each value along with the
+/// value. Locations are built on the stack, so tracking them does not
+/// allocate.
+///
+/// Start visiting by passing the root value to
+/// [`visit_value`](PathVisit::visit_value) with [`Location::root()`], and
+/// descend into containers with [`visit_nested()`]. To pass a `PathVisit`
+/// implementation where a [`Visit`] implementation is expected, wrap it in
+/// [`WithPath`].
+///
+/// # Examples
+///
+/// Collecting the location of every string in a value:
+///
+/// ```
+/// use valuable::path::{self, Location, PathVisit};
+/// use valuable::{Valuable, Value};
+///
+/// #[derive(Valuable)]
+/// struct User {
+///     name: &'static str,
+///     emails: Vec<&'static str>,
+///     role: Role,
+/// }
+///
+/// #[derive(Valuable)]
+/// enum Role {
+///     Admin { team: &'static str },
+///     Guest,
+/// }
+///
+/// struct Strings(Vec<String>);
+///
+/// impl PathVisit for Strings {
+///     fn visit_value(&mut self, location: &Location<'_>, value: Value<'_>) {
+///         match value {
+///             Value::String(s) => self.0.push(format!("{} = {}", location, s)),
+///             value => path::visit_nested(self, location, value),
+///         }
+///     }
+/// }
+///
+/// let user = User {
+///     name: "alice",
+///     emails: vec!["alice@example.com"],
+///     role: Role::Admin { team: "infra" },
+/// };
+///
+/// let mut strings = Strings(vec![]);
+/// strings.visit_value(&Location::root(), user.as_value());
+///
+/// assert_eq!(
+///     strings.0,
+///     [
+///         ".name = alice",
+///         ".emails[0] = alice@example.com",
+///         ".role::Admin.team = infra",
+///     ]
+/// );
+/// ```
+pub trait PathVisit {
+    /// Visits a value found at `location`.
+    ///
+    /// To visit the values nested in `value`, call [`visit_nested()`].
+    fn visit_value(&mut self, location: &Location<'_>, value: Value<'_>);
+
+    /// Returns whether the traversal should continue.
+    ///
+    /// [`WithPath`] checks it between fields, elements, and entries, and
+    /// forwards it as [`Visit::control_flow`]. The default implementation
+    /// always continues.
+    fn control_flow(&self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Visits the values nested in `value`, which was found at `location`.
+///
+/// Struct and enum fields, tuple elements, list elements, and map values are
+/// passed to [`PathVisit::visit_value`], each with its own location. The
+/// fields of an enum are located under a [`Segment::Variant`] segment for the
+/// current variant. Values without nested values are ignored.
+///
+/// See [`PathVisit`] for an example.
+pub fn visit_nested<V>(visit: &mut V, location: &Location<'_>, value: Value<'_>)
+where
+    V: PathVisit + ?Sized,
+{
+    match value {
+        Value::Listable(v) => v.visit(&mut WithPath::new(visit, location)),
+        Value::Mappable(v) => v.visit(&mut WithPath::new(visit, location)),
+        Value::Structable(v) => v.visit(&mut WithPath::new(visit, location)),
+        Value::Tuplable(v) => v.visit(&mut WithPath::new(visit, location)),
+        Value::Enumerable(v) => {
+            let variant = v.variant();
+            let location = location.child(Segment::Variant(variant.name()));
+            v.visit(&mut WithPath::new(visit, &location));
+        }
+        _ => {}
+    }
+}
+
+/// A [`Visit`] adapter that passes values to a [`PathVisit`] implementation,
+/// along with their location.
+///
+/// Each value passed to the adapter is located relative to the location it
+/// was created with:
+///
+/// * values passed to [`visit_value`](Visit::visit_value) and primitive
+///   slice elements at [`Segment::Element`], counting from zero,
+/// * named fields at [`Segment::Field`],
+/// * unnamed fields at [`Segment::Index`], counting from zero,
+/// * map values at [`Segment::Key`].
+///
+/// The adapter does not know which enum variant the fields it receives
+/// belong to. Use [`visit_nested()`] to visit an enum, which adds a
+/// [`Segment::Variant`] segment.
+///
+/// # Examples
+///
+/// ```
+/// use valuable::path::{Location, PathVisit, WithPath};
+/// use valuable::{Valuable, Value};
+///
+/// struct Locations(Vec<String>);
+///
+/// impl PathVisit for Locations {
+///     fn visit_value(&mut self, location: &Location<'_>, _: Value<'_>) {
+///         self.0.push(location.to_string());
+///     }
+/// }
+///
+/// let mut locations = Locations(vec![]);
+/// let root = Location::root();
+/// (1, "two").visit(&mut WithPath::new(&mut locations, &root));
+///
+/// assert_eq!(locations.0, [".0", ".1"]);
+/// ```
+#[derive(Debug)]
+pub struct WithPath<'a, 'b, V: ?Sized> {
+    visit: &'a mut V,
+    location: &'a Location<'b>,
+    // Index of the next element or unnamed field, counted across calls.
+    index: usize,
+}
+
+impl<'a, 'b, V: PathVisit + ?Sized> WithPath<'a, 'b, V> {
+    /// Wraps a path-aware visitor, locating values relative to `location`.
+    pub fn new(visit: &'a mut V, location: &'a Location<'b>) -> WithPath<'a, 'b, V> {
+        WithPath {
+            visit,
+            location,
+            index: 0,
+        }
+    }
+
+    fn visit_indexed(&mut self, value: Value<'_>, segment: fn(usize) -> Segment<'static>) {
+        let location = self.location.child(segment(self.index));
+        self.index += 1;
+        self.visit.visit_value(&location, value);
+    }
+}
+
+impl<V: PathVisit + ?Sized> Visit for WithPath<'_, '_, V> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        self.visit_indexed(value, Segment::Element);
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            if let ControlFlow::Break(()) = self.visit.control_flow() {
+                break;
+            }
+            let location = self.location.child(Segment::Field(field.name()));
+            self.visit.visit_value(&location, *value);
+        }
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        for value in values {
+            if let ControlFlow::Break(()) = self.visit.control_flow() {
+                break;
+            }
+            self.visit_indexed(*value, Segment::Index);
+        }
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        let location = self.location.child(Segment::Key(key));
+        self.visit.visit_value(&location, value);
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        self.visit.control_flow()
+    }
+}
+
+/// An owned version of [`Segment`].
+#[cfg(feature = "alloc")]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OwnedSegment {
+    /// A named field of a struct or enum variant.
+    Field(String),
+
+    /// An unnamed field of a tuple, tuple struct, or enum variant.
+    Index(usize),
+
+    /// An element of a list.
+    Element(usize),
+
+    /// The value of a map entry with the given key.
+    Key(OwnedValue),
+
+    /// An enum variant.
+    Variant(String),
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedSegment {
+    /// Borrows the segment as a [`Segment`].
+    pub fn as_segment(&self) -> Segment<'_> {
+        match self {
+            OwnedSegment::Field(name) => Segment::Field(name),
+            OwnedSegment::Index(index) => Segment::Index(*index),
+            OwnedSegment::Element(index) => Segment::Element(*index),
+            OwnedSegment::Key(key) => Segment::Key(key.as_value()),
+            OwnedSegment::Variant(name) => Segment::Variant(name),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&Segment<'_>> for OwnedSegment {
+    fn from(segment: &Segment<'_>) -> OwnedSegment {
+        match *segment {
+            Segment::Field(name) => OwnedSegment::Field(name.to_string()),
+            Segment::Index(index) => OwnedSegment::Index(index),
+            Segment::Element(index) => OwnedSegment::Element(index),
+            Segment::Key(key) => OwnedSegment::Key(OwnedValue::from(key)),
+            Segment::Variant(name) => OwnedSegment::Variant(name.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OwnedSegment {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_segment(), fmt)
+    }
+}
+
+/// An owned version of [`Location`].
+///
+/// # Examples
+///
+/// ```
+/// use valuable::path::{Location, OwnedLocation, OwnedSegment, Segment};
+///
+/// let root = Location::root();
+/// let field = root.child(Segment::Field("name"));
+///
+/// let owned = OwnedLocation::from(&field);
+/// assert_eq!(owned.segments(), [OwnedSegment::Field("name".to_string())]);
+/// assert_eq!(owned.to_string(), ".name");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OwnedLocation {
+    segments: Vec<OwnedSegment>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedLocation {
+    /// Returns the location of the root value.
+    pub fn root() -> OwnedLocation {
+        OwnedLocation::default()
+    }
+
+    /// Returns the location's segments, starting from the root.
+    pub fn segments(&self) -> &[OwnedSegment] {
+        &self.segments
+    }
+
+    /// Appends a segment to the location.
+    pub fn push(&mut self, segment: OwnedSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Removes the last segment of the location and returns it.
+    pub fn pop(&mut self) -> Option<OwnedSegment> {
+        self.segments.pop()
+    }
+
+    /// Returns `true` if this is the location of the root value.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&Location<'_>> for OwnedLocation {
+    fn from(location: &Location<'_>) -> OwnedLocation {
+        let mut segments = Vec::with_capacity(location.depth());
+        let mut current = Some(location);
+        while let Some(location) = current {
+            if let Some(segment) = location.segment() {
+                segments.push(OwnedSegment::from(segment));
+            }
+            current = location.parent();
+        }
+        segments.reverse();
+        OwnedLocation { segments }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OwnedLocation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            fmt::Display::fmt(segment, fmt)?;
+        }
+        Ok(())
+    }
+}
+
+/// A parsed path selector.
+///
+/// See the [module documentation](self) for the syntax.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    // .name
+    Field(String),
+    // .0
+    Index(usize),
+    // .*
+    AnyField,
+    // [3]
+    Element(usize),
+    // ["key"]
+    Key(String),
+    // [*]
+    AnyElement,
+    // ::Variant
+    Variant(String),
+}
+
+/// An error returned when parsing a [`Selector`] fails.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    message: &'static str,
+}
+
+#[cfg(feature = "alloc")]
+impl ParseError {
+    /// Returns the byte offset in the input at which parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{} at position {}", self.message, self.position)
+    }
+}
+
+#[cfg(all(feature = "alloc", any(feature = "std", not(valuable_no_core_error))))]
+impl crate::Error for ParseError {}
+
+#[cfg(feature = "alloc")]
+impl Selector {
+    /// Parses a selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::path::Selector;
+    ///
+    /// let selector = Selector::parse(r#"request.headers["host"]"#).unwrap();
+    /// assert_eq!(selector.to_string(), r#".request.headers["host"]"#);
+    ///
+    /// let err = Selector::parse("items[").unwrap_err();
+    /// assert_eq!(err.position(), 6);
+    /// ```
+    pub fn parse(input: &str) -> Result<Selector, ParseError> {
+        Parser { input, pos: 0 }.parse()
+    }
+
+    /// Calls `f` with each value in `value` matched by the selector, along
+    /// with the value's location.
+    ///
+    /// Values are passed to `f` in the order they are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::path::Selector;
+    ///
+    /// let list = vec![(1, "one"), (2, "two")];
+    ///
+    /// let mut names = vec![];
+    /// Selector::parse("[*].1").unwrap().select(&list, |location, value| {
+    ///     names.push(format!("{} = {:?}", location, value));
+    /// });
+    ///
+    /// assert_eq!(names, [r#"[0].1 = "one""#, r#"[1].1 = "two""#]);
+    /// ```
+    pub fn select<F>(&self, value: &dyn Valuable, mut f: F)
+    where
+        F: FnMut(&Location<'_>, Value<'_>),
+    {
+        select(&self.steps, value.as_value(), &Location::root(), &mut f);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::str::FromStr for Selector {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Selector, ParseError> {
+        Selector::parse(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Selector {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            match step {
+                Step::Field(name) => fmt::Display::fmt(&Segment::Field(name), fmt)?,
+                Step::Index(index) => fmt::Display::fmt(&Segment::Index(*index), fmt)?,
+                Step::AnyField => fmt.write_str(".*")?,
+                Step::Element(index) => fmt::Display::fmt(&Segment::Element(*index), fmt)?,
+                Step::Key(key) => fmt::Display::fmt(&Segment::Key(Value::String(key)), fmt)?,
+                Step::AnyElement => fmt.write_str("[*]")?,
+                Step::Variant(name) => fmt::Display::fmt(&Segment::Variant(name), fmt)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for Selector {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Selector(\"{}\")", self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Parser<'_> {
+    fn parse(mut self) -> Result<Selector, ParseError> {
+        let mut steps = Vec::new();
+
+        // The leading `.` of the first step is optional.
+        if self.peek().map_or(false, |c| c.is_alphabetic() || c == '_') {
+            steps.push(Step::Field(self.ident()));
+        }
+
+        while let Some(c) = self.peek() {
+            let step = match c {
+                '.' => {
+                    self.bump();
+                    match self.peek() {
+                        Some('*') => {
+                            self.bump();
+                            Step::AnyField
+                        }
+                        Some('"') => Step::Field(self.string()?),
+                        Some(c) if c.is_ascii_digit() => Step::Index(self.number()?),
+                        Some(c) if c.is_alphabetic() || c == '_' => Step::Field(self.ident()),
+                        _ => return Err(self.error("expected field name, index, or `*`")),
+                    }
+                }
+                '[' => {
+                    self.bump();
+                    let step = match self.peek() {
+                        Some('*') => {
+                            self.bump();
+                            Step::AnyElement
+                        }
+                        Some('"') => Step::Key(self.string()?),
+                        Some(c) if c.is_ascii_digit() => Step::Element(self.number()?),
+                        _ => return Err(self.error("expected index, string key, or `*`")),
+                    };
+                    self.expect(']')?;
+                    step
+                }
+                ':' => {
+                    self.bump();
+                    self.expect(':')?;
+                    match self.peek() {
+                        Some(c) if c.is_alphabetic() || c == '_' => Step::Variant(self.ident()),
+                        _ => return Err(self.error("expected variant name")),
+                    }
+                }
+                _ => return Err(self.error("expected `.`, `[`, or `::`")),
+            };
+            steps.push(step);
+        }
+
+        Ok(Selector { steps })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.error(match expected {
+                ']' => "expected `]`",
+                ':' => "expected `::`",
+                _ => "unexpected character",
+            }))
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .map_or(false, |c| c.is_alphanumeric() || c == '_')
+        {
+            self.bump();
+        }
+        self.input[start..self.pos].to_string()
+    }
+
+    fn number(&mut self) -> Result<usize, ParseError> {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        self.input[start..self.pos].parse().map_err(|_| ParseError {
+            position: start,
+            message: "index out of range",
+        })
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump(); // opening quote
+
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    _ => {
+                        return Err(ParseError {
+                            position: self.pos,
+                            message: "invalid escape sequence",
+                        })
+                    }
+                },
+                Some(c) => s.push(c),
+                None => {
+                    return Err(ParseError {
+                        position: start,
+                        message: "unterminated string",
+                    })
+                }
+            }
+        }
+    }
+
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            position: self.pos,
+            message,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+type Callback<'f> = dyn FnMut(&Location<'_>, Value<'_>) + 'f;
+
+#[cfg(feature = "alloc")]
+fn select(steps: &[Step], value: Value<'_>, location: &Location<'_>, f: &mut Callback<'_>) {
+    let (step, rest) = match steps.split_first() {
+        Some(split) => split,
+        None => return f(location, value),
+    };
+
+    match step {
+        Step::Variant(name) => {
+            if let Value::Enumerable(enumerable) = value {
+                if enumerable.variant().name() == name {
+                    let location = location.child(Segment::Variant(name));
+                    select(rest, value, &location, f);
+                }
+            }
+        }
+        Step::Field(_) | Step::Index(_) | Step::AnyField => match value {
+            Value::Structable(v) => v.visit(&mut SelectFields::new(step, rest, location, f)),
+            Value::Enumerable(v) => v.visit(&mut SelectFields::new(step, rest, location, f)),
+            Value::Tuplable(v) => v.visit(&mut SelectFields::new(step, rest, location, f)),
+            _ => {}
+        },
+        Step::Element(_) | Step::Key(_) | Step::AnyElement => match value {
+            Value::Listable(v) => v.visit(&mut SelectElements::new(step, rest, location, f)),
+            Value::Mappable(v) => v.visit(&mut SelectElements::new(step, rest, location, f)),
+            _ => {}
+        },
+    }
+}
+
+// Applies a field step to the fields of a struct, enum, or tuple.
+#[cfg(feature = "alloc")]
+struct SelectFields<'a, 'b, 'f> {
+    step: &'a Step,
+    rest: &'a [Step],
+    location: &'a Location<'b>,
+    f: &'a mut Callback<'f>,
+    // Index of the next unnamed field, counted across calls.
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'b, 'f> SelectFields<'a, 'b, 'f> {
+    fn new(
+        step: &'a Step,
+        rest: &'a [Step],
+        location: &'a Location<'b>,
+        f: &'a mut Callback<'f>,
+    ) -> Self {
+        SelectFields {
+            step,
+            rest,
+            location,
+            f,
+            index: 0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for SelectFields<'_, '_, '_> {
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            let matched = match self.step {
+                Step::Field(name) => field.name() == name,
+                Step::AnyField => true,
+                _ => false,
+            };
+            if matched {
+                let location = self.location.child(Segment::Field(field.name()));
+                select(self.rest, *value, &location, self.f);
+            }
+        }
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        for value in values {
+            let index = self.index;
+            self.index += 1;
+
+            let matched = match self.step {
+                Step::Index(i) => *i == index,
+                Step::AnyField => true,
+                _ => false,
+            };
+            if matched {
+                let location = self.location.child(Segment::Index(index));
+                select(self.rest, *value, &location, self.f);
+            }
+        }
+    }
+
+    fn visit_value(&mut self, _: Value<'_>) {}
+}
+
+// Applies an element step to the elements of a list or the entries of a map.
+#[cfg(feature = "alloc")]
+struct SelectElements<'a, 'b, 'f> {
+    step: &'a Step,
+    rest: &'a [Step],
+    location: &'a Location<'b>,
+    f: &'a mut Callback<'f>,
+    // Index of the next list element.
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'b, 'f> SelectElements<'a, 'b, 'f> {
+    fn new(
+        step: &'a Step,
+        rest: &'a [Step],
+        location: &'a Location<'b>,
+        f: &'a mut Callback<'f>,
+    ) -> Self {
+        SelectElements {
+            step,
+            rest,
+            location,
+            f,
+            index: 0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Visit for SelectElements<'_, '_, '_> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        let index = self.index;
+        self.index += 1;
+
+        let matched = match self.step {
+            Step::Element(i) => *i == index,
+            Step::AnyElement => true,
+            _ => false,
+        };
+        if matched {
+            let location = self.location.child(Segment::Element(index));
+            select(self.rest, value, &location, self.f);
+        }
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        let matched = match self.step {
+            Step::Element(i) => key.as_u128() == Some(*i as u128),
+            Step::Key(k) => key.as_str() == Some(k),
+            Step::AnyElement => true,
+            _ => false,
+        };
+        if matched {
+            let location = self.location.child(Segment::Key(key));
+            select(self.rest, value, &location, self.f);
+        }
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        // Stop visiting a list once the selected element has been reached.
+        match self.step {
+            Step::Element(i) if self.index > *i => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
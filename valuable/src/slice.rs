@@ -76,6 +76,18 @@ macro_rules! slice {
             }
 
 
+            // Returns the elements of the slice in `range`. The range must be
+            // in bounds.
+            pub(crate) fn slice(&self, range: core::ops::Range<usize>) -> Slice<'a> {
+                #[allow(unused_doc_comments)]
+                match self {
+                    $(
+                        $(#[$attrs])*
+                        Slice::$variant(s) => Slice::$variant(&s[range]),
+                    )*
+                }
+            }
+
             /// Returns `true` if the slice is not empty.
             ///
             /// # Examples
@@ -95,6 +107,28 @@ macro_rules! slice {
                 self.len() == 0
             }
 
+            /// Returns the element at position `index`, or `None` if `index`
+            /// is out of bounds.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::{Slice, Value};
+            ///
+            /// let slice = Slice::U32(&[1, 1, 2, 3, 5]);
+            /// assert!(matches!(slice.get(3), Some(Value::U32(3))));
+            /// assert!(slice.get(5).is_none());
+            /// ```
+            pub fn get(&self, index: usize) -> Option<Value<'a>> {
+                #[allow(unused_doc_comments)]
+                match self {
+                    $(
+                        $(#[$attrs])*
+                        Slice::$variant(s) => <[$ty]>::get(s, index).map(Valuable::as_value),
+                    )*
+                }
+            }
+
             /// Returns an iterator over the slice.
             ///
             /// # Examples
@@ -0,0 +1,204 @@
+use crate::*;
+
+use core::fmt::{self, Write};
+use core::ops::ControlFlow;
+
+/// A string-like [`Valuable`] sub-type, visited in chunks.
+///
+/// [`Value::String`] requires the whole string to be stored in contiguous
+/// memory. Types that store text in pieces, such as ropes, or that produce
+/// very large strings incrementally, implement `Stringable` instead and
+/// return [`Value::Stringable`] from their [`Valuable::as_value`]
+/// implementation. Visitors then receive the text as a sequence of chunks,
+/// without it ever being copied into a single buffer.
+///
+/// # Inspecting
+///
+/// Inspecting the contents of a `Stringable` is done by visiting it. When
+/// visiting a `Stringable`, [`visit_str_chunk()`] is called once for each
+/// chunk, in order. The concatenation of all chunks is the string. By
+/// default, [`visit_str_chunk()`] passes each chunk to [`visit_value()`] as
+/// a [`Value::String`].
+///
+/// `dyn Stringable` implements [`Display`](fmt::Display), which writes the
+/// chunks one after the other, and [`Debug`](fmt::Debug), which formats the
+/// string as a quoted string literal, like `str` does.
+///
+/// [`visit_str_chunk()`]: Visit::visit_str_chunk
+/// [`visit_value()`]: Visit::visit_value
+///
+/// # Implementing
+///
+/// ```
+/// use valuable::{Stringable, Valuable, Value, Visit};
+///
+/// struct Rope {
+///     chunks: Vec<String>,
+/// }
+///
+/// impl Valuable for Rope {
+///     fn as_value(&self) -> Value<'_> {
+///         Value::Stringable(self)
+///     }
+///
+///     fn visit(&self, visit: &mut dyn Visit) {
+///         for chunk in &self.chunks {
+///             visit.visit_str_chunk(chunk);
+///         }
+///     }
+/// }
+///
+/// impl Stringable for Rope {
+///     fn size_hint(&self) -> (usize, Option<usize>) {
+///         let len = self.chunks.iter().map(|chunk| chunk.len()).sum();
+///         (len, Some(len))
+///     }
+/// }
+///
+/// let rope = Rope {
+///     chunks: vec!["hello ".to_string(), "world".to_string()],
+/// };
+///
+/// assert_eq!(format!("{:?}", rope.as_value()), r#""hello world""#);
+/// ```
+pub trait Stringable: Valuable {
+    /// Returns the bounds on the length of the string, in bytes.
+    ///
+    /// Specifically, `size_hint()` returns a tuple where the first element
+    /// is the lower bound, and the second element is the upper bound. As with
+    /// [`Listable::size_hint()`], the bounds are not enforced, and are
+    /// primarily intended for optimizations such as reserving space for the
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Stringable, Valuable, Value, Visit};
+    ///
+    /// struct Repeat(&'static str, usize);
+    ///
+    /// impl Valuable for Repeat {
+    ///     fn as_value(&self) -> Value<'_> {
+    ///         Value::Stringable(self)
+    ///     }
+    ///
+    ///     fn visit(&self, visit: &mut dyn Visit) {
+    ///         for _ in 0..self.1 {
+    ///             visit.visit_str_chunk(self.0);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Stringable for Repeat {
+    ///     fn size_hint(&self) -> (usize, Option<usize>) {
+    ///         let len = self.0.len() * self.1;
+    ///         (len, Some(len))
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Repeat("ab", 3).size_hint(), (6, Some(6)));
+    /// ```
+    fn size_hint(&self) -> (usize, Option<usize>);
+}
+
+macro_rules! deref {
+    (
+        $(
+            $(#[$attrs:meta])*
+            $ty:ty,
+        )*
+    ) => {
+        $(
+            $(#[$attrs])*
+            impl<T: ?Sized + Stringable> Stringable for $ty {
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    T::size_hint(&**self)
+                }
+            }
+        )*
+    };
+}
+
+deref! {
+    &T,
+    &mut T,
+    #[cfg(feature = "alloc")]
+    alloc::boxed::Box<T>,
+    #[cfg(feature = "alloc")]
+    alloc::rc::Rc<T>,
+    #[cfg(not(valuable_no_atomic_cas))]
+    #[cfg(feature = "alloc")]
+    alloc::sync::Arc<T>,
+}
+
+// Writes each chunk of a `Stringable`, stopping at the first error.
+struct WriteChunks<'a, 'b> {
+    fmt: &'a mut fmt::Formatter<'b>,
+    debug: bool,
+    res: fmt::Result,
+}
+
+impl WriteChunks<'_, '_> {
+    fn write(&mut self, chunk: &str) -> fmt::Result {
+        if !self.debug {
+            return self.fmt.write_str(chunk);
+        }
+
+        for c in chunk.chars() {
+            // `str` does not escape single quotes.
+            if c == '\'' {
+                self.fmt.write_char(c)?;
+            } else {
+                for e in c.escape_debug() {
+                    self.fmt.write_char(e)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Visit for WriteChunks<'_, '_> {
+    fn visit_value(&mut self, _: Value<'_>) {}
+
+    fn visit_str_chunk(&mut self, chunk: &str) {
+        if self.res.is_ok() {
+            self.res = self.write(chunk);
+        }
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        match self.res {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        }
+    }
+}
+
+fn write_chunks(
+    stringable: &(dyn Stringable + '_),
+    fmt: &mut fmt::Formatter<'_>,
+    debug: bool,
+) -> fmt::Result {
+    let mut write = WriteChunks {
+        fmt,
+        debug,
+        res: Ok(()),
+    };
+    stringable.visit(&mut write);
+    write.res
+}
+
+impl fmt::Display for dyn Stringable + '_ {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_chunks(self, fmt, false)
+    }
+}
+
+impl fmt::Debug for dyn Stringable + '_ {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_char('"')?;
+        write_chunks(self, fmt, true)?;
+        fmt.write_char('"')
+    }
+}
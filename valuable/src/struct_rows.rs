@@ -0,0 +1,420 @@
+use crate::*;
+
+use core::fmt;
+use core::ops::ControlFlow;
+
+/// A list of structs sharing the same definition.
+///
+/// Lists of structs are usually visited one element at a time, each element
+/// being passed to [`Visit::visit_value()`] and then visiting its own fields.
+/// Visitors that handle many structs of the same type, such as table or CSV
+/// writers, can instead implement [`Visit::visit_struct_rows()`] to receive
+/// the whole list at once. The [`StructDef`] shared by all elements is
+/// available up front, and the elements are either visited as rows with
+/// [`visit_rows()`](StructRows::visit_rows), or accessed as columns of
+/// primitive [`Slice`]s with [`columns()`](StructRows::columns), when the
+/// list stores them that way.
+///
+/// `StructRows` values are passed to visitors by lists of structs:
+///
+/// * `#[derive(Valuable)]` structs pass slices of themselves, so that
+///   [`Vec`], arrays, slices, and other lists of derived structs are visited
+///   as rows. Use [`StructRows::visit_slice()`] to do the same for a
+///   `Structable` implemented by hand.
+/// * Lists storing their elements field by field are created with
+///   [`StructRows::from_columns()`].
+///
+/// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+///
+/// # Examples
+///
+/// Writing a list of structs as CSV:
+///
+/// ```
+/// use valuable::{Fields, NamedValues, StructRows, Valuable, Value, Visit};
+///
+/// #[derive(Valuable)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(Default)]
+/// struct Csv(String);
+///
+/// impl Visit for Csv {
+///     fn visit_value(&mut self, value: Value<'_>) {
+///         if let Value::Listable(v) = value {
+///             v.visit(self);
+///         }
+///     }
+///
+///     fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+///         if let Fields::Named(fields) = rows.definition().fields() {
+///             let names: Vec<_> = fields.iter().map(|f| f.name()).collect();
+///             self.0 = names.join(",") + "\n";
+///         }
+///         rows.visit_rows(self);
+///     }
+///
+///     fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+///         let values: Vec<_> = named_values
+///             .iter()
+///             .map(|(_, v)| format!("{:?}", v))
+///             .collect();
+///         self.0 += &(values.join(",") + "\n");
+///     }
+/// }
+///
+/// let points = vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+///
+/// let mut csv = Csv::default();
+/// valuable::visit(&points, &mut csv);
+///
+/// assert_eq!(csv.0, "x,y\n1,2\n3,4\n");
+/// ```
+pub struct StructRows<'a> {
+    definition: StructDef<'a>,
+    source: Source<'a>,
+}
+
+enum Source<'a> {
+    Rows(&'a dyn Rows),
+    Columns(&'a [Slice<'a>]),
+}
+
+// The elements of a slice of structs, with their type erased.
+trait Rows {
+    fn len(&self) -> usize;
+
+    fn for_each(&self, f: &mut dyn FnMut(&dyn Structable) -> ControlFlow<()>);
+}
+
+struct SliceRows<'a, T>(&'a [T]);
+
+impl<T: Structable> Rows for SliceRows<'_, T> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn for_each(&self, f: &mut dyn FnMut(&dyn Structable) -> ControlFlow<()>) {
+        for row in self.0 {
+            if let ControlFlow::Break(()) = f(row) {
+                break;
+            }
+        }
+    }
+}
+
+impl<'a> StructRows<'a> {
+    /// Visits a slice of structs as a [`StructRows`].
+    ///
+    /// This calls [`Visit::visit_struct_rows()`] with the elements of
+    /// `slice`, which must all have the same definition. Nothing is visited
+    /// if the slice is empty. Structs with a [dynamic](StructDef::Dynamic)
+    /// definition may not all have the same fields, so they are passed to
+    /// [`Visit::visit_value()`] one by one instead.
+    ///
+    /// `#[derive(Valuable)]` uses this function to implement
+    /// [`Valuable::visit_slice()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{
+    ///     Fields, NamedField, NamedValues, StructDef, StructRows, Structable, Valuable, Value,
+    ///     Visit,
+    /// };
+    ///
+    /// static FIELDS: &[NamedField<'static>] = &[NamedField::new("id")];
+    ///
+    /// struct User {
+    ///     id: u32,
+    /// }
+    ///
+    /// impl Valuable for User {
+    ///     fn as_value(&self) -> Value<'_> {
+    ///         Value::Structable(self)
+    ///     }
+    ///
+    ///     fn visit(&self, visit: &mut dyn Visit) {
+    ///         visit.visit_named_fields(&NamedValues::new(FIELDS, &[self.id.as_value()]));
+    ///     }
+    ///
+    ///     fn visit_slice(slice: &[Self], visit: &mut dyn Visit) {
+    ///         StructRows::visit_slice(slice, visit);
+    ///     }
+    /// }
+    ///
+    /// impl Structable for User {
+    ///     fn definition(&self) -> StructDef<'_> {
+    ///         StructDef::new_static("User", Fields::Named(FIELDS))
+    ///     }
+    /// }
+    ///
+    /// struct Count(usize);
+    ///
+    /// impl Visit for Count {
+    ///     fn visit_value(&mut self, _: Value<'_>) {}
+    ///
+    ///     fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+    ///         self.0 += rows.len();
+    ///     }
+    /// }
+    ///
+    /// let users = vec![User { id: 1 }, User { id: 2 }];
+    ///
+    /// let mut count = Count(0);
+    /// users.visit(&mut count);
+    /// assert_eq!(count.0, 2);
+    /// ```
+    pub fn visit_slice<T: Structable>(slice: &[T], visit: &mut dyn Visit) {
+        let definition = match slice.first() {
+            Some(first) => first.definition(),
+            None => return,
+        };
+
+        if definition.is_dynamic() {
+            for row in slice {
+                if let ControlFlow::Break(()) = visit.control_flow() {
+                    break;
+                }
+
+                visit.visit_value(Value::Structable(row));
+            }
+            return;
+        }
+
+        visit.visit_struct_rows(&StructRows {
+            definition,
+            source: Source::Rows(&SliceRows(slice)),
+        });
+    }
+
+    /// Creates a list of structs from columns of primitive values.
+    ///
+    /// `columns` holds one slice per field of `definition`, in the order of
+    /// the fields, and the `n`th struct of the list is made of the `n`th
+    /// element of each column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns is not the number of fields in
+    /// `definition`, or if the columns do not all have the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Fields, NamedField, Slice, StructDef, StructRows};
+    ///
+    /// static FIELDS: &[NamedField<'static>] = &[NamedField::new("x"), NamedField::new("y")];
+    ///
+    /// let xs = [1, 3];
+    /// let ys = [2, 4];
+    /// let columns = [Slice::I32(&xs), Slice::I32(&ys)];
+    ///
+    /// let rows = StructRows::from_columns(
+    ///     StructDef::new_static("Point", Fields::Named(FIELDS)),
+    ///     &columns,
+    /// );
+    ///
+    /// assert_eq!(rows.len(), 2);
+    /// assert_eq!(
+    ///     format!("{:?}", rows),
+    ///     "[Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]",
+    /// );
+    /// ```
+    pub fn from_columns(definition: StructDef<'a>, columns: &'a [Slice<'a>]) -> StructRows<'a> {
+        let fields = match definition.fields() {
+            Fields::Named(fields) => fields.len(),
+            Fields::Unnamed(len) => *len,
+        };
+        assert_eq!(
+            columns.len(),
+            fields,
+            "expected one column per field of `{}`",
+            definition.name()
+        );
+
+        let len = columns.first().map_or(0, Slice::len);
+        assert!(
+            columns.iter().all(|column| column.len() == len),
+            "columns have different lengths"
+        );
+
+        StructRows {
+            definition,
+            source: Source::Columns(columns),
+        }
+    }
+
+    /// Returns the definition shared by all structs in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Fields, Slice, StructDef, StructRows};
+    ///
+    /// let rows = StructRows::from_columns(
+    ///     StructDef::new_static("Pair", Fields::Unnamed(2)),
+    ///     &[Slice::U8(&[1]), Slice::Str(&["a"])],
+    /// );
+    /// assert_eq!(rows.definition().name(), "Pair");
+    /// ```
+    pub fn definition(&self) -> &StructDef<'a> {
+        &self.definition
+    }
+
+    /// Returns the number of structs in the list.
+    pub fn len(&self) -> usize {
+        match self.source {
+            Source::Rows(rows) => rows.len(),
+            Source::Columns(columns) => columns.first().map_or(0, Slice::len),
+        }
+    }
+
+    /// Returns `true` if the list contains no structs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the columns of the list, if it stores its structs field by
+    /// field.
+    ///
+    /// Lists created with [`StructRows::from_columns()`] return their
+    /// columns, and other lists return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Fields, Slice, StructDef, StructRows};
+    ///
+    /// let rows = StructRows::from_columns(
+    ///     StructDef::new_static("Pair", Fields::Unnamed(2)),
+    ///     &[Slice::U8(&[1, 2]), Slice::Str(&["a", "b"])],
+    /// );
+    ///
+    /// match rows.columns() {
+    ///     Some([Slice::U8(numbers), Slice::Str(strings)]) => {
+    ///         assert_eq!(numbers, &[1, 2]);
+    ///         assert_eq!(strings, &["a", "b"]);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn columns(&self) -> Option<&'a [Slice<'a>]> {
+        match self.source {
+            Source::Rows(_) => None,
+            Source::Columns(columns) => Some(columns),
+        }
+    }
+
+    /// Visits the fields of each struct in the list.
+    ///
+    /// Each struct is visited the same way as with [`Valuable::visit()`],
+    /// passing its fields to [`Visit::visit_named_fields()`] or
+    /// [`Visit::visit_unnamed_fields()`], possibly in several calls. The
+    /// structs themselves are not passed to [`Visit::visit_value()`].
+    ///
+    /// See the [type documentation](StructRows) for an example.
+    pub fn visit_rows(&self, visit: &mut dyn Visit) {
+        self.for_each_row(|row| {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                return ControlFlow::Break(());
+            }
+
+            row.visit(visit);
+            ControlFlow::Continue(())
+        });
+    }
+
+    // Calls `f` with each struct in the list, until it breaks.
+    pub(crate) fn for_each_row(&self, mut f: impl FnMut(&dyn Structable) -> ControlFlow<()>) {
+        match self.source {
+            Source::Rows(rows) => rows.for_each(&mut f),
+            Source::Columns(columns) => {
+                for index in 0..self.len() {
+                    let row = ColumnRow {
+                        definition: &self.definition,
+                        columns,
+                        index,
+                    };
+                    if let ControlFlow::Break(()) = f(&row) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for StructRows<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = fmt.debug_list();
+        self.for_each_row(|row| {
+            list.entry(&row);
+            ControlFlow::Continue(())
+        });
+        list.finish()
+    }
+}
+
+// The number of fields passed to the visitor at once when visiting a row
+// stored in columns.
+const CHUNK_LEN: usize = 16;
+
+// A struct stored in columns.
+struct ColumnRow<'a, 'b> {
+    definition: &'a StructDef<'b>,
+    columns: &'b [Slice<'b>],
+    index: usize,
+}
+
+impl Valuable for ColumnRow<'_, '_> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let mut start = 0;
+        // Fields are passed in chunks, so that rows can be visited without
+        // allocating. Structs without fields are still visited once. As in
+        // derived code, `control_flow` is checked between chunks.
+        loop {
+            let end = self.columns.len().min(start + CHUNK_LEN);
+            let mut values = [Value::Unit; CHUNK_LEN];
+            for (value, column) in values.iter_mut().zip(&self.columns[start..end]) {
+                *value = column.get(self.index).unwrap_or(Value::Unit);
+            }
+            let values = &values[..end - start];
+
+            match self.definition.fields() {
+                Fields::Named(fields) => {
+                    visit.visit_named_fields(&NamedValues::new(&fields[start..end], values));
+                }
+                Fields::Unnamed(_) => visit.visit_unnamed_fields(values),
+            }
+
+            start = end;
+            if start == self.columns.len() {
+                break;
+            }
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+        }
+    }
+}
+
+impl Structable for ColumnRow<'_, '_> {
+    fn definition(&self) -> StructDef<'_> {
+        match *self.definition {
+            StructDef::Static { name, fields, .. } => StructDef::new_static(name, fields),
+            StructDef::Dynamic { name, fields, .. } => StructDef::new_dynamic(name, fields),
+        }
+    }
+
+    fn field_at(&self, index: usize) -> Option<Value<'_>> {
+        self.columns.get(index)?.get(self.index)
+    }
+}
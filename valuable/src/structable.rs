@@ -1,7 +1,10 @@
 use crate::field::*;
+use crate::value::detach;
 use crate::*;
 
+use core::any::Any;
 use core::fmt;
+use core::ops::ControlFlow;
 
 /// A struct-like [`Valuable`] sub-type.
 ///
@@ -109,6 +112,174 @@ pub trait Structable: Valuable {
     ///
     /// assert_eq!("MyStruct", my_struct.definition().name());
     fn definition(&self) -> StructDef<'_>;
+
+    /// Returns the value of the named field `name`.
+    ///
+    /// Returns `None` if the struct has no field named `name`, or if its
+    /// fields are unnamed.
+    ///
+    /// The default implementation visits the struct to find the field. Values
+    /// passed to a visitor cannot outlive the call to [`visit()`], so the
+    /// default implementation is only able to return values that do not
+    /// borrow from the struct: booleans, characters, numbers up to 64 bits,
+    /// and [`Value::Unit`]. For any other field, including 128-bit integers,
+    /// which are passed by reference, it returns `None`. Implementations
+    /// that can borrow their fields directly should override this method;
+    /// `#[derive(Valuable)]` does so, and finds the field without visiting
+    /// the struct.
+    ///
+    /// [`visit()`]: Valuable::visit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Structable, Valuable, Value};
+    ///
+    /// #[derive(Valuable)]
+    /// struct MyStruct {
+    ///     foo: u32,
+    ///     bar: &'static str,
+    /// }
+    ///
+    /// let my_struct = MyStruct { foo: 123, bar: "hello" };
+    /// let structable: &dyn Structable = &my_struct;
+    ///
+    /// assert!(matches!(structable.field("foo"), Some(Value::U32(123))));
+    /// assert!(matches!(structable.field("bar"), Some(Value::String("hello"))));
+    /// assert!(structable.field("baz").is_none());
+    /// ```
+    fn field(&self, name: &str) -> Option<Value<'_>> {
+        struct FindField<'a> {
+            name: &'a str,
+            found: Option<Option<Value<'static>>>,
+        }
+
+        impl Visit for FindField<'_> {
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                if self.found.is_some() {
+                    return;
+                }
+                if let Some(value) = named_values.get_by_name(self.name) {
+                    self.found = Some(detach(value));
+                }
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                match self.found {
+                    Some(_) => ControlFlow::Break(()),
+                    None => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        let mut visit = FindField { name, found: None };
+        self.visit(&mut visit);
+        visit.found.flatten()
+    }
+
+    /// Returns the value of the field at position `index`.
+    ///
+    /// Fields are counted in the order they are visited, for both named and
+    /// unnamed fields. For a statically defined struct with named fields,
+    /// this is the position of the field in [`Fields::Named`]. Returns `None`
+    /// if `index` is out of bounds.
+    ///
+    /// The default implementation visits the struct, and has the same
+    /// limitation as the default implementation of [`field()`]: it only
+    /// returns values that do not borrow from the struct.
+    /// `#[derive(Valuable)]` overrides this method.
+    ///
+    /// [`field()`]: Structable::field
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Structable, Valuable, Value};
+    ///
+    /// #[derive(Valuable)]
+    /// struct Point(i32, i32);
+    ///
+    /// let point = Point(1, 2);
+    ///
+    /// assert!(matches!(point.field_at(1), Some(Value::I32(2))));
+    /// assert!(point.field_at(2).is_none());
+    /// ```
+    fn field_at(&self, index: usize) -> Option<Value<'_>> {
+        struct FindFieldAt {
+            remaining: usize,
+            found: Option<Option<Value<'static>>>,
+        }
+
+        impl FindFieldAt {
+            fn find(&mut self, value: Option<&Value<'_>>, len: usize) {
+                if self.found.is_some() {
+                    return;
+                }
+                match value {
+                    Some(value) => self.found = Some(detach(value)),
+                    None => self.remaining -= len,
+                }
+            }
+        }
+
+        impl Visit for FindFieldAt {
+            fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+                let value = named_values.iter().nth(self.remaining);
+                self.find(value.map(|(_, value)| value), named_values.len());
+            }
+
+            fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+                self.find(values.get(self.remaining), values.len());
+            }
+
+            fn visit_value(&mut self, _: Value<'_>) {}
+
+            fn control_flow(&self) -> ControlFlow<()> {
+                match self.found {
+                    Some(_) => ControlFlow::Break(()),
+                    None => ControlFlow::Continue(()),
+                }
+            }
+        }
+
+        let mut visit = FindFieldAt {
+            remaining: index,
+            found: None,
+        };
+        self.visit(&mut visit);
+        visit.found.flatten()
+    }
+
+    /// Returns `self` as [`Any`], if the type opts in to downcasting.
+    ///
+    /// A `&dyn Structable` does not tell which concrete type is behind it.
+    /// Types that return `Some(self)` here can be recovered with
+    /// [`Value::downcast_ref()`], for example to handle a few known types in
+    /// a more efficient way.
+    ///
+    /// The default implementation returns `None`. `#[derive(Valuable)]`
+    /// overrides this method for types without generic parameters. Other
+    /// `'static` types may override it to return `Some(self)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Structable, Valuable};
+    ///
+    /// #[derive(Valuable)]
+    /// struct RequestId(u64);
+    ///
+    /// let id = RequestId(7);
+    /// let structable: &dyn Structable = &id;
+    ///
+    /// let any = structable.as_any().unwrap();
+    /// assert_eq!(any.downcast_ref::<RequestId>().unwrap().0, 7);
+    /// ```
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 /// A struct's name, fields, and other struct-level information.
@@ -478,6 +649,18 @@ macro_rules! deref {
                 fn definition(&self) -> StructDef<'_> {
                     T::definition(&**self)
                 }
+
+                fn field(&self, name: &str) -> Option<Value<'_>> {
+                    T::field(&**self, name)
+                }
+
+                fn field_at(&self, index: usize) -> Option<Value<'_>> {
+                    T::field_at(&**self, index)
+                }
+
+                fn as_any(&self) -> Option<&dyn Any> {
+                    T::as_any(&**self)
+                }
             }
         )*
     };
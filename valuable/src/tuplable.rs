@@ -1,5 +1,6 @@
 use crate::{Valuable, Value, Visit};
 
+use core::any::Any;
 use core::fmt;
 
 /// A tuple-like [`Valuable`] sub-type.
@@ -35,6 +36,16 @@ pub trait Tuplable: Valuable {
     /// }
     /// ```
     fn definition(&self) -> TupleDef;
+
+    /// Returns `self` as [`Any`], if the type opts in to downcasting.
+    ///
+    /// The default implementation returns `None`. See
+    /// [`Structable::as_any()`] for details.
+    ///
+    /// [`Structable::as_any()`]: crate::Structable::as_any
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 /// The number of fields and other tuple-level information.
@@ -121,6 +132,10 @@ macro_rules! deref {
                 fn definition(&self) -> TupleDef {
                     T::definition(&**self)
                 }
+
+                fn as_any(&self) -> Option<&dyn Any> {
+                    T::as_any(&**self)
+                }
             }
         )*
     };
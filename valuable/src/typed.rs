@@ -0,0 +1,300 @@
+//! Visiting values with one method per type.
+//!
+//! A [`Visit`] implementation receives every value through
+//! [`visit_value`](Visit::visit_value), and usually starts by matching on the
+//! [`Value`] variants. A [`TypedVisit`] implementation has one method per kind
+//! of value instead, such as [`visit_i64`](TypedVisit::visit_i64) or
+//! [`visit_str`](TypedVisit::visit_str), and only overrides the methods for
+//! the values it cares about. The [`Typed`] adapter turns it into a [`Visit`]
+//! implementation, which passes each value, including struct fields, tuple
+//! and list elements, and map keys and values, to the matching method.
+//!
+//! Integers are widened to 64 bits, so that a visitor interested in numbers
+//! does not need to handle each width: all signed integers up to 64 bits are
+//! passed to [`visit_i64`](TypedVisit::visit_i64), and `f32` values to
+//! [`visit_f64`](TypedVisit::visit_f64). 128-bit integers are passed to
+//! [`visit_i64`](TypedVisit::visit_i64) and
+//! [`visit_u64`](TypedVisit::visit_u64) too, if they fit. Values that the
+//! visitor does not handle end up in [`visit_other`](TypedVisit::visit_other),
+//! which ignores them by default.
+//!
+//! # Examples
+//!
+//! A visitor that sums the numbers and concatenates the strings of a struct:
+//!
+//! ```
+//! use valuable::typed::{Typed, TypedVisit};
+//! use valuable::Valuable;
+//!
+//! #[derive(Valuable)]
+//! struct Order {
+//!     id: &'static str,
+//!     quantity: u8,
+//!     price: i64,
+//!     paid: bool,
+//! }
+//!
+//! #[derive(Default)]
+//! struct Sink {
+//!     sum: i64,
+//!     strings: String,
+//! }
+//!
+//! impl TypedVisit for Sink {
+//!     fn visit_i64(&mut self, v: i64) {
+//!         self.sum += v;
+//!     }
+//!
+//!     fn visit_u64(&mut self, v: u64) {
+//!         self.sum += v as i64;
+//!     }
+//!
+//!     fn visit_str(&mut self, v: &str) {
+//!         self.strings.push_str(v);
+//!     }
+//! }
+//!
+//! let order = Order {
+//!     id: "A-1",
+//!     quantity: 2,
+//!     price: 40,
+//!     paid: true,
+//! };
+//!
+//! let mut sink = Sink::default();
+//! order.visit(&mut Typed::new(&mut sink));
+//!
+//! assert_eq!(sink.sum, 42);
+//! assert_eq!(sink.strings, "A-1");
+//! ```
+
+use crate::*;
+
+use core::ops::ControlFlow;
+
+/// A [`Visit`] adapter that passes values to a [`TypedVisit`]
+/// implementation.
+///
+/// Values passed to [`visit_value`](Visit::visit_value), struct and enum
+/// fields, primitive slice elements, and map keys and values are each passed
+/// to the [`TypedVisit`] method matching their type.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct Typed<'a, V: ?Sized> {
+    visit: &'a mut V,
+}
+
+impl<'a, V: TypedVisit + ?Sized> Typed<'a, V> {
+    /// Wraps a typed visitor.
+    pub fn new(visit: &'a mut V) -> Typed<'a, V> {
+        Typed { visit }
+    }
+
+    fn dispatch(&mut self, value: Value<'_>) {
+        let visit = &mut *self.visit;
+        match value {
+            Value::Bool(v) => visit.visit_bool(v),
+            Value::Char(v) => visit.visit_char(v),
+            Value::F32(v) => visit.visit_f64(v.into()),
+            Value::F64(v) => visit.visit_f64(v),
+            Value::I8(v) => visit.visit_i64(v.into()),
+            Value::I16(v) => visit.visit_i64(v.into()),
+            Value::I32(v) => visit.visit_i64(v.into()),
+            Value::I64(v) => visit.visit_i64(v),
+            Value::I128(&v) => visit.visit_i128(v),
+            Value::Isize(v) => visit.visit_i64(v as i64),
+            Value::String(v) => visit.visit_str(v),
+            Value::U8(v) => visit.visit_u64(v.into()),
+            Value::U16(v) => visit.visit_u64(v.into()),
+            Value::U32(v) => visit.visit_u64(v.into()),
+            Value::U64(v) => visit.visit_u64(v),
+            Value::U128(&v) => visit.visit_u128(v),
+            Value::Usize(v) => visit.visit_u64(v as u64),
+            Value::Bytes(v) => visit.visit_bytes(v),
+            Value::Listable(v) => visit.visit_list(v),
+            Value::Mappable(v) => visit.visit_map(v),
+            Value::Structable(v) => visit.visit_struct(v),
+            Value::Enumerable(v) => visit.visit_enum(v),
+            Value::Tuplable(v) => visit.visit_tuple(v),
+            Value::Unit => visit.visit_unit(),
+            value => visit.visit_other(value),
+        }
+    }
+
+    fn dispatch_all<'b>(&mut self, values: impl IntoIterator<Item = Value<'b>>) {
+        for value in values {
+            if let ControlFlow::Break(()) = self.visit.control_flow() {
+                break;
+            }
+            self.dispatch(value);
+        }
+    }
+}
+
+impl<V: TypedVisit + ?Sized> Visit for Typed<'_, V> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        self.dispatch(value);
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        self.dispatch_all(named_values.iter().map(|(_, value)| *value));
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        self.dispatch_all(values.iter().copied());
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        self.dispatch_all(slice);
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        self.dispatch_all([key, value]);
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        self.visit.control_flow()
+    }
+}
+
+/// A visitor with one method per type of value.
+///
+/// All methods have default implementations, which pass the value on to
+/// [`visit_other`](TypedVisit::visit_other). Use the [`Typed`] adapter to
+/// visit values with a `TypedVisit` implementation.
+///
+/// The methods for structs, enums, tuples, lists, and maps receive the
+/// container itself. To descend into it, visit it with `Typed::new(self)`.
+///
+/// See the [module documentation](self) for an example.
+pub trait TypedVisit {
+    /// Visits a value without a more specific method, or whose method is not
+    /// implemented.
+    ///
+    /// This includes [`Value::Display`], [`Value::Debug`],
+    /// [`Value::Stringable`], paths, and errors. The chunks of a
+    /// [`Value::Stringable`] are passed to [`visit_str`](TypedVisit::visit_str)
+    /// when it is visited with `Typed::new(self)`.
+    /// The default implementation ignores the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::typed::{Typed, TypedVisit};
+    /// use valuable::{Valuable, Value};
+    ///
+    /// struct Unhandled(Vec<String>);
+    ///
+    /// impl TypedVisit for Unhandled {
+    ///     fn visit_i64(&mut self, _: i64) {}
+    ///
+    ///     fn visit_other(&mut self, value: Value<'_>) {
+    ///         self.0.push(format!("{:?}", value));
+    ///     }
+    /// }
+    ///
+    /// let mut unhandled = Unhandled(vec![]);
+    /// (1, true, "a").visit(&mut Typed::new(&mut unhandled));
+    ///
+    /// assert_eq!(unhandled.0, ["true", r#""a""#]);
+    /// ```
+    fn visit_other(&mut self, value: Value<'_>) {
+        let _ = value;
+    }
+
+    /// Visits a `bool` value.
+    fn visit_bool(&mut self, v: bool) {
+        self.visit_other(Value::Bool(v));
+    }
+
+    /// Visits a `char` value.
+    fn visit_char(&mut self, v: char) {
+        self.visit_other(Value::Char(v));
+    }
+
+    /// Visits a signed integer of up to 64 bits.
+    fn visit_i64(&mut self, v: i64) {
+        self.visit_other(Value::I64(v));
+    }
+
+    /// Visits an `i128` value.
+    ///
+    /// The default implementation calls
+    /// [`visit_i64`](TypedVisit::visit_i64) if the value fits in an `i64`.
+    fn visit_i128(&mut self, v: i128) {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => self.visit_other(Value::I128(&v)),
+        }
+    }
+
+    /// Visits an unsigned integer of up to 64 bits.
+    fn visit_u64(&mut self, v: u64) {
+        self.visit_other(Value::U64(v));
+    }
+
+    /// Visits a `u128` value.
+    ///
+    /// The default implementation calls
+    /// [`visit_u64`](TypedVisit::visit_u64) if the value fits in a `u64`.
+    fn visit_u128(&mut self, v: u128) {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => self.visit_other(Value::U128(&v)),
+        }
+    }
+
+    /// Visits an `f32` or `f64` value.
+    fn visit_f64(&mut self, v: f64) {
+        self.visit_other(Value::F64(v));
+    }
+
+    /// Visits a string.
+    fn visit_str(&mut self, v: &str) {
+        self.visit_other(Value::String(v));
+    }
+
+    /// Visits a [`Value::Bytes`] value.
+    fn visit_bytes(&mut self, v: &[u8]) {
+        self.visit_other(Value::Bytes(v));
+    }
+
+    /// Visits a [`Value::Unit`] value, such as `None`.
+    fn visit_unit(&mut self) {
+        self.visit_other(Value::Unit);
+    }
+
+    /// Visits a list.
+    fn visit_list(&mut self, v: &dyn Listable) {
+        self.visit_other(Value::Listable(v));
+    }
+
+    /// Visits a map.
+    fn visit_map(&mut self, v: &dyn Mappable) {
+        self.visit_other(Value::Mappable(v));
+    }
+
+    /// Visits a struct.
+    fn visit_struct(&mut self, v: &dyn Structable) {
+        self.visit_other(Value::Structable(v));
+    }
+
+    /// Visits an enum.
+    fn visit_enum(&mut self, v: &dyn Enumerable) {
+        self.visit_other(Value::Enumerable(v));
+    }
+
+    /// Visits a tuple.
+    fn visit_tuple(&mut self, v: &dyn Tuplable) {
+        self.visit_other(Value::Tuplable(v));
+    }
+
+    /// Returns whether the traversal should continue.
+    ///
+    /// [`Typed`] checks it between fields and elements, and forwards it as
+    /// [`Visit::control_flow`]. The default implementation always continues.
+    fn control_flow(&self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
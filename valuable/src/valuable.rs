@@ -2,6 +2,7 @@ use crate::{Slice, Value, Visit};
 
 use core::fmt;
 use core::num::Wrapping;
+use core::ops::ControlFlow;
 
 /// A type that can be converted to a [`Value`].
 ///
@@ -67,9 +68,46 @@ pub trait Valuable {
         Self: Sized,
     {
         for item in slice {
+            if let ControlFlow::Break(()) = visit.control_flow() {
+                break;
+            }
+
             visit.visit_value(item.as_value());
         }
     }
+
+    /// Returns `slice` as a primitive [`Slice`], or `None` if `Self` is not
+    /// a primitive type.
+    ///
+    /// This method is an implementation detail used to implement
+    /// [`Listable::as_slice()`].
+    fn as_primitive_slice(slice: &[Self]) -> Option<Slice<'_>>
+    where
+        Self: Sized,
+    {
+        let _ = slice;
+        None
+    }
+
+    /// Looks up `key` in `map`, or returns `None` if `key` cannot be
+    /// converted to `Self`.
+    ///
+    /// This method is an implementation detail used to implement
+    /// [`Mappable::get()`] for `BTreeMap` without scanning its entries.
+    ///
+    /// [`Mappable::get()`]: crate::Mappable::get
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    fn lookup<'a, V>(
+        map: &'a alloc::collections::BTreeMap<Self, V>,
+        key: &Value<'_>,
+    ) -> Option<Option<&'a V>>
+    where
+        Self: Sized,
+    {
+        let _ = (map, key);
+        None
+    }
 }
 
 macro_rules! deref {
@@ -107,28 +145,65 @@ deref! {
 }
 
 macro_rules! valuable {
+    (@impl $variant:ident($ty:ty), $($deref:tt)?) => {
+        impl Valuable for $ty {
+            fn as_value(&self) -> Value<'_> {
+                Value::$variant($($deref)? self)
+            }
+
+            fn visit(&self, visit: &mut dyn Visit) {
+                visit.visit_value(self.as_value());
+            }
+
+            fn visit_slice(slice: &[Self], visit: &mut dyn Visit)
+            where
+                Self: Sized,
+            {
+                visit.visit_primitive_slice(Slice::$variant(slice));
+            }
+
+            fn as_primitive_slice(slice: &[Self]) -> Option<Slice<'_>>
+            where
+                Self: Sized,
+            {
+                Some(Slice::$variant(slice))
+            }
+
+            valuable!(@lookup $variant);
+        }
+    };
+    // Floats do not implement `Ord`, so they cannot be looked up.
+    (@lookup F32) => {};
+    (@lookup F64) => {};
+    (@lookup $variant:ident) => {
+        #[cfg(feature = "alloc")]
+        fn lookup<'a, V>(
+            map: &'a alloc::collections::BTreeMap<Self, V>,
+            key: &Value<'_>,
+        ) -> Option<Option<&'a V>> {
+            match *key {
+                // 128-bit integers are held by reference.
+                Value::$variant(ref key) => Some(map.get(core::borrow::Borrow::<Self>::borrow(key))),
+                _ => None,
+            }
+        }
+    };
+    (
+        $(
+            $variant:ident(&$ty:ty),
+        )*
+    ) => {
+        $(
+            valuable!(@impl $variant($ty),);
+        )*
+    };
     (
         $(
             $variant:ident($ty:ty),
         )*
     ) => {
         $(
-            impl Valuable for $ty {
-                fn as_value(&self) -> Value<'_> {
-                    Value::$variant(*self)
-                }
-
-                fn visit(&self, visit: &mut dyn Visit) {
-                    visit.visit_value(self.as_value());
-                }
-
-                fn visit_slice(slice: &[Self], visit: &mut dyn Visit)
-                where
-                    Self: Sized,
-                {
-                    visit.visit_primitive_slice(Slice::$variant(slice));
-                }
-            }
+            valuable!(@impl $variant($ty), *);
         )*
     };
 }
@@ -142,16 +217,20 @@ valuable! {
     I16(i16),
     I32(i32),
     I64(i64),
-    I128(i128),
     Isize(isize),
     U8(u8),
     U16(u16),
     U32(u32),
     U64(u64),
-    U128(u128),
     Usize(usize),
 }
 
+// 128-bit integers are passed by reference.
+valuable! {
+    I128(&i128),
+    U128(&u128),
+}
+
 macro_rules! nonzero {
     (
         $(
@@ -177,16 +256,46 @@ nonzero! {
     I16(NonZeroI16),
     I32(NonZeroI32),
     I64(NonZeroI64),
-    I128(NonZeroI128),
     Isize(NonZeroIsize),
     U8(NonZeroU8),
     U16(NonZeroU16),
     U32(NonZeroU32),
     U64(NonZeroU64),
-    U128(NonZeroU128),
     Usize(NonZeroUsize),
 }
 
+impl Valuable for core::num::NonZeroI128 {
+    // There is no safe way to borrow the integer inside a `NonZeroI128`.
+    #[allow(unsafe_code)]
+    fn as_value(&self) -> Value<'_> {
+        // SAFETY: the standard library documents that `NonZeroI128` is
+        // guaranteed to have the same layout and bit validity as `i128`, with
+        // the exception that `0` is not a valid instance. Every `NonZeroI128`
+        // is therefore a valid `i128`, and the reference is only read.
+        Value::I128(unsafe { &*(self as *const core::num::NonZeroI128 as *const i128) })
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+impl Valuable for core::num::NonZeroU128 {
+    // There is no safe way to borrow the integer inside a `NonZeroU128`.
+    #[allow(unsafe_code)]
+    fn as_value(&self) -> Value<'_> {
+        // SAFETY: the standard library documents that `NonZeroU128` is
+        // guaranteed to have the same layout and bit validity as `u128`, with
+        // the exception that `0` is not a valid instance. Every `NonZeroU128`
+        // is therefore a valid `u128`, and the reference is only read.
+        Value::U128(unsafe { &*(self as *const core::num::NonZeroU128 as *const u128) })
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
 #[cfg(not(valuable_no_atomic))]
 macro_rules! atomic {
     (
@@ -278,6 +387,24 @@ impl Valuable for &'_ str {
     {
         visit.visit_primitive_slice(Slice::Str(slice));
     }
+
+    fn as_primitive_slice(slice: &[Self]) -> Option<Slice<'_>>
+    where
+        Self: Sized,
+    {
+        Some(Slice::Str(slice))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn lookup<'a, V>(
+        map: &'a alloc::collections::BTreeMap<Self, V>,
+        key: &Value<'_>,
+    ) -> Option<Option<&'a V>> {
+        match key {
+            Value::String(key) => Some(map.get(*key)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -296,6 +423,24 @@ impl Valuable for alloc::string::String {
     {
         visit.visit_primitive_slice(Slice::String(slice));
     }
+
+    fn as_primitive_slice(slice: &[Self]) -> Option<Slice<'_>>
+    where
+        Self: Sized,
+    {
+        Some(Slice::String(slice))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn lookup<'a, V>(
+        map: &'a alloc::collections::BTreeMap<Self, V>,
+        key: &Value<'_>,
+    ) -> Option<Option<&'a V>> {
+        match key {
+            Value::String(key) => Some(map.get(*key)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -320,10 +465,9 @@ impl Valuable for std::path::PathBuf {
     }
 }
 
-#[cfg(feature = "std")]
-impl Valuable for dyn std::error::Error + 'static {
+impl Valuable for fmt::Arguments<'_> {
     fn as_value(&self) -> Value<'_> {
-        Value::Error(self)
+        Value::Display(self)
     }
 
     fn visit(&self, visit: &mut dyn Visit) {
@@ -331,6 +475,64 @@ impl Valuable for dyn std::error::Error + 'static {
     }
 }
 
+#[cfg(feature = "std")]
+macro_rules! display {
+    (
+        $(
+            $ty:ident,
+        )*
+    ) => {
+        $(
+            impl Valuable for std::net::$ty {
+                fn as_value(&self) -> Value<'_> {
+                    Value::Display(self)
+                }
+
+                fn visit(&self, visit: &mut dyn Visit) {
+                    visit.visit_value(self.as_value());
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "std")]
+display! {
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+}
+
+macro_rules! error {
+    (
+        $(
+            $ty:ty,
+        )*
+    ) => {
+        $(
+            #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+            impl Valuable for $ty {
+                fn as_value(&self) -> Value<'_> {
+                    Value::Error(self)
+                }
+
+                fn visit(&self, visit: &mut dyn Visit) {
+                    visit.visit_value(self.as_value());
+                }
+            }
+        )*
+    };
+}
+
+error! {
+    dyn crate::Error + 'static,
+    dyn crate::Error + Send + 'static,
+    dyn crate::Error + Send + Sync + 'static,
+}
+
 impl fmt::Debug for dyn Valuable + '_ {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = self.as_value();
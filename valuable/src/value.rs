@@ -1,5 +1,9 @@
-use crate::{Enumerable, Listable, Mappable, Structable, Tuplable, Valuable, Visit};
+use crate::{
+    Enumerable, Listable, Mappable, Number, Stringable, Structable, TryFromValueError, Tuplable,
+    Valuable, Visit,
+};
 
+use core::any::Any;
 use core::fmt;
 
 macro_rules! value {
@@ -56,7 +60,50 @@ macro_rules! value {
         /// );
         /// ```
         ///
+        /// # Comparison
+        ///
+        /// With the `alloc` feature enabled, `Value` implements [`Eq`], [`Ord`],
+        /// and [`Hash`] by comparing the values structurally, recursing into
+        /// nested structs, enums, lists, maps, and tuples:
+        ///
+        /// * Values of different variants are never equal, including numbers
+        ///   of different types. String-like values are the exception, see
+        ///   below. Values of different kinds are ordered as follows:
+        ///   booleans, characters, `f32`, `f64`, signed integers from `i8` to
+        ///   `isize`, strings and string-like values, unsigned integers from
+        ///   `u8` to `usize`, paths, errors, lists, maps, structs, enums,
+        ///   tuples, bytes, and finally [`Value::Unit`].
+        /// * [`Value::String`], [`Value::Stringable`], [`Value::Display`], and
+        ///   [`Value::Debug`] are compared as the string they format to, the
+        ///   same way [`OwnedValue::capture`](crate::OwnedValue::capture)
+        ///   captures them. `Value::Display(&1)` is equal to
+        ///   `Value::String("1")`, and both have the same hash.
+        /// * Floats use a total order: `-0.0` and `0.0` are equal, and all NaNs
+        ///   are equal to each other and greater than any other number.
+        /// * Lists and tuples compare their elements in order.
+        /// * Maps compare their entries sorted by key, so iteration order does
+        ///   not matter.
+        /// * Structs and enums compare their names, the variant name, then the
+        ///   fields, including field names. Whether a type is statically or
+        ///   dynamically defined does not matter.
+        ///
+        /// Nested values are compared in place, stopping at the first
+        /// difference. Maps, formatted values, and errors are captured as
+        /// [`OwnedValue`](crate::OwnedValue)s to compare or hash them, which
+        /// allocates.
+        ///
+        /// ```
+        /// use valuable::{Valuable, Value};
+        ///
+        /// assert_eq!(vec![1, 2].as_value(), [1, 2].as_value());
+        /// assert_eq!(Value::F64(f64::NAN), Value::F64(f64::NAN));
+        /// assert_ne!(Value::I32(1), Value::I64(1));
+        /// assert!(Value::F64(1.0) < Value::F64(f64::NAN));
+        /// assert_eq!(Value::Display(&1), Value::String("1"));
+        /// ```
+        ///
         /// [visitor]: Visit
+        /// [`Hash`]: core::hash::Hash
         #[non_exhaustive]
         #[derive(Clone, Copy)]
         pub enum Value<'a> {
@@ -65,6 +112,64 @@ macro_rules! value {
                 $variant($ty),
             )*
 
+            /// A Rust byte slice value
+            ///
+            /// Binary data such as hashes or packet buffers. Unlike
+            /// [`Value::Listable`], which is used for `&[u8]` and `Vec<u8>` by
+            /// default, this variant tells the visitor to treat the data as
+            /// an opaque byte string. Types opt in using the [`Bytes`]
+            /// wrapper, or the `#[valuable(bytes)]` derive attribute.
+            /// Converting a `&[u8]` with [`Value::from`] also produces this
+            /// variant.
+            ///
+            /// [`Bytes`]: crate::Bytes
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            ///
+            /// let v = Value::Bytes(b"hello");
+            /// assert_eq!(format!("{:?}", v), r#"b"hello""#);
+            /// ```
+            Bytes(&'a [u8]),
+
+            /// A value formatted with its `Display` implementation
+            ///
+            /// Types such as IDs and addresses are best represented by their
+            /// `Display` output. This variant lets the visitor format them
+            /// directly into its output, without allocating an intermediate
+            /// `String`. [`fmt::Arguments`] uses this variant.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            /// use std::net::Ipv4Addr;
+            ///
+            /// let addr = Ipv4Addr::LOCALHOST;
+            /// let v = Value::Display(&addr);
+            /// assert_eq!(format!("{:?}", v), "127.0.0.1");
+            /// ```
+            Display(&'a dyn fmt::Display),
+
+            /// A value formatted with its `Debug` implementation
+            ///
+            /// Like [`Value::Display`], for types which only implement
+            /// `Debug`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            /// use std::time::Duration;
+            ///
+            /// let timeout = Duration::from_millis(1500);
+            /// let v = Value::Debug(&timeout);
+            /// assert_eq!(format!("{:?}", v), "1.5s");
+            /// ```
+            Debug(&'a dyn fmt::Debug),
+
             /// A Rust `()` or `None` value.
             ///
             /// # Examples
@@ -86,6 +191,32 @@ macro_rules! value {
             }
         )*
 
+        /// Converts a byte slice to a [`Value::Bytes`].
+        ///
+        /// This differs from `<[u8]>::as_value()`, which returns a
+        /// [`Value::Listable`] of `u8` values: a [`Valuable`] implementation
+        /// cannot tell binary data from a list of small integers, so slices
+        /// keep the list representation unless they opt in with the
+        /// [`Bytes`](crate::Bytes) wrapper. A conversion from `&[u8]` is an
+        /// explicit request for binary data, and has no such ambiguity.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use valuable::{Bytes, Valuable, Value};
+        ///
+        /// let data: &[u8] = b"\x01\x02";
+        ///
+        /// assert!(matches!(Value::from(data), Value::Bytes(_)));
+        /// assert!(matches!(Bytes(data).as_value(), Value::Bytes(_)));
+        /// assert!(matches!(data.as_value(), Value::Listable(_)));
+        /// ```
+        impl<'a> From<&'a [u8]> for Value<'a> {
+            fn from(src: &'a [u8]) -> Value<'a> {
+                Value::Bytes(src)
+            }
+        }
+
         impl<'a> From<()> for Value<'a> {
             fn from(_: ()) -> Value<'a> {
                 Value::Tuplable(&())
@@ -104,6 +235,9 @@ macro_rules! value {
                         $(#[$attrs])*
                         $variant(v) => fmt::Debug::fmt(v, fmt),
                     )*
+                    Bytes(v) => debug_bytes(v, fmt),
+                    Display(v) => fmt::Display::fmt(v, fmt),
+                    Debug(v) => fmt::Debug::fmt(v, fmt),
                     Unit => ().fmt(fmt),
                 }
             }
@@ -202,14 +336,17 @@ value! {
 
     /// A Rust `i128` value
     ///
+    /// 128-bit integers are passed by reference, so that they do not make
+    /// every `Value` larger.
+    ///
     /// # Examples
     ///
     /// ```
     /// use valuable::Value;
     ///
-    /// let v = Value::I128(42);
+    /// let v = Value::I128(&42);
     /// ```
-    I128(i128),
+    I128(&'a i128),
 
     /// A Rust `isize` value
     ///
@@ -279,14 +416,17 @@ value! {
 
     /// A Rust `u128` value
     ///
+    /// 128-bit integers are passed by reference, so that they do not make
+    /// every `Value` larger.
+    ///
     /// # Examples
     ///
     /// ```
     /// use valuable::Value;
     ///
-    /// let v = Value::U128(42);
+    /// let v = Value::U128(&42);
     /// ```
-    U128(u128),
+    U128(&'a u128),
 
     /// A Rust `usize` value
     ///
@@ -324,8 +464,8 @@ value! {
     /// let err: io::Error = io::ErrorKind::Other.into();
     /// let v = Value::Error(&err);
     /// ```
-    #[cfg(feature = "std")]
-    Error(&'a (dyn std::error::Error +'static)),
+    #[cfg(any(feature = "std", not(valuable_no_core_error)))]
+    Error(&'a (dyn crate::Error +'static)),
 
     /// A Rust list value
     ///
@@ -404,6 +544,79 @@ value! {
     /// let v = Value::Tuplable(&my_tuple);
     /// ```
     Tuplable(&'a dyn Tuplable),
+
+    /// A string visited in chunks
+    ///
+    /// See [`Stringable`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Stringable, Valuable, Value, Visit};
+    ///
+    /// struct Repeat(char, usize);
+    ///
+    /// impl Valuable for Repeat {
+    ///     fn as_value(&self) -> Value<'_> {
+    ///         Value::Stringable(self)
+    ///     }
+    ///
+    ///     fn visit(&self, visit: &mut dyn Visit) {
+    ///         let mut buf = [0; 4];
+    ///         let chunk = self.0.encode_utf8(&mut buf);
+    ///         for _ in 0..self.1 {
+    ///             visit.visit_str_chunk(chunk);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Stringable for Repeat {
+    ///     fn size_hint(&self) -> (usize, Option<usize>) {
+    ///         let len = self.0.len_utf8() * self.1;
+    ///         (len, Some(len))
+    ///     }
+    /// }
+    ///
+    /// let repeat = Repeat('a', 3);
+    /// let v = Value::Stringable(&repeat);
+    /// assert_eq!(format!("{:?}", v), r#""aaa""#);
+    /// ```
+    Stringable(&'a dyn Stringable),
+}
+
+// Formats bytes as a byte string literal, e.g. `b"\x00abc"`.
+fn debug_bytes(bytes: &[u8], fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use fmt::Write;
+
+    fmt.write_str("b\"")?;
+    for &b in bytes {
+        for c in core::ascii::escape_default(b) {
+            fmt.write_char(c as char)?;
+        }
+    }
+    fmt.write_char('"')
+}
+
+// Returns a copy of `value` if it does not borrow any data.
+pub(crate) fn detach(value: &Value<'_>) -> Option<Value<'static>> {
+    Some(match *value {
+        Value::Bool(v) => Value::Bool(v),
+        Value::Char(v) => Value::Char(v),
+        Value::F32(v) => Value::F32(v),
+        Value::F64(v) => Value::F64(v),
+        Value::I8(v) => Value::I8(v),
+        Value::I16(v) => Value::I16(v),
+        Value::I32(v) => Value::I32(v),
+        Value::I64(v) => Value::I64(v),
+        Value::Isize(v) => Value::Isize(v),
+        Value::U8(v) => Value::U8(v),
+        Value::U16(v) => Value::U16(v),
+        Value::U32(v) => Value::U32(v),
+        Value::U64(v) => Value::U64(v),
+        Value::Usize(v) => Value::Usize(v),
+        Value::Unit => Value::Unit,
+        _ => return None,
+    })
 }
 
 impl Valuable for Value<'_> {
@@ -498,6 +711,40 @@ macro_rules! convert {
                 }
             }
 
+            /// Return a [`Number`] representation of `self`, if it is an
+            /// integer or a float.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::{Number, Value};
+            ///
+            /// assert_eq!(Value::U16(42).as_number(), Some(Number::from(42)));
+            /// assert_eq!(Value::F32(0.5).as_number(), Some(Number::from(0.5)));
+            /// assert_eq!(Value::Bool(true).as_number(), None);
+            /// ```
+            pub fn as_number(&self) -> Option<Number> {
+                use Value::*;
+
+                match *self {
+                    I8(v) => Some(v.into()),
+                    I16(v) => Some(v.into()),
+                    I32(v) => Some(v.into()),
+                    I64(v) => Some(v.into()),
+                    I128(&v) => Some(v.into()),
+                    Isize(v) => Some(v.into()),
+                    U8(v) => Some(v.into()),
+                    U16(v) => Some(v.into()),
+                    U32(v) => Some(v.into()),
+                    U64(v) => Some(v.into()),
+                    U128(&v) => Some(v.into()),
+                    Usize(v) => Some(v.into()),
+                    F32(v) => Some(v.into()),
+                    F64(v) => Some(v.into()),
+                    _ => None,
+                }
+            }
+
             $(
                 $(#[$attrs])*
                 pub fn $as(&self) -> Option<$ty> {
@@ -508,13 +755,13 @@ macro_rules! convert {
                         I16(v) => v.try_into().ok(),
                         I32(v) => v.try_into().ok(),
                         I64(v) => v.try_into().ok(),
-                        I128(v) => v.try_into().ok(),
+                        I128(&v) => v.try_into().ok(),
                         Isize(v) => v.try_into().ok(),
                         U8(v) => v.try_into().ok(),
                         U16(v) => v.try_into().ok(),
                         U32(v) => v.try_into().ok(),
                         U64(v) => v.try_into().ok(),
-                        U128(v) => v.try_into().ok(),
+                        U128(&v) => v.try_into().ok(),
                         Usize(v) => v.try_into().ok(),
                         _ => None,
                     }
@@ -538,6 +785,62 @@ macro_rules! convert {
                 }
             }
 
+            /// Return a `&[u8]` representation of `self`, if possible.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            ///
+            /// assert_eq!(Value::Bytes(b"hello").as_bytes(), Some(&b"hello"[..]));
+            /// assert_eq!(Value::String("hello").as_bytes(), None);
+            /// ```
+            pub fn as_bytes(&self) -> Option<&[u8]> {
+                match *self {
+                    Value::Bytes(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            /// Return a `&dyn Display` representation of `self`, if possible.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            ///
+            /// let args = format_args!("{}-{}", 1, 2);
+            ///
+            /// assert_eq!(Value::Display(&args).as_display().unwrap().to_string(), "1-2");
+            /// assert!(Value::String("1-2").as_display().is_none());
+            /// ```
+            pub fn as_display(&self) -> Option<&dyn fmt::Display> {
+                match *self {
+                    Value::Display(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            /// Return a `&dyn Debug` representation of `self`, if possible.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::Value;
+            /// use std::time::Duration;
+            ///
+            /// let timeout = Duration::from_secs(1);
+            ///
+            /// assert!(Value::Debug(&timeout).as_debug().is_some());
+            /// assert!(Value::Bool(true).as_debug().is_none());
+            /// ```
+            pub fn as_debug(&self) -> Option<&dyn fmt::Debug> {
+                match *self {
+                    Value::Debug(v) => Some(v),
+                    _ => None,
+                }
+            }
+
             /// Return a `&Path` representation of `self`, if possible.
             ///
             /// # Examples
@@ -572,9 +875,9 @@ macro_rules! convert {
             /// assert!(Value::Error(&err).as_error().is_some());
             /// assert!(Value::Bool(true).as_error().is_none());
             /// ```
-            #[cfg(feature = "std")]
-            pub fn as_error(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            pub fn as_error(&self) -> Option<&(dyn crate::Error + 'static)> {
                 match *self {
+                    #[cfg(any(feature = "std", not(valuable_no_core_error)))]
                     Value::Error(v) => Some(v),
                     _ => None,
                 }
@@ -689,6 +992,90 @@ macro_rules! convert {
                     _ => None,
                 }
             }
+
+            /// Return a `&dyn Stringable` representation of `self`, if
+            /// possible.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::{Stringable, Valuable, Value, Visit};
+            ///
+            /// struct Chunks(Vec<&'static str>);
+            ///
+            /// impl Valuable for Chunks {
+            ///     fn as_value(&self) -> Value<'_> {
+            ///         Value::Stringable(self)
+            ///     }
+            ///
+            ///     fn visit(&self, visit: &mut dyn Visit) {
+            ///         for chunk in &self.0 {
+            ///             visit.visit_str_chunk(chunk);
+            ///         }
+            ///     }
+            /// }
+            ///
+            /// impl Stringable for Chunks {
+            ///     fn size_hint(&self) -> (usize, Option<usize>) {
+            ///         let len = self.0.iter().map(|chunk| chunk.len()).sum();
+            ///         (len, Some(len))
+            ///     }
+            /// }
+            ///
+            /// let chunks = Chunks(vec!["he", "llo"]);
+            /// let v = chunks.as_value();
+            ///
+            /// assert_eq!(v.as_stringable().unwrap().to_string(), "hello");
+            /// assert!(Value::String("hello").as_stringable().is_none());
+            /// ```
+            pub fn as_stringable(&self) -> Option<&dyn Stringable> {
+                match *self {
+                    Value::Stringable(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            /// Return a reference to the concrete value behind `self`, if it
+            /// is of type `T`.
+            ///
+            /// Structs, enums, tuples, lists, and maps can be downcast if
+            /// their type opts in through the `as_any()` method of the
+            /// corresponding trait, see [`Structable::as_any()`]. All other
+            /// values return `None`; errors can be downcast through
+            /// [`as_error()`](Value::as_error) instead.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use valuable::{Valuable, Value};
+            ///
+            /// #[derive(Valuable)]
+            /// struct RequestId(u64);
+            ///
+            /// #[derive(Valuable)]
+            /// struct Span {
+            ///     name: &'static str,
+            /// }
+            ///
+            /// fn fast_path(value: Value<'_>) -> Option<u64> {
+            ///     value.downcast_ref::<RequestId>().map(|id| id.0)
+            /// }
+            ///
+            /// assert_eq!(fast_path(RequestId(7).as_value()), Some(7));
+            /// assert_eq!(fast_path(Span { name: "poll" }.as_value()), None);
+            /// assert_eq!(fast_path(Value::U64(7)), None);
+            /// ```
+            pub fn downcast_ref<T: Any>(&self) -> Option<&'a T> {
+                let any = match *self {
+                    Value::Structable(v) => v.as_any(),
+                    Value::Enumerable(v) => v.as_any(),
+                    Value::Tuplable(v) => v.as_any(),
+                    Value::Listable(v) => v.as_any(),
+                    Value::Mappable(v) => v.as_any(),
+                    _ => None,
+                };
+                any?.downcast_ref()
+            }
         }
     }
 }
@@ -747,9 +1134,9 @@ convert! {
     /// use valuable::Value;
     ///
     /// assert_eq!(Value::I64(42).as_i64(), Some(42));
-    /// assert_eq!(Value::I128(42).as_i64(), Some(42));
+    /// assert_eq!(Value::I128(&42).as_i64(), Some(42));
     ///
-    /// assert_eq!(Value::I128(i128::MAX).as_i64(), None);
+    /// assert_eq!(Value::I128(&i128::MAX).as_i64(), None);
     /// assert_eq!(Value::Bool(true).as_i64(), None);
     /// ```
     i64 => as_i64,
@@ -761,10 +1148,10 @@ convert! {
     /// ```
     /// use valuable::Value;
     ///
-    /// assert_eq!(Value::I128(42).as_i128(), Some(42));
-    /// assert_eq!(Value::U128(42).as_i128(), Some(42));
+    /// assert_eq!(Value::I128(&42).as_i128(), Some(42));
+    /// assert_eq!(Value::U128(&42).as_i128(), Some(42));
     ///
-    /// assert_eq!(Value::U128(u128::MAX).as_i128(), None);
+    /// assert_eq!(Value::U128(&u128::MAX).as_i128(), None);
     /// assert_eq!(Value::Bool(true).as_i128(), None);
     /// ```
     i128 => as_i128,
@@ -837,9 +1224,9 @@ convert! {
     /// use valuable::Value;
     ///
     /// assert_eq!(Value::U64(42).as_u64(), Some(42));
-    /// assert_eq!(Value::U128(42).as_u64(), Some(42));
+    /// assert_eq!(Value::U128(&42).as_u64(), Some(42));
     ///
-    /// assert_eq!(Value::U128(u128::MAX).as_u64(), None);
+    /// assert_eq!(Value::U128(&u128::MAX).as_u64(), None);
     /// assert_eq!(Value::Bool(true).as_u64(), None);
     /// ```
     u64 => as_u64,
@@ -851,7 +1238,7 @@ convert! {
     /// ```
     /// use valuable::Value;
     ///
-    /// assert_eq!(Value::U128(42).as_u128(), Some(42));
+    /// assert_eq!(Value::U128(&42).as_u128(), Some(42));
     /// assert_eq!(Value::I32(42).as_u128(), Some(42));
     ///
     /// assert_eq!(Value::I32(-5).as_u128(), None);
@@ -874,3 +1261,61 @@ convert! {
     /// ```
     usize => as_usize,
 }
+
+macro_rules! try_from {
+    (
+        $(
+            $ty:ty => $variant:ident,
+        )*
+    ) => {
+        $(
+            impl<'a> TryFrom<Value<'a>> for $ty {
+                type Error = TryFromValueError;
+
+                fn try_from(value: Value<'a>) -> Result<$ty, TryFromValueError> {
+                    match value {
+                        Value::$variant(v) => Ok(v),
+                        _ => Err(TryFromValueError::invalid_type(stringify!($ty))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from! {
+    bool => Bool,
+    char => Char,
+}
+
+impl<'a> TryFrom<Value<'a>> for &'a str {
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value<'a>) -> Result<&'a str, TryFromValueError> {
+        match value {
+            Value::String(v) => Ok(v),
+            _ => Err(TryFromValueError::invalid_type("&str")),
+        }
+    }
+}
+
+macro_rules! try_from_number {
+    ($($ty:ty),*) => {
+        $(
+            /// Numbers are converted if they are exactly representable in the
+            /// target type, see [`Number`].
+            impl TryFrom<Value<'_>> for $ty {
+                type Error = TryFromValueError;
+
+                fn try_from(value: Value<'_>) -> Result<$ty, TryFromValueError> {
+                    match value.as_number() {
+                        Some(number) => number.try_into(),
+                        None => Err(TryFromValueError::invalid_type(stringify!($ty))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+try_from_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
@@ -1,5 +1,7 @@
 use crate::*;
 
+use core::ops::ControlFlow;
+
 /// Traverse a value's fields and variants.
 ///
 /// Each method of the `Visit` trait is a hook that enables the implementor to
@@ -179,9 +181,13 @@ pub trait Visit {
     /// Visit a struct or enum's named fields.
     ///
     /// When the struct/enum is statically defined, all fields are known ahead
-    /// of time and `visit_named_fields` is called once with all field values.
-    /// When the struct/enum is dynamic, then the `visit_named_fields` method
-    /// may be called multiple times.
+    /// of time and `visit_named_fields` is usually called once with all field
+    /// values. Very wide structs may pass their fields in several chunks
+    /// instead, or one at a time through [`visit_named_field()`]. When the
+    /// struct/enum is dynamic, then the `visit_named_fields` method may be
+    /// called multiple times.
+    ///
+    /// [`visit_named_field()`]: Visit::visit_named_field
     ///
     /// See [`Structable`] and [`Enumerable`] for static vs. dynamic details.
     ///
@@ -226,10 +232,99 @@ pub trait Visit {
         let _ = named_values;
     }
 
+    /// Visit a single named field of a struct or enum.
+    ///
+    /// Structs with many fields may pass their fields one at a time, instead
+    /// of building an array of all field values for [`visit_named_fields()`].
+    /// `#[derive(Valuable)]` does so for structs with the
+    /// `#[valuable(chunk_size = 1)]` attribute. As with dynamic structs, a
+    /// visitor that breaks through [`control_flow()`] stops receiving the
+    /// remaining fields.
+    ///
+    /// The default implementation calls [`visit_named_fields()`] with a
+    /// [`NamedValues`] containing only `field`, so that visitors implementing
+    /// [`visit_named_fields()`] keep working. Looking up `field` with
+    /// [`NamedValues::get()`] still works when it is borrowed from the struct's
+    /// field definitions.
+    ///
+    /// [`visit_named_fields()`]: Visit::visit_named_fields
+    /// [`control_flow()`]: Visit::control_flow
+    ///
+    /// # Examples
+    ///
+    /// Collecting a few fields of a wide struct.
+    ///
+    /// ```
+    /// use valuable::{NamedField, Valuable, Value, Visit};
+    /// use std::ops::ControlFlow;
+    ///
+    /// #[derive(Valuable)]
+    /// #[valuable(chunk_size = 1)]
+    /// struct Telemetry {
+    ///     host: &'static str,
+    ///     cpu: f32,
+    ///     memory: u64,
+    ///     disk: u64,
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Collect {
+    ///     host: Option<String>,
+    ///     cpu: Option<f32>,
+    ///     visited: usize,
+    /// }
+    ///
+    /// impl Visit for Collect {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         if let Value::Structable(v) = value {
+    ///             v.visit(self);
+    ///         }
+    ///     }
+    ///
+    ///     fn visit_named_field(&mut self, field: &NamedField<'_>, value: Value<'_>) {
+    ///         self.visited += 1;
+    ///         match field.name() {
+    ///             "host" => self.host = value.as_str().map(str::to_string),
+    ///             "cpu" => self.cpu = value.as_f32(),
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///
+    ///     fn control_flow(&self) -> ControlFlow<()> {
+    ///         if self.host.is_some() && self.cpu.is_some() {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let telemetry = Telemetry {
+    ///     host: "db-1",
+    ///     cpu: 0.5,
+    ///     memory: 1024,
+    ///     disk: 2048,
+    /// };
+    ///
+    /// let mut collect = Collect::default();
+    /// valuable::visit(&telemetry, &mut collect);
+    ///
+    /// assert_eq!(collect.host.as_deref(), Some("db-1"));
+    /// assert_eq!(collect.cpu, Some(0.5));
+    /// assert_eq!(collect.visited, 2);
+    /// ```
+    fn visit_named_field(&mut self, field: &NamedField<'_>, value: Value<'_>) {
+        self.visit_named_fields(&NamedValues::new(
+            core::slice::from_ref(field),
+            core::slice::from_ref(&value),
+        ));
+    }
+
     /// Visit a struct or enum's unnamed fields.
     ///
     /// When the struct/enum is statically defined, all fields are known ahead
-    /// of time and `visit_unnamed_fields` is called once with all field values.
+    /// of time and `visit_unnamed_fields` is usually called once with all
+    /// field values, or once per chunk of fields for very wide structs.
     /// When the struct/enum is dynamic, then the `visit_unnamed_fields` method
     /// may be called multiple times.
     ///
@@ -320,10 +415,135 @@ pub trait Visit {
     /// ```
     fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
         for value in slice {
+            if let ControlFlow::Break(()) = self.control_flow() {
+                break;
+            }
+
             self.visit_value(value);
         }
     }
 
+    /// Visit a chunk of a string.
+    ///
+    /// This method is called by [`Stringable`] types, which present a string
+    /// as a sequence of chunks instead of a single `&str`. It is called once
+    /// for each chunk, in order, and the concatenation of all chunks is the
+    /// string. Chunks may be empty.
+    ///
+    /// The default implementation passes the chunk to [`visit_value()`] as a
+    /// [`Value::String`].
+    ///
+    /// [`visit_value()`]: Visit::visit_value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{Stringable, Valuable, Value, Visit};
+    ///
+    /// struct Lines<'a>(&'a [&'a str]);
+    ///
+    /// impl Valuable for Lines<'_> {
+    ///     fn as_value(&self) -> Value<'_> {
+    ///         Value::Stringable(self)
+    ///     }
+    ///
+    ///     fn visit(&self, visit: &mut dyn Visit) {
+    ///         for line in self.0 {
+    ///             visit.visit_str_chunk(line);
+    ///             visit.visit_str_chunk("\n");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Stringable for Lines<'_> {
+    ///     fn size_hint(&self) -> (usize, Option<usize>) {
+    ///         let len = self.0.iter().map(|line| line.len() + 1).sum();
+    ///         (len, Some(len))
+    ///     }
+    /// }
+    ///
+    /// struct Collect(String);
+    ///
+    /// impl Visit for Collect {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         if let Value::Stringable(v) = value {
+    ///             v.visit(self);
+    ///         }
+    ///     }
+    ///
+    ///     fn visit_str_chunk(&mut self, chunk: &str) {
+    ///         self.0.push_str(chunk);
+    ///     }
+    /// }
+    ///
+    /// let mut collect = Collect(String::new());
+    /// valuable::visit(&Lines(&["a", "b"]), &mut collect);
+    /// assert_eq!(collect.0, "a\nb\n");
+    /// ```
+    fn visit_str_chunk(&mut self, chunk: &str) {
+        self.visit_value(Value::String(chunk));
+    }
+
+    /// Visit a list of structs sharing the same definition.
+    ///
+    /// This method exists as an optimization when visiting [`Listable`] types
+    /// containing structs. Lists of `#[derive(Valuable)]` structs, and lists
+    /// built with [`StructRows::from_columns()`], pass all of their elements
+    /// at once to `visit_struct_rows`, so that the visitor can handle the
+    /// shared [`StructDef`] once, then the fields of each struct, or whole
+    /// columns of fields. See [`StructRows`] for details.
+    ///
+    /// As with [`visit_primitive_slice()`], the method may be called multiple
+    /// times for a single `Listable` type.
+    ///
+    /// The default implementation passes each struct to [`visit_value()`].
+    ///
+    /// [`visit_primitive_slice()`]: Visit::visit_primitive_slice
+    /// [`visit_value()`]: Visit::visit_value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use valuable::{StructRows, Valuable, Value, Visit};
+    ///
+    /// #[derive(Valuable)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// struct Names(Vec<String>);
+    ///
+    /// impl Visit for Names {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         if let Value::Listable(v) = value {
+    ///             v.visit(self);
+    ///         }
+    ///     }
+    ///
+    ///     fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+    ///         let name = rows.definition().name();
+    ///         self.0.push(format!("{} x {}", rows.len(), name));
+    ///     }
+    /// }
+    ///
+    /// let points = vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+    ///
+    /// let mut names = Names(vec![]);
+    /// valuable::visit(&points, &mut names);
+    /// assert_eq!(names.0, ["2 x Point"]);
+    /// ```
+    fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+        rows.for_each_row(|row| {
+            if let ControlFlow::Break(()) = self.control_flow() {
+                return ControlFlow::Break(());
+            }
+
+            self.visit_value(Value::Structable(row));
+            ControlFlow::Continue(())
+        });
+    }
+
     /// Visit a `Mappable`'s entries.
     ///
     /// The `visit_entry` method is called once for each entry contained by a
@@ -361,6 +581,66 @@ pub trait Visit {
     fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
         let _ = (key, value);
     }
+
+    /// Returns whether the traversal should continue.
+    ///
+    /// Implementations of [`Valuable`] that call the visitor more than once,
+    /// such as lists, maps, and dynamic structs, check this method before each
+    /// call and stop visiting the remaining items once it returns
+    /// [`ControlFlow::Break`]. This lets a visitor stop early after it has
+    /// found what it was looking for, or after it encountered an error. The
+    /// visitor is responsible for holding on to the result or error itself.
+    ///
+    /// Breaking is best-effort: a single call to [`visit_named_fields()`] or
+    /// [`visit_unnamed_fields()`] still passes all of its values at once.
+    ///
+    /// The default implementation always returns [`ControlFlow::Continue`].
+    ///
+    /// [`visit_named_fields()`]: Visit::visit_named_fields
+    /// [`visit_unnamed_fields()`]: Visit::visit_unnamed_fields
+    ///
+    /// # Examples
+    ///
+    /// Stop after finding the first even number
+    ///
+    /// ```
+    /// use valuable::{Valuable, Value, Visit};
+    /// use std::ops::ControlFlow;
+    ///
+    /// struct FindEven {
+    ///     found: Option<i32>,
+    ///     visited: usize,
+    /// }
+    ///
+    /// impl Visit for FindEven {
+    ///     fn visit_value(&mut self, value: Value<'_>) {
+    ///         self.visited += 1;
+    ///
+    ///         match value {
+    ///             Value::Listable(v) => v.visit(self),
+    ///             Value::I32(v) if v % 2 == 0 => self.found = Some(v),
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///
+    ///     fn control_flow(&self) -> ControlFlow<()> {
+    ///         match self.found {
+    ///             Some(_) => ControlFlow::Break(()),
+    ///             None => ControlFlow::Continue(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut visit = FindEven { found: None, visited: 0 };
+    /// valuable::visit(&vec![1, 3, 4, 5, 7], &mut visit);
+    ///
+    /// assert_eq!(visit.found, Some(4));
+    /// // The list itself, then 1, 3, and 4.
+    /// assert_eq!(visit.visited, 4);
+    /// ```
+    fn control_flow(&self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
 }
 
 macro_rules! deref {
@@ -381,6 +661,10 @@ macro_rules! deref {
                     T::visit_named_fields(&mut **self, named_values)
                 }
 
+                fn visit_named_field(&mut self, field: &NamedField<'_>, value: Value<'_>) {
+                    T::visit_named_field(&mut **self, field, value)
+                }
+
                 fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
                     T::visit_unnamed_fields(&mut **self, values)
                 }
@@ -389,9 +673,21 @@ macro_rules! deref {
                     T::visit_primitive_slice(&mut **self, slice)
                 }
 
+                fn visit_str_chunk(&mut self, chunk: &str) {
+                    T::visit_str_chunk(&mut **self, chunk)
+                }
+
+                fn visit_struct_rows(&mut self, rows: &StructRows<'_>) {
+                    T::visit_struct_rows(&mut **self, rows)
+                }
+
                 fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
                     T::visit_entry(&mut **self, key, value)
                 }
+
+                fn control_flow(&self) -> ControlFlow<()> {
+                    T::control_flow(&**self)
+                }
             }
         )*
     };
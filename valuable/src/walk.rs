@@ -0,0 +1,322 @@
+//! Traversing nested values as a stream of events.
+//!
+//! Inspecting a value with [`Visit`] means writing a visitor for every level
+//! of nesting, and calling back into [`Valuable::visit`] to descend. The
+//! [`walk()`] function does this once for all values: it traverses a value
+//! and its nested values depth-first, and reports what it finds to a single
+//! [`Handler`] as a flat sequence of [`Event`]s.
+//!
+//! Every value produces either a single [`Event::Value`], for primitive
+//! values, or an `Enter*` event, followed by the events of its nested values,
+//! followed by [`Event::Leave`]. Named struct fields are announced with
+//! [`Event::Field`], and map entries with [`Event::Entry`]. Each event comes
+//! with the [`Location`] of the value it describes, which also gives the
+//! current depth.
+//!
+//! The handler controls the traversal by returning an [`Action`]: it can skip
+//! the contents of a value, for example to limit the depth of the traversal,
+//! or stop altogether.
+//!
+//! Nested values are only borrowed for the duration of the [`visit`] call
+//! that provides them, so they cannot be queued on an explicit stack: the
+//! traversal still descends through one `visit` call per level of nesting,
+//! and uses stack space proportional to the depth of the value. To bound it,
+//! the nested values of values at [`Handler::max_depth()`] are skipped, as if
+//! the handler had returned [`Action::Skip`]. The handler itself does not
+//! need to recurse or keep a stack of visitors.
+//!
+//! [`visit`]: Valuable::visit
+//!
+//! # Examples
+//!
+//! Print a value as an indented outline:
+//!
+//! ```
+//! use valuable::path::Location;
+//! use valuable::walk::{walk, Action, Event, Handler};
+//! use valuable::Valuable;
+//!
+//! #[derive(Valuable)]
+//! struct Person {
+//!     name: String,
+//!     tags: Vec<&'static str>,
+//! }
+//!
+//! struct Outline(String);
+//!
+//! impl Handler for Outline {
+//!     fn event(&mut self, location: &Location<'_>, event: Event<'_>) -> Action {
+//!         let indent = "  ".repeat(location.depth());
+//!         match event {
+//!             Event::EnterStruct(s) => {
+//!                 self.0 += &format!("{}{}\n", indent, s.definition().name());
+//!             }
+//!             Event::EnterList(_) => self.0 += &format!("{}{}:\n", indent, location),
+//!             Event::Value(value) => {
+//!                 self.0 += &format!("{}{} = {:?}\n", indent, location, value);
+//!             }
+//!             _ => {}
+//!         }
+//!         Action::Continue
+//!     }
+//! }
+//!
+//! let person = Person {
+//!     name: "Ferris".to_string(),
+//!     tags: vec!["crab"],
+//! };
+//!
+//! let mut outline = Outline(String::new());
+//! walk(&person, &mut outline);
+//!
+//! assert_eq!(
+//!     outline.0,
+//!     "Person\n  \
+//!        .name = \"Ferris\"\n  \
+//!        .tags:\n    \
+//!          .tags[0] = \"crab\"\n",
+//! );
+//! ```
+
+use crate::path::{Location, Segment};
+use crate::*;
+
+use core::ops::ControlFlow;
+
+// Depth of the deepest values that are entered by default.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Traverses `value` and its nested values, passing [`Event`]s to
+/// `handler`.
+///
+/// See the [module documentation](self) for details.
+pub fn walk(value: &dyn Valuable, handler: &mut dyn Handler) {
+    let mut walker = Walker {
+        handler,
+        stopped: false,
+    };
+    walker.value(&Location::root(), value.as_value());
+}
+
+/// Receives the [`Event`]s of a traversal started with [`walk()`].
+///
+/// `Handler` is implemented for closures taking a [`Location`] and an
+/// [`Event`].
+pub trait Handler {
+    /// Handles a single event.
+    ///
+    /// `location` is the location of the value that the event describes.
+    /// For [`Event::Field`] and [`Event::Entry`], it is the location of the
+    /// field or entry value.
+    fn event(&mut self, location: &Location<'_>, event: Event<'_>) -> Action;
+
+    /// Returns the maximum depth of the traversal.
+    ///
+    /// Values at this depth are still reported, but their nested values are
+    /// skipped, as if [`event()`](Handler::event) had returned
+    /// [`Action::Skip`] for them. The root value is at depth 0, so with a
+    /// maximum depth of 0, only the root value is reported.
+    ///
+    /// Defaults to 128.
+    fn max_depth(&self) -> usize {
+        DEFAULT_MAX_DEPTH
+    }
+}
+
+impl<F> Handler for F
+where
+    F: FnMut(&Location<'_>, Event<'_>) -> Action,
+{
+    fn event(&mut self, location: &Location<'_>, event: Event<'_>) -> Action {
+        self(location, event)
+    }
+}
+
+/// An event of a traversal started with [`walk()`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum Event<'a> {
+    /// A primitive value, which has no nested values.
+    Value(Value<'a>),
+
+    /// The start of a struct.
+    EnterStruct(&'a dyn Structable),
+
+    /// The start of an enum. The events for the fields of the current
+    /// variant follow.
+    EnterEnum(&'a dyn Enumerable),
+
+    /// The start of a tuple.
+    EnterTuple(&'a dyn Tuplable),
+
+    /// The start of a list.
+    EnterList(&'a dyn Listable),
+
+    /// The start of a map.
+    EnterMap(&'a dyn Mappable),
+
+    /// A named field of a struct or enum variant. The events for the field's
+    /// value follow.
+    Field(&'a str),
+
+    /// An entry of a map with the given key. The events for the entry's value
+    /// follow.
+    Entry(Value<'a>),
+
+    /// The end of the value started by the matching `Enter*` event.
+    Leave,
+}
+
+/// Returned by a [`Handler`] to control the traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Continue the traversal.
+    Continue,
+
+    /// Skip the nested values of the value that was just entered, or the
+    /// value of the field or entry that was just announced. [`Event::Leave`]
+    /// is still emitted for an entered value. Has no effect for other
+    /// events.
+    Skip,
+
+    /// Stop the traversal. No further events are emitted.
+    Stop,
+}
+
+struct Walker<'h> {
+    handler: &'h mut dyn Handler,
+    stopped: bool,
+}
+
+impl Walker<'_> {
+    fn emit(&mut self, location: &Location<'_>, event: Event<'_>) -> Action {
+        let action = self.handler.event(location, event);
+        if action == Action::Stop {
+            self.stopped = true;
+        }
+        action
+    }
+
+    fn value(&mut self, location: &Location<'_>, value: Value<'_>) {
+        let event = match value {
+            Value::Structable(v) => Event::EnterStruct(v),
+            Value::Enumerable(v) => Event::EnterEnum(v),
+            Value::Tuplable(v) => Event::EnterTuple(v),
+            Value::Listable(v) => Event::EnterList(v),
+            Value::Mappable(v) => Event::EnterMap(v),
+            value => {
+                self.emit(location, Event::Value(value));
+                return;
+            }
+        };
+
+        let action = match self.emit(location, event) {
+            Action::Continue if location.depth() >= self.handler.max_depth() => Action::Skip,
+            action => action,
+        };
+
+        match action {
+            Action::Continue => match value {
+                Value::Enumerable(v) => {
+                    let variant = v.variant();
+                    let location = location.child(Segment::Variant(variant.name()));
+                    v.visit(&mut Children::new(self, &location));
+                }
+                Value::Structable(v) => v.visit(&mut Children::new(self, location)),
+                Value::Tuplable(v) => v.visit(&mut Children::new(self, location)),
+                Value::Listable(v) => v.visit(&mut Children::new(self, location)),
+                Value::Mappable(v) => v.visit(&mut Children::new(self, location)),
+                _ => unreachable!(),
+            },
+            Action::Skip => {}
+            Action::Stop => return,
+        }
+
+        if !self.stopped {
+            self.emit(location, Event::Leave);
+        }
+    }
+}
+
+// Walks the nested values of a single value.
+struct Children<'a, 'b, 'h> {
+    walker: &'a mut Walker<'h>,
+    location: &'a Location<'b>,
+    // Index of the next unnamed field or list element, counted across calls.
+    index: usize,
+}
+
+impl<'a, 'b, 'h> Children<'a, 'b, 'h> {
+    fn new(walker: &'a mut Walker<'h>, location: &'a Location<'b>) -> Self {
+        Children {
+            walker,
+            location,
+            index: 0,
+        }
+    }
+
+    fn next_index(&mut self) -> usize {
+        let index = self.index;
+        self.index += 1;
+        index
+    }
+}
+
+impl Visit for Children<'_, '_, '_> {
+    fn visit_value(&mut self, value: Value<'_>) {
+        if self.walker.stopped {
+            return;
+        }
+        let location = self.location.child(Segment::Element(self.next_index()));
+        self.walker.value(&location, value);
+    }
+
+    fn visit_primitive_slice(&mut self, slice: Slice<'_>) {
+        for value in slice {
+            if self.walker.stopped {
+                break;
+            }
+            self.visit_value(value);
+        }
+    }
+
+    fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+        for (field, value) in named_values {
+            if self.walker.stopped {
+                break;
+            }
+            let location = self.location.child(Segment::Field(field.name()));
+            if let Action::Continue = self.walker.emit(&location, Event::Field(field.name())) {
+                self.walker.value(&location, *value);
+            }
+        }
+    }
+
+    fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+        for value in values {
+            if self.walker.stopped {
+                break;
+            }
+            let location = self.location.child(Segment::Index(self.next_index()));
+            self.walker.value(&location, *value);
+        }
+    }
+
+    fn visit_entry(&mut self, key: Value<'_>, value: Value<'_>) {
+        if self.walker.stopped {
+            return;
+        }
+        let location = self.location.child(Segment::Key(key));
+        if let Action::Continue = self.walker.emit(&location, Event::Entry(key)) {
+            self.walker.value(&location, value);
+        }
+    }
+
+    fn control_flow(&self) -> ControlFlow<()> {
+        if self.walker.stopped {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}